@@ -1,8 +1,20 @@
-use slash::{Context, Filter};
+use slash::{Client, Config, Context, Filter};
 
 #[tokio::main]
-async fn main() {
+async fn main() -> slash::Result<()> {
     let ping = slash::command("ping")
         .description("Test the bot's latency.")
-        .map(|context: Context| context.reply("pong!"));
+        .then(|context: Context| async move {
+            let reply = match context.gateway_latency() {
+                Some(latency) => format!("pong! ({} ms)", latency.as_millis()),
+                None => "pong!".to_string(),
+            };
+            context.reply(reply).await
+        });
+
+    Client::builder()
+        .config(Config::from_env()?)
+        .command(ping)
+        .run()
+        .await
 }
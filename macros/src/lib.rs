@@ -0,0 +1,198 @@
+//! Procedural macros for slash, re-exported by it with the `macros` feature.
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    parenthesized, parse::Parse, parse_macro_input, spanned::Spanned, Attribute, Data, DeriveInput,
+    Error, Expr, ExprLit, Field, Fields, Ident, Lit, LitStr, Meta, Result, Token,
+};
+
+/// Derive `slash::options::CommandOptions` for a struct whose fields are the options of a
+/// command, read with `slash::options::FromOption`.
+///
+/// Each field is declared as an option of the same name, required unless it is an `Option`.
+/// Its description is taken from its doc comment, and can be customized with the `option`
+/// attribute:
+///
+/// - `description = "..."` overrides the doc comment.
+/// - `rename = "..."` names the option differently than the field.
+/// - `min = 0` and `max = 7` bound `INTEGER` and `NUMBER` options.
+/// - `choices("spam", "abuse")` restricts the option to a set of values.
+/// - `channel_types(GuildForum, GuildStageVoice)` only shows channels of the given
+///   `ChannelType`s for `CHANNEL` options.
+#[proc_macro_derive(CommandOptions, attributes(option))]
+pub fn derive_command_options(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// The options of a field, from its doc comment and `option` attribute.
+struct OptionAttrs {
+    name: String,
+    description: Option<String>,
+    min: Option<Lit>,
+    max: Option<Lit>,
+    choices: Vec<Lit>,
+    channel_types: Vec<Ident>,
+}
+
+fn expand(input: DeriveInput) -> Result<TokenStream2> {
+    let fields = match input.data {
+        Data::Struct(ref data) => match data.fields {
+            Fields::Named(ref fields) => &fields.named,
+            _ => return Err(Error::new(input.span(), "options must be named fields")),
+        },
+        _ => {
+            return Err(Error::new(
+                input.span(),
+                "CommandOptions can only be derived for structs",
+            ))
+        }
+    };
+
+    let mut declarations = Vec::new();
+    let mut extractions = Vec::new();
+    for field in fields {
+        let ident = field.ident.as_ref().expect("named fields have identifiers");
+        let ty = &field.ty;
+        let attrs = parse_attrs(field)?;
+        let name = &attrs.name;
+        let description = attrs.description.ok_or_else(|| {
+            Error::new(
+                field.span(),
+                "options need a description, from a doc comment or `#[option(description = \"...\")]`",
+            )
+        })?;
+        let min = attrs.min.iter().map(choice_value);
+        let max = attrs.max.iter().map(choice_value);
+        let choices = attrs.choices.iter().map(|choice| {
+            let label = match choice {
+                Lit::Str(lit) => lit.value(),
+                Lit::Int(lit) => lit.base10_digits().to_string(),
+                Lit::Float(lit) => lit.base10_digits().to_string(),
+                _ => String::new(),
+            };
+            let value = choice_value(choice);
+            quote! {
+                ::slash::model::command::CommandOptionChoice {
+                    name: #label.to_string(),
+                    value: #value,
+                }
+            }
+        });
+        let channel_types = attrs
+            .channel_types
+            .iter()
+            .map(|ty| quote!(::slash::model::channel::ChannelType::#ty));
+
+        declarations.push(quote! {{
+            let mut option = ::slash::model::command::ApplicationCommandOption::new(
+                <#ty as ::slash::options::OptionType>::TYPE,
+                #name,
+                #description,
+            );
+            option.required = <#ty as ::slash::options::OptionType>::REQUIRED;
+            #(option.min_value = ::std::option::Option::Some(#min);)*
+            #(option.max_value = ::std::option::Option::Some(#max);)*
+            option.choices = ::std::vec![#(#choices),*];
+            option.channel_types = ::std::vec![#(#channel_types),*];
+            option
+        }});
+        extractions.push(quote! {
+            #ident: context.option::<#ty>(#name)?,
+        });
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::slash::options::CommandOptions for #ident #ty_generics #where_clause {
+            fn options() -> ::std::vec::Vec<::slash::model::command::ApplicationCommandOption> {
+                ::std::vec![#(#declarations),*]
+            }
+
+            fn from_context(
+                context: &::slash::Context,
+            ) -> ::std::result::Result<Self, ::slash::reject::Rejection> {
+                ::std::result::Result::Ok(Self {
+                    #(#extractions)*
+                })
+            }
+        }
+    })
+}
+
+fn parse_attrs(field: &Field) -> Result<OptionAttrs> {
+    let ident = field.ident.as_ref().expect("named fields have identifiers");
+    let mut attrs = OptionAttrs {
+        name: ident.to_string(),
+        description: doc_comment(&field.attrs),
+        min: None,
+        max: None,
+        choices: Vec::new(),
+        channel_types: Vec::new(),
+    };
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("option"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("description") {
+                attrs.description = Some(meta.value()?.parse::<LitStr>()?.value());
+            } else if meta.path.is_ident("rename") {
+                attrs.name = meta.value()?.parse::<LitStr>()?.value();
+            } else if meta.path.is_ident("min") {
+                attrs.min = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("max") {
+                attrs.max = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("choices") {
+                let content;
+                parenthesized!(content in meta.input);
+                attrs
+                    .choices
+                    .extend(content.parse_terminated(Lit::parse, Token![,])?);
+            } else if meta.path.is_ident("channel_types") {
+                let content;
+                parenthesized!(content in meta.input);
+                attrs
+                    .channel_types
+                    .extend(content.parse_terminated(Ident::parse, Token![,])?);
+            } else {
+                return Err(meta.error("unknown option attribute"));
+            }
+            Ok(())
+        })?;
+    }
+    Ok(attrs)
+}
+
+/// Returns the doc comment of a field, joined into a single line.
+fn doc_comment(attrs: &[Attribute]) -> Option<String> {
+    let lines: Vec<String> = attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("doc"))
+        .filter_map(|attr| match attr.meta {
+            Meta::NameValue(ref meta) => match meta.value {
+                Expr::Lit(ExprLit {
+                    lit: Lit::Str(ref lit),
+                    ..
+                }) => Some(lit.value().trim().to_string()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect();
+    Some(lines.join(" ")).filter(|doc| !doc.is_empty())
+}
+
+/// Create the `ChoiceValue` of a literal.
+fn choice_value(lit: &Lit) -> TokenStream2 {
+    match lit {
+        Lit::Int(lit) => quote!(::slash::model::command::ChoiceValue::Integer(#lit)),
+        Lit::Float(lit) => quote!(::slash::model::command::ChoiceValue::Double(#lit)),
+        lit => quote!(::slash::model::command::ChoiceValue::String(#lit.to_string())),
+    }
+}
@@ -0,0 +1,73 @@
+//! Integrates the interactions endpoint with [`axum`](::axum).
+use ::axum::routing::{post_service, MethodRouter};
+
+use crate::{client::Client, errors::Result, filter::Filter, reject::Rejection};
+
+/// Create a route serving the interactions endpoint, which verifies that requests were sent by
+/// Discord and dispatches their interactions to `filter`.
+///
+/// Handlers can respond to their interaction, but no bot token is set to make other REST
+/// requests with. To do so, configure a [`Client`] and mount its
+/// [`service`](Client::service) with [`post_service`] instead.
+///
+/// # Example
+///
+/// ```no_run
+/// use slash::{Context, Filter};
+///
+/// # fn run() -> slash::Result<axum::Router> {
+/// let ping = slash::command("ping")
+///     .description("Test the bot's latency.")
+///     .then(|context: Context| async move { context.reply("pong!").await });
+///
+/// let app = axum::Router::new().route(
+///     "/interactions",
+///     slash::axum::interactions_route(ping, "<public key>")?,
+/// );
+/// # Ok(app)
+/// # }
+/// ```
+pub fn interactions_route<F, S>(filter: F, public_key: &str) -> Result<MethodRouter<S>>
+where
+    F: Filter + Send + Sync + 'static,
+    F::Future: 'static,
+    F::Error: Into<Rejection>,
+    S: Clone + Send + Sync + 'static,
+{
+    let service = Client::builder()
+        .command(filter)
+        .public_key(public_key)
+        .build_webhook()?
+        .service()?;
+    Ok(post_service(service))
+}
+
+#[cfg(test)]
+mod tests {
+    use ::axum::{body::Body, Router};
+    use ed25519_dalek::{Signer, SigningKey};
+    use http::{Request, StatusCode};
+    use tower_service::Service;
+
+    use crate::{Context, Filter};
+
+    #[tokio::test]
+    async fn test_interactions_route_answers_ping() {
+        let key = SigningKey::from_bytes(&[7; 32]);
+        let ping = crate::command("ping").map(|_: Context| ());
+        let route =
+            super::interactions_route(ping, &hex::encode(key.verifying_key().as_bytes())).unwrap();
+        let mut app: Router = Router::new().route("/interactions", route);
+
+        let body = r#"{"id":"1","application_id":"1234","type":1,"token":"token"}"#;
+        let timestamp = "1700000000";
+        let signature = key.sign(format!("{}{}", timestamp, body).as_bytes());
+        let request = Request::post("/interactions")
+            .header("x-signature-ed25519", hex::encode(signature.to_bytes()))
+            .header("x-signature-timestamp", timestamp)
+            .body(Body::from(body))
+            .unwrap();
+        let response = app.call(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+}
@@ -0,0 +1,105 @@
+//! Defines the `Backoff` strategy spacing out retries and reconnects.
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+    time::Duration,
+};
+
+/// An exponential backoff strategy, deciding how long to wait before retrying a failed REST
+/// request or reconnecting to the gateway.
+///
+/// Each attempt waits `multiplier` times longer than the previous one, starting from
+/// `base_delay` and up to `max_delay`. The delay is then shortened by a random fraction of up
+/// to `jitter`, so that clients failing together don't retry in lockstep.
+///
+/// Defaults to a 1 second base delay, doubling up to 60 seconds, with a jitter of 0.5.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Backoff {
+    base_delay: Duration,
+    multiplier: f64,
+    max_delay: Duration,
+    jitter: f64,
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Self {
+            base_delay: Duration::from_secs(1),
+            multiplier: 2.0,
+            max_delay: Duration::from_secs(60),
+            jitter: 0.5,
+        }
+    }
+}
+
+impl Backoff {
+    /// Set the delay before the first attempt.
+    pub fn base_delay(mut self, delay: Duration) -> Self {
+        self.base_delay = delay;
+        self
+    }
+    /// Set how much longer each attempt waits than the previous one. Values below 1 are
+    /// treated as 1.
+    pub fn multiplier(mut self, multiplier: f64) -> Self {
+        self.multiplier = multiplier;
+        self
+    }
+    /// Set the longest delay before an attempt.
+    pub fn max_delay(mut self, delay: Duration) -> Self {
+        self.max_delay = delay;
+        self
+    }
+    /// Set the largest fraction of the delay that is randomly taken off, between 0 and 1.
+    pub fn jitter(mut self, jitter: f64) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Returns the delay before the given attempt, counting from 1.
+    pub fn delay(&self, attempt: u32) -> Duration {
+        // Capped, since the delay reaches any sensible maximum long before then.
+        let exponent = attempt.saturating_sub(1).min(64) as i32;
+        let delay = self.base_delay.as_secs_f64() * self.multiplier.max(1.0).powi(exponent);
+        let delay = delay.min(self.max_delay.as_secs_f64());
+        let jitter = self.jitter.clamp(0.0, 1.0) * random();
+        Duration::from_secs_f64(delay * (1.0 - jitter))
+    }
+}
+
+/// Returns a random number in `[0, 1)`.
+fn random() -> f64 {
+    // Every `RandomState` is seeded differently, which is random enough for jitter without
+    // pulling in a random number generator that needs OS support on every target.
+    let bits = RandomState::new().build_hasher().finish();
+    (bits >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::Backoff;
+
+    #[test]
+    fn test_backoff_delay() {
+        let backoff = Backoff::default()
+            .base_delay(Duration::from_millis(100))
+            .multiplier(3.0)
+            .max_delay(Duration::from_secs(1))
+            .jitter(0.0);
+        assert_eq!(backoff.delay(1), Duration::from_millis(100));
+        assert_eq!(backoff.delay(2), Duration::from_millis(300));
+        assert_eq!(backoff.delay(3), Duration::from_millis(900));
+        assert_eq!(backoff.delay(4), Duration::from_secs(1));
+        assert_eq!(backoff.delay(u32::MAX), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_backoff_jitter() {
+        let backoff = Backoff::default();
+        for _ in 0..100 {
+            let delay = backoff.delay(2);
+            assert!(delay > Duration::from_secs(1) && delay <= Duration::from_secs(2));
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use std::{
+    collections::HashMap,
+    sync::{RwLock, RwLockReadGuard, RwLockWriteGuard},
+};
+
+use async_trait::async_trait;
+
+use super::CacheBackend;
+use crate::{
+    errors::Result,
+    model::{channel::Channel, guild::Guild, member::Member, snowflake::Snowflake},
+};
+
+/// A [`CacheBackend`] keeping every entity in the memory of this process. This is the default
+/// backend.
+#[derive(Debug, Default)]
+pub struct InMemoryCache {
+    guilds: RwLock<HashMap<Snowflake, Guild>>,
+    channels: RwLock<HashMap<Snowflake, Channel>>,
+    members: RwLock<HashMap<(Snowflake, Snowflake), Member>>,
+}
+
+#[async_trait]
+impl CacheBackend for InMemoryCache {
+    async fn get_guild(&self, guild_id: Snowflake) -> Result<Option<Guild>> {
+        Ok(read(&self.guilds).get(&guild_id).cloned())
+    }
+
+    async fn put_guild(&self, guild: Guild) -> Result<()> {
+        write(&self.guilds).insert(guild.id, guild);
+        Ok(())
+    }
+
+    async fn invalidate_guild(&self, guild_id: Snowflake) -> Result<()> {
+        write(&self.guilds).remove(&guild_id);
+        write(&self.channels).retain(|_, channel| channel.guild_id != Some(guild_id));
+        write(&self.members).retain(|&(id, _), _| id != guild_id);
+        Ok(())
+    }
+
+    async fn get_channel(&self, channel_id: Snowflake) -> Result<Option<Channel>> {
+        Ok(read(&self.channels).get(&channel_id).cloned())
+    }
+
+    async fn get_guild_channels(&self, guild_id: Snowflake) -> Result<Vec<Channel>> {
+        Ok(read(&self.channels)
+            .values()
+            .filter(|channel| channel.guild_id == Some(guild_id))
+            .cloned()
+            .collect())
+    }
+
+    async fn put_channel(&self, channel: Channel) -> Result<()> {
+        write(&self.channels).insert(channel.id, channel);
+        Ok(())
+    }
+
+    async fn invalidate_channel(&self, channel_id: Snowflake) -> Result<()> {
+        write(&self.channels).remove(&channel_id);
+        Ok(())
+    }
+
+    async fn get_member(&self, guild_id: Snowflake, user_id: Snowflake) -> Result<Option<Member>> {
+        Ok(read(&self.members).get(&(guild_id, user_id)).cloned())
+    }
+
+    async fn put_member(&self, guild_id: Snowflake, member: Member) -> Result<()> {
+        if let Some(user_id) = member.user_id() {
+            write(&self.members).insert((guild_id, user_id), member);
+        }
+        Ok(())
+    }
+
+    async fn invalidate_member(&self, guild_id: Snowflake, user_id: Snowflake) -> Result<()> {
+        write(&self.members).remove(&(guild_id, user_id));
+        Ok(())
+    }
+}
+
+/// Lock a map for reading, recovering it if a writer panicked, since every update leaves it
+/// consistent.
+fn read<T>(lock: &RwLock<T>) -> RwLockReadGuard<'_, T> {
+    lock.read().unwrap_or_else(|err| err.into_inner())
+}
+
+/// Lock a map for writing, recovering it if a writer panicked.
+fn write<T>(lock: &RwLock<T>) -> RwLockWriteGuard<'_, T> {
+    lock.write().unwrap_or_else(|err| err.into_inner())
+}
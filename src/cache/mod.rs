@@ -0,0 +1,365 @@
+//! Defines the `Cache` of guilds, channels, roles and members kept up to date by the gateway,
+//! and the `CacheBackend` storing them.
+mod memory;
+#[cfg(feature = "redis")]
+mod redis;
+
+use std::{fmt, sync::Arc};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use serde_json::Value;
+
+pub use self::memory::InMemoryCache;
+#[cfg(feature = "redis")]
+pub use self::redis::RedisCache;
+use crate::{
+    errors::Result,
+    model::{
+        channel::Channel, guild::Guild, member::Member, role::Role, snowflake::Snowflake,
+        user::User,
+    },
+};
+
+/// Stores the entities of a [`Cache`].
+///
+/// Roles are stored as part of their guild. Implementations may be shared by several
+/// processes, such as [`RedisCache`], in which case every process sees the updates of the
+/// others.
+#[async_trait]
+pub trait CacheBackend: Send + Sync {
+    /// Returns a cached guild.
+    async fn get_guild(&self, guild_id: Snowflake) -> Result<Option<Guild>>;
+    /// Insert or replace a guild.
+    async fn put_guild(&self, guild: Guild) -> Result<()>;
+    /// Remove a guild, along with every channel and member in it.
+    async fn invalidate_guild(&self, guild_id: Snowflake) -> Result<()>;
+    /// Returns a cached channel or thread.
+    async fn get_channel(&self, channel_id: Snowflake) -> Result<Option<Channel>>;
+    /// Returns every cached channel and thread in a guild.
+    async fn get_guild_channels(&self, guild_id: Snowflake) -> Result<Vec<Channel>>;
+    /// Insert or replace a channel or thread.
+    async fn put_channel(&self, channel: Channel) -> Result<()>;
+    /// Remove a channel or thread.
+    async fn invalidate_channel(&self, channel_id: Snowflake) -> Result<()>;
+    /// Returns a cached member of a guild.
+    async fn get_member(&self, guild_id: Snowflake, user_id: Snowflake) -> Result<Option<Member>>;
+    /// Insert or replace a member of a guild. Members without their user can't be looked up,
+    /// so they are skipped.
+    async fn put_member(&self, guild_id: Snowflake, member: Member) -> Result<()>;
+    /// Remove a member of a guild.
+    async fn invalidate_member(&self, guild_id: Snowflake, user_id: Snowflake) -> Result<()>;
+}
+
+/// A cache of the guilds, channels, roles and members the bot can see, updated as the gateway
+/// dispatches events about them. Entities are kept in an [`InMemoryCache`] by default.
+///
+/// Only entities the gateway sends events for are cached: guilds, channels and roles need the
+/// [`GUILDS`](crate::model::gateway::GatewayIntents::GUILDS) intent, and members beyond the bot
+/// itself need the privileged
+/// [`GUILD_MEMBERS`](crate::model::gateway::GatewayIntents::GUILD_MEMBERS) intent.
+#[derive(Clone)]
+pub struct Cache {
+    backend: Arc<dyn CacheBackend>,
+}
+
+impl Default for Cache {
+    fn default() -> Self {
+        Self::new(InMemoryCache::default())
+    }
+}
+
+impl fmt::Debug for Cache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Cache").finish_non_exhaustive()
+    }
+}
+
+/// A guild sent in a `GUILD_CREATE` event, with the channels and members it contains.
+#[derive(Deserialize)]
+struct GuildCreate {
+    #[serde(flatten)]
+    guild: Guild,
+    #[serde(default)]
+    channels: Vec<Channel>,
+    #[serde(default)]
+    threads: Vec<Channel>,
+    #[serde(default)]
+    members: Vec<Member>,
+}
+
+#[derive(Deserialize)]
+struct GuildDelete {
+    id: Snowflake,
+    /// Whether the guild is only offline, rather than the bot having left it.
+    #[serde(default)]
+    unavailable: bool,
+}
+
+#[derive(Deserialize)]
+struct ChannelDelete {
+    id: Snowflake,
+}
+
+#[derive(Deserialize)]
+struct RoleUpdate {
+    guild_id: Snowflake,
+    role: Role,
+}
+
+#[derive(Deserialize)]
+struct RoleDelete {
+    guild_id: Snowflake,
+    role_id: Snowflake,
+}
+
+#[derive(Deserialize)]
+struct MemberAdd {
+    guild_id: Snowflake,
+    #[serde(flatten)]
+    member: Member,
+}
+
+/// The fields of a member sent in a `GUILD_MEMBER_UPDATE` event.
+#[derive(Deserialize)]
+struct MemberUpdate {
+    guild_id: Snowflake,
+    user: User,
+    roles: Vec<Snowflake>,
+    nick: Option<String>,
+    avatar: Option<String>,
+    premium_since: Option<String>,
+    pending: Option<bool>,
+    communication_disabled_until: Option<DateTime<Utc>>,
+}
+
+#[derive(Deserialize)]
+struct MemberRemove {
+    guild_id: Snowflake,
+    user: User,
+}
+
+#[derive(Deserialize)]
+struct MembersChunk {
+    guild_id: Snowflake,
+    members: Vec<Member>,
+}
+
+impl Cache {
+    /// Create a cache storing its entities in the given backend.
+    pub fn new<B: CacheBackend + 'static>(backend: B) -> Self {
+        Self {
+            backend: Arc::new(backend),
+        }
+    }
+
+    /// Returns the backend storing the cached entities.
+    pub fn backend(&self) -> &dyn CacheBackend {
+        &*self.backend
+    }
+
+    /// Returns a cached guild.
+    pub async fn guild(&self, guild_id: Snowflake) -> Result<Option<Guild>> {
+        self.backend.get_guild(guild_id).await
+    }
+
+    /// Returns a cached channel or thread.
+    pub async fn channel(&self, channel_id: Snowflake) -> Result<Option<Channel>> {
+        self.backend.get_channel(channel_id).await
+    }
+
+    /// Returns every cached channel and thread in a guild.
+    pub async fn guild_channels(&self, guild_id: Snowflake) -> Result<Vec<Channel>> {
+        self.backend.get_guild_channels(guild_id).await
+    }
+
+    /// Returns a cached role of a guild.
+    pub async fn role(&self, guild_id: Snowflake, role_id: Snowflake) -> Result<Option<Role>> {
+        Ok(self
+            .roles(guild_id)
+            .await?
+            .into_iter()
+            .find(|role| role.id == role_id))
+    }
+
+    /// Returns every cached role of a guild.
+    pub async fn roles(&self, guild_id: Snowflake) -> Result<Vec<Role>> {
+        Ok(self
+            .guild(guild_id)
+            .await?
+            .map(|guild| guild.roles)
+            .unwrap_or_default())
+    }
+
+    /// Returns a cached member of a guild.
+    pub async fn member(&self, guild_id: Snowflake, user_id: Snowflake) -> Result<Option<Member>> {
+        self.backend.get_member(guild_id, user_id).await
+    }
+
+    /// Update the cache from a dispatched event, ignoring events it doesn't track.
+    pub(crate) async fn update(&self, event: &str, data: &Value) {
+        if let Err(err) = self.try_update(event, data).await {
+            tracing::warn!(event, error = %err, "failed to update the cache");
+        }
+    }
+
+    async fn try_update(&self, event: &str, data: &Value) -> Result<()> {
+        let backend = &*self.backend;
+        match event {
+            "GUILD_CREATE" => {
+                let GuildCreate {
+                    guild,
+                    channels,
+                    threads,
+                    members,
+                } = GuildCreate::deserialize(data)?;
+                let guild_id = guild.id;
+                for mut channel in channels.into_iter().chain(threads) {
+                    // Channels sent within a guild leave out its ID.
+                    channel.guild_id = Some(guild_id);
+                    backend.put_channel(channel).await?;
+                }
+                for member in members {
+                    backend.put_member(guild_id, member).await?;
+                }
+                backend.put_guild(guild).await?;
+            }
+            "GUILD_UPDATE" => backend.put_guild(Guild::deserialize(data)?).await?,
+            "GUILD_DELETE" => {
+                let GuildDelete { id, unavailable } = GuildDelete::deserialize(data)?;
+                // Guilds going offline are sent again once they recover.
+                if !unavailable {
+                    backend.invalidate_guild(id).await?;
+                }
+            }
+            "CHANNEL_CREATE" | "CHANNEL_UPDATE" | "THREAD_CREATE" | "THREAD_UPDATE" => {
+                backend.put_channel(Channel::deserialize(data)?).await?;
+            }
+            "CHANNEL_DELETE" | "THREAD_DELETE" => {
+                let ChannelDelete { id } = ChannelDelete::deserialize(data)?;
+                backend.invalidate_channel(id).await?;
+            }
+            "GUILD_ROLE_CREATE" | "GUILD_ROLE_UPDATE" => {
+                let RoleUpdate { guild_id, role } = RoleUpdate::deserialize(data)?;
+                if let Some(mut guild) = backend.get_guild(guild_id).await? {
+                    match guild.roles.iter_mut().find(|cached| cached.id == role.id) {
+                        Some(cached) => *cached = role,
+                        None => guild.roles.push(role),
+                    }
+                    backend.put_guild(guild).await?;
+                }
+            }
+            "GUILD_ROLE_DELETE" => {
+                let RoleDelete { guild_id, role_id } = RoleDelete::deserialize(data)?;
+                if let Some(mut guild) = backend.get_guild(guild_id).await? {
+                    guild.roles.retain(|role| role.id != role_id);
+                    backend.put_guild(guild).await?;
+                }
+            }
+            "GUILD_MEMBER_ADD" => {
+                let MemberAdd { guild_id, member } = MemberAdd::deserialize(data)?;
+                backend.put_member(guild_id, member).await?;
+            }
+            "GUILD_MEMBER_UPDATE" => {
+                let update = MemberUpdate::deserialize(data)?;
+                // Members that weren't cached lack fields the update doesn't send.
+                let member = backend.get_member(update.guild_id, update.user.id).await?;
+                if let Some(mut member) = member {
+                    member.user = Some(update.user);
+                    member.roles = update.roles;
+                    member.nickname = update.nick;
+                    member.avatar = update.avatar;
+                    member.premium_since = update.premium_since;
+                    member.pending = update.pending;
+                    member.communication_disabled_until = update.communication_disabled_until;
+                    backend.put_member(update.guild_id, member).await?;
+                }
+            }
+            "GUILD_MEMBER_REMOVE" => {
+                let MemberRemove { guild_id, user } = MemberRemove::deserialize(data)?;
+                backend.invalidate_member(guild_id, user.id).await?;
+            }
+            "GUILD_MEMBERS_CHUNK" => {
+                let MembersChunk { guild_id, members } = MembersChunk::deserialize(data)?;
+                for member in members {
+                    backend.put_member(guild_id, member).await?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::Cache;
+
+    fn role(id: &str, name: &str) -> serde_json::Value {
+        json!({
+            "id": id,
+            "name": name,
+            "color": 0,
+            "hoist": false,
+            "position": 0,
+            "permissions": "0",
+            "managed": false,
+            "mentionable": false,
+        })
+    }
+
+    fn member(id: &str) -> serde_json::Value {
+        json!({
+            "user": { "id": id, "username": "user", "discriminator": "0" },
+            "roles": [],
+            "joined_at": "2024-01-01T00:00:00Z",
+            "deaf": false,
+            "mute": false,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_cache_tracks_guild_events() {
+        let cache = Cache::default();
+        cache
+            .update(
+                "GUILD_CREATE",
+                &json!({
+                    "id": "1",
+                    "name": "guild",
+                    "owner_id": "2",
+                    "roles": [role("1", "@everyone")],
+                    "channels": [{ "id": "3", "type": 0, "name": "general" }],
+                    "members": [member("2")],
+                }),
+            )
+            .await;
+        assert_eq!(cache.guild(1.into()).await.unwrap().unwrap().name, "guild");
+        let channel = cache.channel(3.into()).await.unwrap().unwrap();
+        assert_eq!(channel.guild_id, Some(1.into()));
+        assert!(cache.member(1.into(), 2.into()).await.unwrap().is_some());
+
+        cache
+            .update(
+                "GUILD_ROLE_CREATE",
+                &json!({ "guild_id": "1", "role": role("4", "mods") }),
+            )
+            .await;
+        let role = cache.role(1.into(), 4.into()).await.unwrap().unwrap();
+        assert_eq!(role.name, "mods");
+        cache
+            .update(
+                "GUILD_MEMBER_REMOVE",
+                &json!({ "guild_id": "1", "user": { "id": "2", "username": "user", "discriminator": "0" } }),
+            )
+            .await;
+        assert!(cache.member(1.into(), 2.into()).await.unwrap().is_none());
+
+        cache.update("GUILD_DELETE", &json!({ "id": "1" })).await;
+        assert!(cache.guild(1.into()).await.unwrap().is_none());
+        assert!(cache.guild_channels(1.into()).await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,215 @@
+use std::fmt;
+
+use async_trait::async_trait;
+use redis::{aio::ConnectionManager, AsyncCommands, RedisError};
+use serde::{de::DeserializeOwned, Serialize};
+
+use super::CacheBackend;
+use crate::{
+    errors::{Error, Result},
+    model::{channel::Channel, guild::Guild, member::Member, snowflake::Snowflake},
+};
+
+/// The prefix of every key written by a [`RedisCache`], by default.
+const DEFAULT_PREFIX: &str = "slash:";
+
+/// A [`CacheBackend`] storing entities in Redis as JSON, so that several processes can share
+/// a single cache.
+///
+/// Alongside each entity, the IDs of every channel and member of a guild are kept in sets, so
+/// that they can be removed with the guild.
+#[derive(Clone)]
+pub struct RedisCache {
+    connection: ConnectionManager,
+    prefix: String,
+}
+
+impl RedisCache {
+    /// Connect to the Redis server of the given client, reconnecting whenever the connection
+    /// drops.
+    pub async fn new(client: redis::Client) -> Result<Self> {
+        Ok(Self {
+            connection: ConnectionManager::new(client).await.map_err(cache_error)?,
+            prefix: DEFAULT_PREFIX.to_string(),
+        })
+    }
+
+    /// Set the prefix of every key, to keep several caches on the same server apart. Defaults
+    /// to `slash:`.
+    pub fn prefix<S: Into<String>>(mut self, prefix: S) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    fn guild_key(&self, guild_id: Snowflake) -> String {
+        format!("{}guild:{}", self.prefix, guild_id)
+    }
+
+    fn guild_channels_key(&self, guild_id: Snowflake) -> String {
+        format!("{}guild:{}:channels", self.prefix, guild_id)
+    }
+
+    fn guild_members_key(&self, guild_id: Snowflake) -> String {
+        format!("{}guild:{}:members", self.prefix, guild_id)
+    }
+
+    fn channel_key(&self, channel_id: Snowflake) -> String {
+        format!("{}channel:{}", self.prefix, channel_id)
+    }
+
+    fn member_key(&self, guild_id: Snowflake, user_id: Snowflake) -> String {
+        format!("{}member:{}:{}", self.prefix, guild_id, user_id)
+    }
+
+    async fn get<T: DeserializeOwned>(&self, key: String) -> Result<Option<T>> {
+        let value: Option<String> = self
+            .connection
+            .clone()
+            .get(key)
+            .await
+            .map_err(cache_error)?;
+        Ok(value
+            .map(|value| serde_json::from_str(&value))
+            .transpose()?)
+    }
+
+    async fn set<T: Serialize>(&self, key: String, value: &T) -> Result<()> {
+        let value = serde_json::to_string(value)?;
+        self.connection
+            .clone()
+            .set(key, value)
+            .await
+            .map_err(cache_error)
+    }
+}
+
+#[async_trait]
+impl CacheBackend for RedisCache {
+    async fn get_guild(&self, guild_id: Snowflake) -> Result<Option<Guild>> {
+        self.get(self.guild_key(guild_id)).await
+    }
+
+    async fn put_guild(&self, guild: Guild) -> Result<()> {
+        self.set(self.guild_key(guild.id), &guild).await
+    }
+
+    async fn invalidate_guild(&self, guild_id: Snowflake) -> Result<()> {
+        let mut connection = self.connection.clone();
+        let channels_key = self.guild_channels_key(guild_id);
+        let members_key = self.guild_members_key(guild_id);
+        let channels: Vec<u64> = connection
+            .smembers(&channels_key)
+            .await
+            .map_err(cache_error)?;
+        let members: Vec<u64> = connection
+            .smembers(&members_key)
+            .await
+            .map_err(cache_error)?;
+        let mut keys = vec![self.guild_key(guild_id), channels_key, members_key];
+        keys.extend(channels.into_iter().map(|id| self.channel_key(id.into())));
+        keys.extend(
+            members
+                .into_iter()
+                .map(|id| self.member_key(guild_id, id.into())),
+        );
+        connection.del(keys).await.map_err(cache_error)
+    }
+
+    async fn get_channel(&self, channel_id: Snowflake) -> Result<Option<Channel>> {
+        self.get(self.channel_key(channel_id)).await
+    }
+
+    async fn get_guild_channels(&self, guild_id: Snowflake) -> Result<Vec<Channel>> {
+        let mut connection = self.connection.clone();
+        let ids: Vec<u64> = connection
+            .smembers(self.guild_channels_key(guild_id))
+            .await
+            .map_err(cache_error)?;
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let keys: Vec<String> = ids
+            .into_iter()
+            .map(|id| self.channel_key(id.into()))
+            .collect();
+        let values: Vec<Option<String>> = connection.mget(keys).await.map_err(cache_error)?;
+        // Channels removed since their ID was added to the set are skipped.
+        values
+            .into_iter()
+            .flatten()
+            .map(|value| Ok(serde_json::from_str(&value)?))
+            .collect()
+    }
+
+    async fn put_channel(&self, channel: Channel) -> Result<()> {
+        self.set(self.channel_key(channel.id), &channel).await?;
+        if let Some(guild_id) = channel.guild_id {
+            self.connection
+                .clone()
+                .sadd::<_, _, ()>(self.guild_channels_key(guild_id), channel.id.to_string())
+                .await
+                .map_err(cache_error)?;
+        }
+        Ok(())
+    }
+
+    async fn invalidate_channel(&self, channel_id: Snowflake) -> Result<()> {
+        if let Some(Channel {
+            guild_id: Some(guild_id),
+            ..
+        }) = self.get_channel(channel_id).await?
+        {
+            self.connection
+                .clone()
+                .srem::<_, _, ()>(self.guild_channels_key(guild_id), channel_id.to_string())
+                .await
+                .map_err(cache_error)?;
+        }
+        self.connection
+            .clone()
+            .del(self.channel_key(channel_id))
+            .await
+            .map_err(cache_error)
+    }
+
+    async fn get_member(&self, guild_id: Snowflake, user_id: Snowflake) -> Result<Option<Member>> {
+        self.get(self.member_key(guild_id, user_id)).await
+    }
+
+    async fn put_member(&self, guild_id: Snowflake, member: Member) -> Result<()> {
+        let Some(user_id) = member.user_id() else {
+            return Ok(());
+        };
+        self.set(self.member_key(guild_id, user_id), &member)
+            .await?;
+        self.connection
+            .clone()
+            .sadd(self.guild_members_key(guild_id), user_id.to_string())
+            .await
+            .map_err(cache_error)
+    }
+
+    async fn invalidate_member(&self, guild_id: Snowflake, user_id: Snowflake) -> Result<()> {
+        let mut connection = self.connection.clone();
+        connection
+            .srem::<_, _, ()>(self.guild_members_key(guild_id), user_id.to_string())
+            .await
+            .map_err(cache_error)?;
+        connection
+            .del(self.member_key(guild_id, user_id))
+            .await
+            .map_err(cache_error)
+    }
+}
+
+impl fmt::Debug for RedisCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RedisCache")
+            .field("prefix", &self.prefix)
+            .finish_non_exhaustive()
+    }
+}
+
+fn cache_error(err: RedisError) -> Error {
+    Error::Cache(Box::new(err))
+}
@@ -0,0 +1,1163 @@
+//! Defines the `Client`, which registers commands, connects to Discord and dispatches
+//! interactions to them.
+#[cfg(feature = "webhook")]
+use std::net::SocketAddr;
+#[cfg(feature = "tls")]
+use std::path::{Path, PathBuf};
+use std::{
+    fmt,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, RwLock,
+    },
+    time::Duration,
+};
+
+#[cfg(feature = "gateway")]
+use futures_util::StreamExt;
+use tracing::Instrument;
+
+#[cfg(feature = "gateway")]
+use crate::gateway::{Gateway, GatewayBuilder};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::model::interaction::InteractionType;
+#[cfg(feature = "gateway")]
+use crate::model::{self, gateway::GatewayEvent};
+use crate::{
+    backoff::Backoff,
+    collector::Collectors,
+    command::{
+        self, CommandSet, RegistrationProgress, RegistrationStatus, RegistrationStore, SyncPlan,
+        ValidationError,
+    },
+    config::Config,
+    context::Context,
+    dispatch::{self, ComponentHandler},
+    errors::{Error, Result},
+    filter::Filter,
+    model::{
+        command::ApplicationCommand,
+        interaction::{Interaction, InteractionResponse},
+        snowflake::Snowflake,
+    },
+    reject::Rejection,
+    rest,
+    router::Router,
+    runtime,
+    shutdown::{InFlight, ShutdownHandle},
+};
+
+/// How long shutting down waits for interactions being handled to finish, by default.
+const DEFAULT_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(10);
+/// How long to wait between replacing the commands of each scope, by default.
+const DEFAULT_REGISTRATION_INTERVAL: Duration = Duration::from_secs(1);
+
+/// A callback reporting the progress of registering commands.
+type ProgressCallback = Arc<dyn Fn(&RegistrationProgress<'_>) + Send + Sync>;
+/// A callback reporting each command handled.
+type CompletionCallback = Arc<dyn Fn(&CommandCompletion<'_>) + Send + Sync>;
+
+/// A command handled by a client, reported to the callback set with
+/// [`on_command_complete`](ClientBuilder::on_command_complete).
+#[non_exhaustive]
+pub struct CommandCompletion<'a> {
+    /// Name of the command.
+    pub name: &'a str,
+    /// The context the command was handled in, to tell who used it and where.
+    pub context: &'a Context,
+    /// How long the command took, from receiving the interaction until its handler returned.
+    pub duration: Duration,
+    /// Whether a handler accepted the command, or the rejection if none did. Commands no
+    /// handler matched are rejected as [not found](Rejection::is_not_found).
+    pub result: Result<(), &'a Rejection>,
+}
+
+impl fmt::Debug for CommandCompletion<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CommandCompletion")
+            .field("name", &self.name)
+            .field("interaction_id", &self.context.interaction_id())
+            .field("duration", &self.duration)
+            .field("result", &self.result)
+            .finish()
+    }
+}
+
+/// Which of a client's collectors and component handlers receive a component interaction
+/// both would accept.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ComponentPriority {
+    /// Collectors, which are scoped to a single message, take precedence. This is the default.
+    #[default]
+    Collectors,
+    /// Component handlers take precedence, so that collectors only receive interactions no
+    /// handler accepts.
+    Handlers,
+}
+
+/// How a client receives interactions.
+enum Transport {
+    /// Over a gateway connection.
+    #[cfg(feature = "gateway")]
+    Gateway(Gateway),
+    /// As HTTP requests sent to the interactions endpoint.
+    #[cfg(feature = "webhook")]
+    Http(crate::http::server::Listen),
+}
+
+/// How the HTTP server terminates TLS.
+#[cfg(feature = "tls")]
+enum Tls {
+    /// With the certificate chain and private key in these PEM files.
+    Paths { cert: PathBuf, key: PathBuf },
+    /// With a preconfigured rustls configuration.
+    Config(Arc<crate::rustls::ServerConfig>),
+}
+
+#[cfg(feature = "tls")]
+impl Tls {
+    fn load(self) -> Result<Arc<crate::rustls::ServerConfig>> {
+        match self {
+            Tls::Paths { cert, key } => crate::http::tls::load(&cert, &key),
+            Tls::Config(config) => Ok(config),
+        }
+    }
+}
+
+/// Configures a [`Client`].
+pub struct ClientBuilder {
+    token: Option<String>,
+    application_id: Option<Snowflake>,
+    router: Router,
+    components: Vec<ComponentHandler>,
+    component_priority: ComponentPriority,
+    register_commands: bool,
+    dry_run: bool,
+    validate_application: bool,
+    guild_id: Option<Snowflake>,
+    production: bool,
+    registration_interval: Duration,
+    registration_store: Option<Arc<dyn RegistrationStore>>,
+    on_registration: Option<ProgressCallback>,
+    on_command_complete: Option<CompletionCallback>,
+    shutdown_timeout: Duration,
+    #[cfg(feature = "webhook")]
+    address: Option<SocketAddr>,
+    #[cfg(feature = "webhook")]
+    health_checks: bool,
+    #[cfg(feature = "tls")]
+    tls: Option<Tls>,
+    #[cfg(feature = "verify")]
+    public_key: Option<String>,
+    #[cfg(feature = "gateway")]
+    shards: Option<(Option<u32>, u32)>,
+    backoff: Option<Backoff>,
+    configure_rest: Option<Box<dyn FnOnce(rest::ClientBuilder) -> rest::ClientBuilder + Send>>,
+    #[cfg(feature = "gateway")]
+    configure_gateway: Option<Box<dyn FnOnce(GatewayBuilder) -> GatewayBuilder + Send>>,
+}
+
+impl ClientBuilder {
+    /// Apply the settings of a [`Config`], such as one loaded with [`Config::from_env`].
+    pub fn config(mut self, config: Config) -> Self {
+        self.token = Some(config.token);
+        self.application_id = Some(config.application_id);
+        if config.guild_id.is_some() {
+            self.guild_id = config.guild_id;
+        }
+        if config.production {
+            self.production = true;
+        }
+        #[cfg(feature = "verify")]
+        if config.public_key.is_some() {
+            self.public_key = config.public_key;
+        }
+        #[cfg(feature = "webhook")]
+        if config.address.is_some() {
+            self.address = config.address;
+        }
+        #[cfg(feature = "gateway")]
+        if let Some(total) = config.shard_count {
+            self.shards = Some((config.shard_id, total));
+        }
+        self
+    }
+    /// Set the bot token to authenticate with.
+    pub fn token<S: AsRef<str>>(mut self, token: S) -> Self {
+        self.token = Some(token.as_ref().to_string());
+        self
+    }
+    /// Set the ID of the application the commands are registered to.
+    pub fn application_id<S: Into<Snowflake>>(mut self, application_id: S) -> Self {
+        self.application_id = Some(application_id.into());
+        self
+    }
+    /// Add a command, or any filter built from commands, to dispatch interactions to.
+    ///
+    /// Filters are tried in the order they were added, until one accepts the interaction.
+    pub fn command<F>(mut self, filter: F) -> Self
+    where
+        F: Filter + Send + Sync + 'static,
+        F::Future: 'static,
+        F::Error: Into<Rejection>,
+    {
+        self.router = self.router.command(filter);
+        self
+    }
+    /// Add a command, or any filter built from commands, that is only registered to the given
+    /// guild, such as extra commands for an admin guild.
+    ///
+    /// Interactions from the guild are dispatched to its commands before the global ones.
+    pub fn guild_command<S, F>(mut self, guild_id: S, filter: F) -> Self
+    where
+        S: Into<Snowflake>,
+        F: Filter + Send + Sync + 'static,
+        F::Future: 'static,
+        F::Error: Into<Rejection>,
+    {
+        self.router = self.router.guild_command(guild_id, filter);
+        self
+    }
+    /// Set the commands to dispatch interactions to, replacing any added before.
+    pub fn router(mut self, router: Router) -> Self {
+        self.router = router;
+        self
+    }
+    /// Add a filter handling the component interactions whose custom ID starts with `prefix`,
+    /// such as one created by `CustomId::filter`.
+    ///
+    /// Unlike collectors, component handlers aren't tied to a message the client sent, so
+    /// they keep handling the components of messages sent before the client restarted. When
+    /// several prefixes match, the longest wins. Interactions the handler doesn't accept are
+    /// dispatched to the commands.
+    pub fn component<S, F>(mut self, prefix: S, filter: F) -> Self
+    where
+        S: Into<String>,
+        F: Filter + Send + Sync + 'static,
+        F::Future: 'static,
+        F::Error: Into<Rejection>,
+    {
+        self.components.push(ComponentHandler {
+            prefix: prefix.into(),
+            handler: Box::new(filter),
+        });
+        self
+    }
+    /// Set whether collectors or component handlers receive the component interactions both
+    /// would accept. Defaults to [`ComponentPriority::Collectors`].
+    pub fn component_priority(mut self, priority: ComponentPriority) -> Self {
+        self.component_priority = priority;
+        self
+    }
+    /// Set whether the commands are registered with Discord when the client starts running.
+    /// Defaults to `true`.
+    pub fn register_commands(mut self, register: bool) -> Self {
+        self.register_commands = register;
+        self
+    }
+    /// Set whether the client only logs the changes registering the commands would make when
+    /// it starts running, without making them, so they can be reviewed before a deployment.
+    /// Defaults to `false`.
+    pub fn dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+    /// Set whether the client checks that its application ID is the application of its token
+    /// when it starts running, failing with [`Error::InvalidConfig`] if it isn't, rather than
+    /// with Discord's errors once it registers commands or responds to interactions. Defaults
+    /// to `false`, which saves a request on every start.
+    pub fn validate_application(mut self, validate: bool) -> Self {
+        self.validate_application = validate;
+        self
+    }
+    /// Set how long to wait between replacing the commands of each guild, to spread them
+    /// under Discord's daily limit of command creations. Defaults to 1 second.
+    pub fn registration_interval(mut self, interval: Duration) -> Self {
+        self.registration_interval = interval;
+        self
+    }
+    /// Set where to store a hash of the commands registered to each scope, so that scopes
+    /// whose commands are unchanged since they were last registered are skipped without
+    /// making any request to Discord, such as with a [`FileStore`](crate::command::FileStore).
+    /// By default, the registered commands are fetched from Discord to compare them.
+    pub fn registration_store<S>(mut self, store: S) -> Self
+    where
+        S: RegistrationStore + 'static,
+    {
+        self.registration_store = Some(Arc::new(store));
+        self
+    }
+    /// Set a callback reporting the progress of registering commands, called once the
+    /// commands of each scope are registered, found up to date, or failed to register.
+    pub fn on_registration<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&RegistrationProgress<'_>) + Send + Sync + 'static,
+    {
+        self.on_registration = Some(Arc::new(callback));
+        self
+    }
+    /// Set a callback called after every command is handled, with how long it took and whether
+    /// it was rejected, such as to report the latency and error rate of each command. Not
+    /// available on `wasm32`, where the system clock can't be read.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let builder = slash::Client::builder();
+    /// builder.on_command_complete(|completion| {
+    ///     let status = if completion.result.is_ok() { "ok" } else { "rejected" };
+    ///     println!("/{} {} in {:?}", completion.name, status, completion.duration);
+    /// });
+    /// ```
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn on_command_complete<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&CommandCompletion<'_>) + Send + Sync + 'static,
+    {
+        self.on_command_complete = Some(Arc::new(callback));
+        self
+    }
+    /// Set a dev guild to register every global command to, skipping global registration.
+    /// Guild commands take effect immediately, unlike global ones, which is useful while
+    /// developing. Commands added with [`guild_command`](ClientBuilder::guild_command) are
+    /// still registered to their own guild.
+    ///
+    /// The dev guild is ignored in [`production`](ClientBuilder::production) mode.
+    pub fn guild_id<S: Into<Snowflake>>(mut self, guild_id: S) -> Self {
+        self.guild_id = Some(guild_id.into());
+        self
+    }
+    /// Set whether the client runs in production mode, registering the commands globally even
+    /// if a dev guild is set, so that the same configuration can be deployed. Defaults to
+    /// `false`.
+    pub fn production(mut self, production: bool) -> Self {
+        self.production = production;
+        self
+    }
+    /// Set how long shutting down waits for the interactions being handled to finish before
+    /// cancelling them. Defaults to 10 seconds.
+    pub fn shutdown_timeout(mut self, timeout: Duration) -> Self {
+        self.shutdown_timeout = timeout;
+        self
+    }
+    /// Receive interactions as HTTP requests on the given address, instead of over the gateway.
+    ///
+    /// The application's interactions endpoint URL must point to this server, and its public
+    /// key must be set with [`ClientBuilder::public_key`].
+    #[cfg(feature = "webhook")]
+    pub fn listen<A: Into<SocketAddr>>(mut self, address: A) -> Self {
+        self.address = Some(address.into());
+        self
+    }
+    /// Set whether the HTTP server answers `GET` requests to `/healthz`, once it is running,
+    /// and to `/readyz`, once the commands are registered and interactions are being
+    /// dispatched, so that orchestrators can probe the bot. Defaults to `false`.
+    #[cfg(feature = "webhook")]
+    pub fn health_checks(mut self, enabled: bool) -> Self {
+        self.health_checks = enabled;
+        self
+    }
+    /// Terminate TLS in the HTTP server, with the PEM-encoded certificate chain and private key
+    /// at the given paths. Discord only sends interactions to HTTPS endpoints, so this is needed
+    /// unless the server is behind a proxy terminating TLS.
+    #[cfg(feature = "tls")]
+    pub fn tls<P: AsRef<Path>>(mut self, cert_path: P, key_path: P) -> Self {
+        self.tls = Some(Tls::Paths {
+            cert: cert_path.as_ref().to_path_buf(),
+            key: key_path.as_ref().to_path_buf(),
+        });
+        self
+    }
+    /// Terminate TLS in the HTTP server with the given rustls configuration.
+    #[cfg(feature = "tls")]
+    pub fn tls_config(mut self, config: Arc<crate::rustls::ServerConfig>) -> Self {
+        self.tls = Some(Tls::Config(config));
+        self
+    }
+    /// Set the application's public key, used to verify that HTTP requests come from Discord.
+    #[cfg(feature = "verify")]
+    pub fn public_key<S: AsRef<str>>(mut self, public_key: S) -> Self {
+        self.public_key = Some(public_key.as_ref().to_string());
+        self
+    }
+    /// Set the backoff strategy of both the REST client's retries and the gateway's
+    /// reconnects. Either can still be overridden with [`ClientBuilder::rest`] or
+    /// [`ClientBuilder::gateway`].
+    pub fn backoff(mut self, backoff: Backoff) -> Self {
+        self.backoff = Some(backoff);
+        self
+    }
+    /// Configure the REST client, such as its timeout and retry policy.
+    pub fn rest<F>(mut self, configure: F) -> Self
+    where
+        F: FnOnce(rest::ClientBuilder) -> rest::ClientBuilder + Send + 'static,
+    {
+        self.configure_rest = Some(Box::new(configure));
+        self
+    }
+    /// Configure the gateway connection interactions are received over.
+    #[cfg(feature = "gateway")]
+    pub fn gateway<F>(mut self, configure: F) -> Self
+    where
+        F: FnOnce(GatewayBuilder) -> GatewayBuilder + Send + 'static,
+    {
+        self.configure_gateway = Some(Box::new(configure));
+        self
+    }
+    /// Create the client.
+    pub fn build(mut self) -> Result<Client> {
+        let token = self.token.take().ok_or(Error::MissingConfig("token"))?;
+        if self.application_id.is_none() {
+            return Err(Error::MissingConfig("application ID"));
+        }
+        let transport = self.transport(&token)?;
+        let http = self.rest_client(Some(token));
+        self.finish(http, transport)
+    }
+    /// Create the client and run it.
+    pub async fn run(self) -> Result<()> {
+        self.build()?.run().await
+    }
+
+    /// Create a client that only handles the interactions it is sent over HTTP by another
+    /// server, so needs neither a token nor an application ID.
+    #[cfg(all(feature = "verify", any(test, feature = "warp", feature = "axum")))]
+    pub(crate) fn build_webhook(mut self) -> Result<Client> {
+        if self.public_key.is_none() {
+            return Err(Error::MissingConfig("public key"));
+        }
+        let http = self.rest_client(self.token.clone());
+        self.finish(http, None)
+    }
+
+    /// Create the REST client, authenticating with the token if there is one.
+    fn rest_client(&mut self, token: Option<String>) -> rest::Client {
+        let mut builder = rest::Client::builder();
+        if let Some(token) = token {
+            builder = builder.token(token);
+        }
+        if let Some(backoff) = self.backoff {
+            builder = builder.backoff(backoff);
+        }
+        if let Some(configure) = self.configure_rest.take() {
+            builder = configure(builder);
+        }
+        builder.build()
+    }
+
+    fn finish(self, http: rest::Client, transport: Option<Transport>) -> Result<Client> {
+        #[cfg(feature = "verify")]
+        let public_key = self
+            .public_key
+            .as_deref()
+            .map(|key| crate::http::parse_public_key(key).ok_or(Error::InvalidConfig("public key")))
+            .transpose()?;
+        Ok(Client {
+            inner: Arc::new(ClientInner {
+                http: Arc::new(http),
+                application_id: self.application_id,
+                router: RwLock::new(Arc::new(self.router)),
+                components: self.components,
+                component_priority: self.component_priority,
+                register_commands: self.register_commands,
+                dry_run: self.dry_run,
+                validate_application: self.validate_application,
+                guild_id: self.guild_id.filter(|_| !self.production),
+                registration_interval: self.registration_interval,
+                registration_store: self.registration_store,
+                on_registration: self.on_registration,
+                on_command_complete: self.on_command_complete,
+                transport,
+                #[cfg(feature = "verify")]
+                public_key,
+                shutdown: ShutdownHandle::new(),
+                shutdown_timeout: self.shutdown_timeout,
+                in_flight: InFlight::new(),
+                collectors: Arc::default(),
+                ready: AtomicBool::new(false),
+            }),
+        })
+    }
+
+    /// Choose how interactions are received: over HTTP if an address to listen on was given,
+    /// and over the gateway otherwise. Without the gateway, interactions may only be received
+    /// by mounting the client in another HTTP server.
+    #[allow(unused_variables)]
+    fn transport(&mut self, token: &str) -> Result<Option<Transport>> {
+        #[cfg(feature = "webhook")]
+        if let Some(address) = self.address {
+            if self.public_key.is_none() {
+                return Err(Error::MissingConfig("public key"));
+            }
+            return Ok(Some(Transport::Http(crate::http::server::Listen {
+                address,
+                health_checks: self.health_checks,
+                #[cfg(feature = "tls")]
+                tls: self.tls.take().map(Tls::load).transpose()?,
+            })));
+        }
+        #[cfg(feature = "gateway")]
+        {
+            let mut builder = Gateway::builder(token);
+            if let Some(backoff) = self.backoff {
+                builder = builder.backoff(backoff);
+            }
+            builder = match self.shards {
+                Some((Some(id), total)) => builder.shard(id, total),
+                Some((None, total)) => builder.shards(total),
+                None => builder,
+            };
+            if let Some(configure) = self.configure_gateway.take() {
+                builder = configure(builder);
+            }
+            Ok(Some(Transport::Gateway(builder.build())))
+        }
+        #[cfg(not(feature = "gateway"))]
+        Ok(None)
+    }
+}
+
+impl fmt::Debug for ClientBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ClientBuilder")
+            .field("application_id", &self.application_id)
+            .field("router", &self.router)
+            .field("components", &self.components.len())
+            .field("component_priority", &self.component_priority)
+            .field("register_commands", &self.register_commands)
+            .field("dry_run", &self.dry_run)
+            .field("validate_application", &self.validate_application)
+            .field("guild_id", &self.guild_id)
+            .field("production", &self.production)
+            .finish_non_exhaustive()
+    }
+}
+
+struct ClientInner {
+    http: Arc<rest::Client>,
+    /// Only missing for clients mounted in another HTTP server.
+    application_id: Option<Snowflake>,
+    /// The commands, swapped as a whole when they are replaced.
+    router: RwLock<Arc<Router>>,
+    components: Vec<ComponentHandler>,
+    component_priority: ComponentPriority,
+    register_commands: bool,
+    dry_run: bool,
+    validate_application: bool,
+    /// The dev guild commands are registered to, unless in production mode.
+    guild_id: Option<Snowflake>,
+    registration_interval: Duration,
+    registration_store: Option<Arc<dyn RegistrationStore>>,
+    on_registration: Option<ProgressCallback>,
+    #[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+    on_command_complete: Option<CompletionCallback>,
+    transport: Option<Transport>,
+    #[cfg(feature = "verify")]
+    public_key: Option<ed25519_dalek::VerifyingKey>,
+    shutdown: ShutdownHandle,
+    shutdown_timeout: Duration,
+    /// The interactions being handled.
+    in_flight: InFlight,
+    collectors: Arc<Collectors>,
+    /// Whether the commands are registered and interactions are being dispatched.
+    ready: AtomicBool,
+}
+
+/// A bot, which registers its commands, connects to Discord and dispatches interactions to
+/// its commands.
+///
+/// # Example
+///
+/// ```no_run
+/// use slash::{Client, Context, Filter};
+///
+/// # async fn run() -> slash::Result<()> {
+/// let ping = slash::command("ping")
+///     .description("Test the bot's latency.")
+///     .then(|context: Context| async move { context.reply("pong!").await });
+///
+/// Client::builder()
+///     .token("token")
+///     .application_id(1234)
+///     .command(ping)
+///     .run()
+///     .await
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct Client {
+    inner: Arc<ClientInner>,
+}
+
+impl Client {
+    /// Start configuring a client.
+    pub fn builder() -> ClientBuilder {
+        ClientBuilder {
+            token: None,
+            application_id: None,
+            router: Router::new(),
+            components: Vec::new(),
+            component_priority: ComponentPriority::default(),
+            register_commands: true,
+            dry_run: false,
+            validate_application: false,
+            guild_id: None,
+            production: false,
+            registration_interval: DEFAULT_REGISTRATION_INTERVAL,
+            registration_store: None,
+            on_registration: None,
+            on_command_complete: None,
+            shutdown_timeout: DEFAULT_SHUTDOWN_TIMEOUT,
+            #[cfg(feature = "webhook")]
+            address: None,
+            #[cfg(feature = "webhook")]
+            health_checks: false,
+            #[cfg(feature = "tls")]
+            tls: None,
+            #[cfg(feature = "verify")]
+            public_key: None,
+            #[cfg(feature = "gateway")]
+            shards: None,
+            backoff: None,
+            configure_rest: None,
+            #[cfg(feature = "gateway")]
+            configure_gateway: None,
+        }
+    }
+
+    /// Returns the REST client used by the bot.
+    pub fn http(&self) -> &rest::Client {
+        &self.inner.http
+    }
+
+    /// Returns the gateway connection, unless interactions are received over HTTP.
+    #[cfg(feature = "gateway")]
+    pub fn gateway(&self) -> Option<&Gateway> {
+        match self.inner.transport {
+            Some(Transport::Gateway(ref gateway)) => Some(gateway),
+            _ => None,
+        }
+    }
+
+    /// Returns a handle that shuts the client down.
+    ///
+    /// Shutting down stops receiving interactions, closing gateway connections with code 1000,
+    /// then waits for the interactions being handled to finish, along with the REST requests
+    /// they make, for up to the [shutdown timeout](ClientBuilder::shutdown_timeout).
+    /// [`Client::run`] returns once this is done.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn run(client: slash::Client) -> slash::Result<()> {
+    /// let shutdown = client.shutdown_handle();
+    /// tokio::spawn(async move {
+    ///     tokio::signal::ctrl_c().await.ok();
+    ///     shutdown.shutdown();
+    /// });
+    /// client.run().await
+    /// # }
+    /// ```
+    pub fn shutdown_handle(&self) -> ShutdownHandle {
+        self.inner.shutdown.clone()
+    }
+
+    /// Create a [`tower::Service`](tower_service::Service) serving the interactions endpoint,
+    /// to mount the client in another HTTP server instead of running it. The client must have
+    /// been given its public key.
+    #[cfg(feature = "tower")]
+    pub fn service(&self) -> Result<crate::service::InteractionService> {
+        crate::service::InteractionService::new(self.clone())
+    }
+
+    /// Verify a request sent to the interactions endpoint, and dispatch the interaction it
+    /// carries, for HTTP servers slash doesn't integrate with, such as edge runtimes. The
+    /// client must have been given its public key.
+    ///
+    /// Unlike the built-in server and [`service`](Client::service), which acknowledge the
+    /// interaction right away and handle it in the background, this only returns once the
+    /// interaction is handled, as runtimes like these may stop running the request's tasks
+    /// once it's answered. Handlers still have to respond within Discord's 3 seconds.
+    ///
+    /// `signature` and `timestamp` are the values of the `X-Signature-Ed25519` and
+    /// `X-Signature-Timestamp` headers.
+    #[cfg(feature = "verify")]
+    pub async fn handle_request(
+        &self,
+        signature: Option<&str>,
+        timestamp: Option<&str>,
+        body: &[u8],
+    ) -> Result<crate::HttpResponse> {
+        let key = self
+            .public_key()
+            .ok_or(Error::MissingConfig("public key"))?;
+        let dispatch = crate::http::Dispatch::Await;
+        Ok(crate::http::handle(self, key, signature, timestamp, body, dispatch).await)
+    }
+
+    /// Collects and validates the registration payloads of every global command.
+    pub fn commands(&self) -> Result<Vec<ApplicationCommand>, ValidationError> {
+        self.router().commands()
+    }
+
+    /// Collects and validates the commands to register to each scope: globally, or to a
+    /// guild. Global commands are registered to the dev guild instead, if one is set.
+    pub fn command_sets(&self) -> Result<Vec<CommandSet>, ValidationError> {
+        self.router().command_sets(self.inner.guild_id)
+    }
+
+    /// Register every command with Discord, replacing any previously registered commands in
+    /// each scope. Returns the registered commands.
+    ///
+    /// Scopes whose registered commands are already up to date are left alone, so that they
+    /// don't count against Discord's daily limit of command creations. Scopes that fail to
+    /// register are reported to the [`on_registration`](ClientBuilder::on_registration)
+    /// callback and skipped, rather than failing the others. Scopes skipped because their
+    /// hash matches the [`registration_store`](ClientBuilder::registration_store) are left
+    /// out of the returned commands.
+    pub async fn register_commands(&self) -> Result<Vec<ApplicationCommand>> {
+        self.register_sets(self.command_sets()?).await
+    }
+
+    /// Replace the commands interactions are dispatched to while the client runs, without
+    /// reconnecting, then register them as the client does when it starts running. Returns
+    /// the registered commands, which are empty if the client doesn't register commands or
+    /// is in dry-run mode.
+    ///
+    /// Interactions already being handled finish with the previous commands. If any of the
+    /// new commands is invalid, the previous ones are kept. Guilds that no longer have any
+    /// commands keep the ones registered to them.
+    pub async fn replace_commands(&self, router: Router) -> Result<Vec<ApplicationCommand>> {
+        let sets = router.command_sets(self.inner.guild_id)?;
+        *self
+            .inner
+            .router
+            .write()
+            .unwrap_or_else(|err| err.into_inner()) = Arc::new(router);
+        if !self.inner.register_commands {
+            Ok(Vec::new())
+        } else if self.inner.dry_run {
+            self.log_plans().await?;
+            Ok(Vec::new())
+        } else {
+            self.register_sets(sets).await
+        }
+    }
+
+    async fn register_sets(&self, sets: Vec<CommandSet>) -> Result<Vec<ApplicationCommand>> {
+        let application_id = self.application_id()?;
+        let router = self.router();
+        let total = sets.len();
+        let mut registered = Vec::new();
+        let mut overwrites = 0;
+        for (index, set) in sets.iter().enumerate() {
+            let result = self.register_set(application_id, set, overwrites > 0).await;
+            let status = match result {
+                Ok((ref commands, true)) => RegistrationStatus::Registered(commands),
+                Ok((_, false)) => RegistrationStatus::Unchanged,
+                Err(ref err) => RegistrationStatus::Failed(err),
+            };
+            match status {
+                RegistrationStatus::Registered(commands) => {
+                    overwrites += 1;
+                    tracing::info!(count = commands.len(), guild_id = ?set.guild_id, "registered commands");
+                }
+                RegistrationStatus::Unchanged => {
+                    tracing::debug!(guild_id = ?set.guild_id, "commands are up to date");
+                }
+                RegistrationStatus::Failed(err) => {
+                    tracing::warn!(guild_id = ?set.guild_id, error = %err, "failed to register commands");
+                }
+            }
+            if let Some(callback) = &self.inner.on_registration {
+                callback(&RegistrationProgress {
+                    guild_id: set.guild_id,
+                    completed: index + 1,
+                    total,
+                    status,
+                });
+            }
+            if let Ok((commands, _)) = result {
+                router.set_registered(set.guild_id, &commands);
+                registered.extend(commands);
+            }
+        }
+        Ok(registered)
+    }
+
+    /// Register the commands of a scope, unless their hash matches the registration store,
+    /// then store their hash. Returns the registered commands, and whether they were
+    /// replaced.
+    async fn register_set(
+        &self,
+        application_id: Snowflake,
+        set: &CommandSet,
+        wait: bool,
+    ) -> Result<(Vec<ApplicationCommand>, bool)> {
+        let Some(store) = &self.inner.registration_store else {
+            return self.overwrite_set(application_id, set, wait).await;
+        };
+        let hash = command::hash(application_id, set)?;
+        match store.load(set.guild_id).await {
+            Ok(stored) if stored.as_deref() == Some(hash.as_str()) => {
+                return Ok((Vec::new(), false))
+            }
+            Ok(_) => {}
+            Err(err) => {
+                tracing::warn!(guild_id = ?set.guild_id, error = %err, "failed to load registered commands hash");
+            }
+        }
+        let registered = self.overwrite_set(application_id, set, wait).await?;
+        if let Err(err) = store.save(set.guild_id, &hash).await {
+            tracing::warn!(guild_id = ?set.guild_id, error = %err, "failed to save registered commands hash");
+        }
+        Ok(registered)
+    }
+
+    /// Replace the commands of a scope, unless the registered ones are already up to date,
+    /// waiting for the registration interval first if `wait` is set. Returns the registered
+    /// commands, and whether they were replaced.
+    async fn overwrite_set(
+        &self,
+        application_id: Snowflake,
+        set: &CommandSet,
+        wait: bool,
+    ) -> Result<(Vec<ApplicationCommand>, bool)> {
+        let http = &self.inner.http;
+        let current = self
+            .registered_commands(application_id, set.guild_id)
+            .await?;
+        if SyncPlan::new(set.guild_id, &set.commands, &current).is_empty() {
+            return Ok((current, false));
+        }
+        if wait {
+            runtime::sleep(self.inner.registration_interval).await;
+        }
+        let commands = match set.guild_id {
+            Some(guild_id) => {
+                http.bulk_overwrite_guild_commands(application_id, guild_id, &set.commands)
+                    .await?
+            }
+            None => {
+                http.bulk_overwrite_global_commands(application_id, &set.commands)
+                    .await?
+            }
+        };
+        Ok((commands, true))
+    }
+
+    /// Fetch the commands registered to a scope.
+    async fn registered_commands(
+        &self,
+        application_id: Snowflake,
+        guild_id: Option<Snowflake>,
+    ) -> Result<Vec<ApplicationCommand>> {
+        let http = &self.inner.http;
+        match guild_id {
+            Some(guild_id) => http.get_guild_commands(application_id, guild_id).await,
+            None => http.get_global_commands(application_id).await,
+        }
+    }
+
+    /// Compare the commands with those registered with Discord, returning the changes
+    /// registering them would make to each scope, without making them.
+    pub async fn plan_commands(&self) -> Result<Vec<SyncPlan>> {
+        let application_id = self.application_id()?;
+        let mut plans = Vec::new();
+        for set in self.command_sets()? {
+            let registered = self
+                .registered_commands(application_id, set.guild_id)
+                .await?;
+            plans.push(SyncPlan::new(set.guild_id, &set.commands, &registered));
+        }
+        Ok(plans)
+    }
+
+    async fn log_plans(&self) -> Result<()> {
+        for plan in self.plan_commands().await? {
+            tracing::info!("dry run, not registering {}", plan);
+        }
+        Ok(())
+    }
+
+    /// Returns the commands interactions are currently dispatched to, along with the ids
+    /// Discord assigned to them once registered.
+    pub fn router(&self) -> Arc<Router> {
+        self.inner
+            .router
+            .read()
+            .unwrap_or_else(|err| err.into_inner())
+            .clone()
+    }
+
+    fn application_id(&self) -> Result<Snowflake> {
+        self.inner
+            .application_id
+            .ok_or(Error::MissingConfig("application ID"))
+    }
+
+    /// Check that the configured application ID is the application of the bot's token.
+    async fn validate_application(&self) -> Result<()> {
+        let application = self.inner.http.get_current_application().await?;
+        if application.id != self.application_id()? {
+            tracing::error!(
+                application_id = %application.id,
+                configured = ?self.inner.application_id,
+                "the application ID doesn't match the token"
+            );
+            return Err(Error::InvalidConfig("application ID"));
+        }
+        Ok(())
+    }
+
+    /// Run the bot, registering its commands and dispatching interactions until it is shut
+    /// down or the connection to Discord fails.
+    pub async fn run(&self) -> Result<()> {
+        let transport = self
+            .inner
+            .transport
+            .as_ref()
+            .ok_or(Error::MissingConfig("address to listen on"))?;
+        if self.inner.validate_application {
+            self.validate_application().await?;
+        }
+        if self.inner.register_commands && self.inner.dry_run {
+            self.log_plans().await?;
+        } else if self.inner.register_commands {
+            self.register_commands().await?;
+        }
+        self.set_ready(true);
+        let result = self.serve(transport).await;
+        self.set_ready(false);
+        let remaining = self
+            .inner
+            .in_flight
+            .drain(self.inner.shutdown_timeout)
+            .await;
+        if remaining > 0 {
+            tracing::warn!(
+                remaining,
+                "interactions were still being handled at shutdown"
+            );
+        }
+        result
+    }
+
+    /// Receive interactions until the transport stops.
+    #[allow(unused_variables)]
+    async fn serve(&self, transport: &Transport) -> Result<()> {
+        match *transport {
+            #[cfg(feature = "gateway")]
+            Transport::Gateway(ref gateway) => self.run_gateway(gateway).await,
+            #[cfg(feature = "webhook")]
+            Transport::Http(ref listen) => crate::http::server::serve(listen, self.clone()).await,
+        }
+    }
+
+    #[cfg(feature = "gateway")]
+    async fn run_gateway(&self, gateway: &Gateway) -> Result<()> {
+        // Subscribed before connecting, so that no interaction is missed.
+        let mut events = Box::pin(gateway.events());
+        let listener = async {
+            while let Some(event) = events.next().await {
+                let data = match event {
+                    GatewayEvent::Raw { name, data } if name == "INTERACTION_CREATE" => data,
+                    _ => continue,
+                };
+                let interaction: Interaction = match model::from_value(data) {
+                    Ok(interaction) => interaction,
+                    Err(err) => {
+                        tracing::warn!(error = %err, "failed to deserialize interaction");
+                        continue;
+                    }
+                };
+                self.spawn_dispatch(self.context(interaction).with_gateway(gateway.clone()));
+            }
+        };
+        let run = async {
+            tokio::select! {
+                result = gateway.run() => result,
+                () = listener => Ok(()),
+            }
+        };
+        tokio::pin!(run);
+        tokio::select! {
+            result = &mut run => result,
+            () = self.inner.shutdown.wait() => {
+                // Keeps running until every shard has closed its connection.
+                gateway.shutdown();
+                run.await
+            }
+        }
+    }
+
+    /// Create the context of an interaction.
+    pub(crate) fn context(&self, interaction: Interaction) -> Context {
+        Context::new(interaction, self.inner.http.clone())
+            .with_collectors(self.inner.collectors.clone())
+    }
+
+    /// Returns the application's public key, if it was given.
+    #[cfg(feature = "verify")]
+    pub(crate) fn public_key(&self) -> Option<&ed25519_dalek::VerifyingKey> {
+        self.inner.public_key.as_ref()
+    }
+
+    /// Whether the commands are registered and interactions are being dispatched.
+    #[cfg(feature = "webhook")]
+    pub(crate) fn is_ready(&self) -> bool {
+        self.inner.ready.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn set_ready(&self, ready: bool) {
+        self.inner.ready.store(ready, Ordering::Relaxed);
+    }
+
+    /// Returns how long shutting down waits for interactions being handled to finish.
+    #[cfg(feature = "webhook")]
+    pub(crate) fn shutdown_timeout(&self) -> Duration {
+        self.inner.shutdown_timeout
+    }
+
+    /// Dispatch an interaction to the collectors and component handlers, in order of priority,
+    /// then to the commands. Pings are answered with a pong before reaching any of them.
+    ///
+    /// Everything logged meanwhile is in the span of the interaction, recording its ID.
+    pub(crate) async fn dispatch(&self, context: Context) {
+        let _guard = self.inner.in_flight.start();
+        let span = context.span();
+        #[cfg(not(target_arch = "wasm32"))]
+        let start = std::time::Instant::now();
+        async {
+            if context.is_ping() {
+                if let Err(err) = context.respond(InteractionResponse::pong()).await {
+                    tracing::warn!(error = %err, "failed to respond to ping");
+                }
+                return;
+            }
+            if let Some(context) = self.dispatch_component(context).await {
+                let router = self.router();
+                let handlers = router.handlers(context.guild_id());
+                #[cfg_attr(target_arch = "wasm32", allow(unused_variables))]
+                let result = dispatch::dispatch(handlers, context.clone()).await;
+                #[cfg(not(target_arch = "wasm32"))]
+                self.complete(&context, start.elapsed(), result.as_ref().map(|_| ()));
+            }
+        }
+        .instrument(span)
+        .await
+    }
+
+    /// Dispatch an interaction in the background. It's tracked as in flight from now on, so
+    /// that shutting down waits for it even if it hasn't started yet.
+    pub(crate) fn spawn_dispatch(&self, context: Context) {
+        let guard = self.inner.in_flight.start();
+        let client = self.clone();
+        runtime::spawn(async move {
+            client.dispatch(context).await;
+            drop(guard);
+        });
+    }
+
+    /// Report a command handled to the callback set with
+    /// [`on_command_complete`](ClientBuilder::on_command_complete), if any.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn complete(&self, context: &Context, duration: Duration, result: Result<(), &Rejection>) {
+        let (callback, data) = match (&self.inner.on_command_complete, context.command_data()) {
+            // Autocomplete interactions carry command data too, but aren't command executions.
+            (Some(callback), Some(data))
+                if context.interaction().ty == InteractionType::ApplicationCommand =>
+            {
+                (callback, data)
+            }
+            _ => return,
+        };
+        callback(&CommandCompletion {
+            name: &data.name,
+            context,
+            duration,
+            result,
+        });
+    }
+
+    /// Offer an interaction to the collectors and component handlers, returning it back if
+    /// neither accepts it.
+    async fn dispatch_component(&self, context: Context) -> Option<Context> {
+        let collectors = &self.inner.collectors;
+        let components = &self.inner.components;
+        match self.inner.component_priority {
+            ComponentPriority::Collectors => {
+                let context = collectors.collect(context)?;
+                dispatch::dispatch_component(components, context).await
+            }
+            ComponentPriority::Handlers => {
+                let context = dispatch::dispatch_component(components, context).await?;
+                collectors.collect(context)
+            }
+        }
+    }
+}
+
+impl fmt::Debug for Client {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Client")
+            .field("application_id", &self.inner.application_id)
+            .field("router", &*self.router())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(all(test, feature = "verify"))]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use serde_json::json;
+
+    use crate::{model::permissions::Permissions, permission, test_util, Client, Context, Filter};
+
+    #[tokio::test]
+    async fn test_on_command_complete() {
+        let completed = Arc::new(Mutex::new(vec![]));
+        let client = {
+            let completed = completed.clone();
+            Client::builder()
+                .public_key("0".repeat(64))
+                .command(crate::command("ping").map(|_: Context| {}))
+                .command(
+                    crate::command("purge")
+                        .and(permission::require(Permissions::MANAGE_MESSAGES))
+                        .map(|_: Context| {}),
+                )
+                .on_command_complete(move |completion| {
+                    let result = completion
+                        .result
+                        .map_err(|rejection| rejection.is_missing_permissions());
+                    completed
+                        .lock()
+                        .unwrap()
+                        .push((completion.name.to_string(), result));
+                })
+                .build_webhook()
+                .unwrap()
+        };
+
+        for name in ["ping", "purge"] {
+            let interaction = test_util::interaction(json!({
+                "data": { "id": "940565712559304736", "name": name, "type": 1 }
+            }));
+            client.dispatch(client.context(interaction)).await;
+        }
+        let autocomplete = test_util::interaction(json!({
+            "type": 4,
+            "data": { "id": "940565712559304736", "name": "ping", "type": 1 }
+        }));
+        client.dispatch(client.context(autocomplete)).await;
+        assert_eq!(
+            *completed.lock().unwrap(),
+            [
+                ("ping".to_string(), Ok(())),
+                ("purge".to_string(), Err(true))
+            ]
+        );
+    }
+}
@@ -0,0 +1,198 @@
+//! Defines the `ComponentCollector`, which receives the component interactions on a message
+//! from within a handler.
+use std::{
+    fmt,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    task::{Context as TaskContext, Poll},
+    time::Duration,
+};
+
+use futures_util::{FutureExt, Stream};
+use tokio::sync::mpsc;
+
+use crate::{
+    context::Context,
+    model::snowflake::Snowflake,
+    runtime::{self, Timer},
+};
+
+/// A callback deciding whether a collector receives a component interaction.
+type CollectorFilter = Box<dyn Fn(&Context) -> bool + Send + Sync>;
+
+struct Entry {
+    id: u64,
+    message_id: Snowflake,
+    filter: CollectorFilter,
+    sender: mpsc::UnboundedSender<Context>,
+}
+
+/// The collectors waiting for component interactions, shared by every context of a client.
+#[derive(Default)]
+pub(crate) struct Collectors {
+    next_id: AtomicU64,
+    entries: Mutex<Vec<Entry>>,
+}
+
+impl Collectors {
+    /// Start collecting the component interactions on a message accepted by the filter.
+    pub(crate) fn register(
+        self: &Arc<Self>,
+        message_id: Snowflake,
+        filter: CollectorFilter,
+        timeout: Duration,
+    ) -> ComponentCollector {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let (sender, receiver) = mpsc::unbounded_channel();
+        self.lock().push(Entry {
+            id,
+            message_id,
+            filter,
+            sender,
+        });
+        ComponentCollector {
+            id,
+            collectors: self.clone(),
+            receiver,
+            deadline: runtime::timer(timeout),
+        }
+    }
+
+    /// Send a component interaction to the first collector accepting it. Returns the context
+    /// back if no collector does, so that it can be dispatched to the handlers instead.
+    pub(crate) fn collect(&self, context: Context) -> Option<Context> {
+        let message_id = match context.message() {
+            Some(message) if context.custom_id().is_some() => message.id,
+            _ => return Some(context),
+        };
+        let entries = self.lock();
+        let entry = entries
+            .iter()
+            .find(|entry| entry.message_id == message_id && (entry.filter)(&context));
+        match entry {
+            // Only fails if the collector was dropped without being removed yet.
+            Some(entry) => entry.sender.send(context).err().map(|err| err.0),
+            None => Some(context),
+        }
+    }
+
+    fn remove(&self, id: u64) {
+        self.lock().retain(|entry| entry.id != id);
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<Entry>> {
+        self.entries.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+impl fmt::Debug for Collectors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Collectors")
+            .field("entries", &self.lock().len())
+            .finish()
+    }
+}
+
+/// A [`Stream`] of the component interactions on a message, created with
+/// [`Context::await_component`].
+///
+/// The stream ends once its timeout elapses. Each interaction must still be responded to, such
+/// as with [`Context::defer_update`], within 3 seconds.
+pub struct ComponentCollector {
+    id: u64,
+    collectors: Arc<Collectors>,
+    receiver: mpsc::UnboundedReceiver<Context>,
+    deadline: Timer,
+}
+
+impl ComponentCollector {
+    /// Wait for the next component interaction, or `None` once the timeout elapses.
+    pub async fn next(&mut self) -> Option<Context> {
+        futures_util::StreamExt::next(self).await
+    }
+}
+
+impl Stream for ComponentCollector {
+    type Item = Context;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<Option<Context>> {
+        if let Poll::Ready(context) = self.receiver.poll_recv(cx) {
+            return Poll::Ready(context);
+        }
+        if self.deadline.poll_unpin(cx).is_ready() {
+            self.receiver.close();
+            self.collectors.remove(self.id);
+            return Poll::Ready(None);
+        }
+        Poll::Pending
+    }
+}
+
+impl Drop for ComponentCollector {
+    fn drop(&mut self) {
+        self.collectors.remove(self.id);
+    }
+}
+
+impl fmt::Debug for ComponentCollector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ComponentCollector")
+            .field("id", &self.id)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, time::Duration};
+
+    use serde_json::json;
+
+    use super::Collectors;
+    use crate::{context::Context, test_util};
+
+    fn click(custom_id: &str) -> Context {
+        test_util::context(json!({
+            "type": 3,
+            "data": { "custom_id": custom_id, "component_type": 2 },
+            "message": {
+                "id": "3",
+                "channel_id": "4",
+                "author": { "id": "2", "username": "slash", "discriminator": "0" },
+                "content": "confirm?",
+                "timestamp": "2024-01-01T00:00:00Z",
+                "edited_timestamp": null,
+                "type": 0
+            }
+        }))
+    }
+
+    #[tokio::test]
+    async fn test_collector_receives_matching_clicks() {
+        let collectors = Arc::new(Collectors::default());
+        let mut collector = collectors.register(
+            3.into(),
+            Box::new(|click: &Context| click.custom_id() == Some("confirm")),
+            Duration::from_secs(5),
+        );
+        assert!(collectors.collect(click("cancel")).is_some());
+        assert!(collectors.collect(click("confirm")).is_none());
+        let received = collector.next().await.unwrap();
+        assert_eq!(received.custom_id(), Some("confirm"));
+
+        drop(collector);
+        assert!(collectors.collect(click("confirm")).is_some());
+    }
+
+    #[tokio::test]
+    async fn test_collector_ends_after_timeout() {
+        let collectors = Arc::new(Collectors::default());
+        let mut collector =
+            collectors.register(3.into(), Box::new(|_: &Context| true), Duration::ZERO);
+        assert!(collector.next().await.is_none());
+        assert!(collectors.collect(click("confirm")).is_some());
+    }
+}
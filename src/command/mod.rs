@@ -0,0 +1,509 @@
+mod store;
+pub(crate) mod sync;
+mod tree;
+pub(crate) mod validate;
+
+use std::{pin::Pin, task::Poll};
+
+use futures_util::Future;
+
+use crate::{
+    context::{self, Context},
+    filter::{Filter, FilterBase, FilterMeta, Internal},
+    generic::One,
+    model::command::{
+        ApplicationCommand, ApplicationCommandOption, ApplicationCommandType, CommandOptionType,
+        IntegrationType, InteractionContextType,
+    },
+    reject::{self, Rejection},
+};
+
+pub(crate) use self::store::hash;
+#[cfg(not(target_arch = "wasm32"))]
+pub use self::store::FileStore;
+pub use self::store::RegistrationStore;
+pub use self::sync::{
+    CommandSet, RegistrationProgress, RegistrationStatus, SyncOperation, SyncPlan,
+};
+pub use self::validate::ValidationError;
+
+/// Returns a new command object.
+pub fn command(name: &str) -> Command {
+    Command::new(name, ApplicationCommandType::ChatInput)
+}
+
+/// Returns a new command shown when right-clicking or tapping on a user.
+pub fn user_command(name: &str) -> UserCommand {
+    UserCommand(Command::new(name, ApplicationCommandType::User))
+}
+
+/// Returns a new command shown when right-clicking or tapping on a message.
+pub fn message_command(name: &str) -> MessageCommand {
+    MessageCommand(Command::new(name, ApplicationCommandType::Message))
+}
+
+#[derive(Clone, Debug)]
+pub struct Command {
+    command: ApplicationCommand,
+}
+
+impl Command {
+    fn new(name: &str, ty: ApplicationCommandType) -> Self {
+        Command {
+            command: ApplicationCommand::new(ty, name),
+        }
+    }
+
+    /// Returns the type of this command.
+    pub fn ty(&self) -> ApplicationCommandType {
+        self.command.ty
+    }
+    /// Set the name of this command.
+    pub fn name<S: AsRef<str>>(mut self, name: S) -> Self {
+        self.command.name = name.as_ref().to_string();
+        self
+    }
+    /// Set the description of this command.
+    pub fn description<S: AsRef<str>>(mut self, description: S) -> Self {
+        self.command.description = description.as_ref().to_string();
+        self
+    }
+    /// Set the installation contexts this command is available in.
+    ///
+    /// Defaults to the application's configured contexts when unset.
+    pub fn integration_types<I: IntoIterator<Item = IntegrationType>>(mut self, types: I) -> Self {
+        self.command.integration_types = Some(types.into_iter().collect());
+        self
+    }
+    /// Set the interaction contexts this command can be used in.
+    pub fn contexts<I: IntoIterator<Item = InteractionContextType>>(mut self, contexts: I) -> Self {
+        self.command.contexts = Some(contexts.into_iter().collect());
+        self
+    }
+    /// Build and validate the registration payload for this command.
+    ///
+    /// Options are declared by the argument filters chained onto the command, and are not
+    /// included here unless the command was created from a registered one; use [`commands`]
+    /// to collect them from a complete filter.
+    pub fn build(&self) -> Result<ApplicationCommand, ValidationError> {
+        validate::command(&self.command)?;
+        Ok(self.command.clone())
+    }
+}
+
+/// Returns a new subcommand, to chain onto the command it belongs to.
+///
+/// A command's subcommands are declared by chaining them onto it with [`or`](Filter::or), and
+/// the arguments chained onto a subcommand become its options:
+///
+/// ```
+/// use slash::{arg, command::subcommand, Context, Filter};
+///
+/// let role = slash::command("role").description("Manage roles").and(
+///     subcommand("add", "Give a role")
+///         .and(arg::string("role", "The role to give"))
+///         .map(|_context: Context, _role: String| {})
+///         .or(subcommand("list", "List your roles").map(|_context: Context| {})),
+/// );
+/// ```
+pub fn subcommand(name: &str, description: &str) -> SubCommand {
+    SubCommand {
+        group: None,
+        option: ApplicationCommandOption::new(CommandOptionType::SubCommand, name, description),
+    }
+}
+
+/// Returns a new group of subcommands, whose subcommands are created with
+/// [`Group::subcommand`].
+pub fn group(name: &str, description: &str) -> Group {
+    Group {
+        option: ApplicationCommandOption::new(
+            CommandOptionType::SubCommandGroup,
+            name,
+            description,
+        ),
+    }
+}
+
+/// A group of subcommands, nested under a command.
+#[derive(Clone, Debug)]
+pub struct Group {
+    option: ApplicationCommandOption,
+}
+
+impl Group {
+    /// Returns a new subcommand of this group.
+    ///
+    /// The subcommands of a group should be declared one after another, as the group is only
+    /// declared again by a subcommand following a different one.
+    pub fn subcommand(&self, name: &str, description: &str) -> SubCommand {
+        SubCommand {
+            group: Some(self.option.clone()),
+            ..subcommand(name, description)
+        }
+    }
+}
+
+/// A subcommand of a command, or of a [`Group`], matching the interactions that use it.
+#[derive(Clone, Debug)]
+pub struct SubCommand {
+    group: Option<ApplicationCommandOption>,
+    option: ApplicationCommandOption,
+}
+
+impl FilterBase for SubCommand {
+    type Extract = One<Context>;
+    type Error = Rejection;
+    type Future = CommandFut;
+
+    fn filter(&self, _: Internal) -> Self::Future {
+        let result = context::with(|context| {
+            let data = context.command_data().ok_or_else(reject::not_found)?;
+            let mut options = &data.options;
+            if let Some(ref group) = self.group {
+                match options.first() {
+                    Some(option)
+                        if option.ty == CommandOptionType::SubCommandGroup
+                            && option.name == group.name =>
+                    {
+                        options = &option.options;
+                    }
+                    _ => return Err(reject::not_found()),
+                }
+            }
+            match options.first() {
+                Some(option)
+                    if option.ty == CommandOptionType::SubCommand
+                        && option.name == self.option.name =>
+                {
+                    Ok((context.clone(),))
+                }
+                _ => Err(reject::not_found()),
+            }
+        });
+        CommandFut {
+            result: Some(result),
+        }
+    }
+
+    fn meta(&self, _: Internal, visitor: &mut dyn FilterMeta) {
+        visitor.subcommand(self.group.as_ref(), &self.option);
+    }
+}
+
+/// Collects the registration payloads of the commands declared by a filter, nesting each
+/// subcommand and option under the command declared before it.
+#[derive(Debug, Default)]
+pub(crate) struct CommandCollector {
+    pub(crate) commands: Vec<ApplicationCommand>,
+}
+
+impl FilterMeta for CommandCollector {
+    fn command(&mut self, command: &ApplicationCommand) {
+        self.commands.push(command.clone());
+    }
+
+    fn subcommand(
+        &mut self,
+        group: Option<&ApplicationCommandOption>,
+        subcommand: &ApplicationCommandOption,
+    ) {
+        let Some(command) = self.commands.last_mut() else {
+            return;
+        };
+        let options = match group {
+            Some(group) => {
+                let declared = command.options.last().is_some_and(|option| {
+                    option.ty == CommandOptionType::SubCommandGroup && option.name == group.name
+                });
+                if !declared {
+                    command.options.push(group.clone());
+                }
+                &mut command
+                    .options
+                    .last_mut()
+                    .expect("group was declared")
+                    .options
+            }
+            None => &mut command.options,
+        };
+        options.push(subcommand.clone());
+    }
+
+    fn option(&mut self, option: &ApplicationCommandOption) {
+        if let Some(command) = self.commands.last_mut() {
+            declared_options(command).push(option.clone());
+        }
+    }
+}
+
+/// Returns the options of the subcommand declared last in a command, or of the command itself
+/// if it has no subcommands, which are those that arguments chained after it declare.
+fn declared_options(command: &mut ApplicationCommand) -> &mut Vec<ApplicationCommandOption> {
+    let mut options = &mut command.options;
+    while options.last().is_some_and(|option| {
+        matches!(
+            option.ty,
+            CommandOptionType::SubCommand | CommandOptionType::SubCommandGroup
+        )
+    }) {
+        options = &mut options.last_mut().expect("option was found").options;
+    }
+    options
+}
+
+/// Create a command from one registered with Discord, such as one fetched with
+/// [`get_global_commands`](crate::rest::Client::get_global_commands), so that it can be tweaked
+/// and registered again. Its options are kept, and its ID too so that registering it updates
+/// the existing command.
+///
+/// Fails if it isn't a `CHAT_INPUT` command: context-menu commands convert into a
+/// [`UserCommand`] or [`MessageCommand`] instead.
+impl TryFrom<ApplicationCommand> for Command {
+    type Error = ValidationError;
+
+    fn try_from(command: ApplicationCommand) -> Result<Self, Self::Error> {
+        Command::registered(command, ApplicationCommandType::ChatInput)
+    }
+}
+
+impl Command {
+    fn registered(
+        mut command: ApplicationCommand,
+        ty: ApplicationCommandType,
+    ) -> Result<Self, ValidationError> {
+        if command.ty != ty {
+            return Err(ValidationError::WrongType {
+                name: command.name,
+                expected: ty,
+                found: command.ty,
+            });
+        }
+        // Set by Discord, depending on where and when the command is registered.
+        command.application_id = None;
+        command.guild_id = None;
+        command.version = None;
+        Ok(Command { command })
+    }
+}
+
+/// Collects and validates the registration payloads of every command declared by a filter,
+/// including the options declared by the arguments chained onto each command.
+pub fn commands<F: Filter>(filter: &F) -> Result<Vec<ApplicationCommand>, ValidationError> {
+    let mut collector = CommandCollector::default();
+    filter.visit(&mut collector);
+    validate::commands(&collector.commands)?;
+    Ok(collector.commands)
+}
+
+impl FilterBase for Command {
+    type Extract = One<Context>;
+    type Error = Rejection;
+    type Future = CommandFut;
+
+    fn filter(&self, _: Internal) -> Self::Future {
+        let result = context::with(|context| {
+            let data = context.command_data().ok_or_else(reject::not_found)?;
+            if data.ty != self.command.ty || data.name != self.command.name {
+                return Err(reject::not_found());
+            }
+            Ok((context.clone(),))
+        });
+        CommandFut {
+            result: Some(result),
+        }
+    }
+
+    fn meta(&self, _: Internal, visitor: &mut dyn FilterMeta) {
+        visitor.command(&self.command);
+    }
+}
+
+macro_rules! context_menu_command {
+    ($(#[$doc:meta])* $name:ident, $ty:ident) => {
+        $(#[$doc])*
+        ///
+        /// Context-menu commands have no description, so unlike [`Command`] none can be set.
+        #[derive(Clone, Debug)]
+        pub struct $name(Command);
+
+        impl $name {
+            /// Returns the type of this command.
+            pub fn ty(&self) -> ApplicationCommandType {
+                self.0.ty()
+            }
+            /// Set the name of this command.
+            pub fn name<S: AsRef<str>>(self, name: S) -> Self {
+                Self(self.0.name(name))
+            }
+            /// Set the installation contexts this command is available in.
+            pub fn integration_types<I: IntoIterator<Item = IntegrationType>>(self, types: I) -> Self {
+                Self(self.0.integration_types(types))
+            }
+            /// Set the interaction contexts this command can be used in.
+            pub fn contexts<I: IntoIterator<Item = InteractionContextType>>(self, contexts: I) -> Self {
+                Self(self.0.contexts(contexts))
+            }
+            /// Build and validate the registration payload for this command.
+            pub fn build(&self) -> Result<ApplicationCommand, ValidationError> {
+                self.0.build()
+            }
+        }
+
+        /// Create a command from one registered with Discord, so that it can be tweaked and
+        /// registered again, keeping its ID. Fails if it is of another type.
+        impl TryFrom<ApplicationCommand> for $name {
+            type Error = ValidationError;
+
+            fn try_from(command: ApplicationCommand) -> Result<Self, Self::Error> {
+                Command::registered(command, ApplicationCommandType::$ty).map(Self)
+            }
+        }
+
+        impl FilterBase for $name {
+            type Extract = One<Context>;
+            type Error = Rejection;
+            type Future = CommandFut;
+
+            fn filter(&self, internal: Internal) -> Self::Future {
+                self.0.filter(internal)
+            }
+
+            fn meta(&self, internal: Internal, visitor: &mut dyn FilterMeta) {
+                self.0.meta(internal, visitor)
+            }
+        }
+    };
+}
+
+context_menu_command!(
+    /// A command run on a user, resolvable through [`Context::target_user`].
+    UserCommand,
+    User
+);
+
+context_menu_command!(
+    /// A command run on a message, resolvable through [`Context::target_message`].
+    MessageCommand,
+    Message
+);
+
+pub struct CommandFut {
+    result: Option<Result<One<Context>, Rejection>>,
+}
+
+impl Future for CommandFut {
+    type Output = Result<One<Context>, Rejection>;
+
+    fn poll(
+        mut self: Pin<&mut Self>,
+        _: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Self::Output> {
+        Poll::Ready(self.result.take().expect("polled after complete"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::ValidationError;
+    use crate::{
+        arg,
+        model::command::{ApplicationCommand, ApplicationCommandType},
+        Filter,
+    };
+
+    #[test]
+    fn test_commands_serialize() {
+        let filter = super::command("echo")
+            .description("Echo a message")
+            .and(arg::string("text", "The text to echo"))
+            .or(super::user_command("Greet"));
+
+        let commands = serde_json::to_value(super::commands(&filter).unwrap()).unwrap();
+        assert_eq!(
+            commands,
+            json!([
+                {
+                    "type": 1,
+                    "name": "echo",
+                    "description": "Echo a message",
+                    "options": [
+                        {
+                            "type": 3,
+                            "name": "text",
+                            "description": "The text to echo",
+                            "required": true
+                        }
+                    ]
+                },
+                {
+                    "type": 2,
+                    "name": "Greet",
+                    "description": ""
+                }
+            ])
+        );
+    }
+
+    #[test]
+    fn test_command_from_registered() {
+        let registered: ApplicationCommand = serde_json::from_value(json!({
+            "id": "940565712559304736",
+            "application_id": "940565712559304735",
+            "type": 1,
+            "name": "echo",
+            "description": "Echo a message",
+            "options": [{ "type": 3, "name": "text", "description": "Text", "required": true }]
+        }))
+        .unwrap();
+
+        let command = super::Command::try_from(registered)
+            .unwrap()
+            .description("Repeat a message")
+            .build()
+            .unwrap();
+        assert_eq!(command.description, "Repeat a message");
+        assert_eq!(command.options.len(), 1);
+        assert_eq!(command.application_id, None);
+        assert!(command.id.is_some());
+    }
+
+    #[test]
+    fn test_user_command_from_registered() {
+        let registered: ApplicationCommand = serde_json::from_value(json!({
+            "id": "940565712559304736",
+            "application_id": "940565712559304735",
+            "guild_id": "940565712559304737",
+            "version": "940565712559304738",
+            "type": 2,
+            "name": "Greet",
+            "description": ""
+        }))
+        .unwrap();
+        assert_eq!(
+            super::Command::try_from(registered.clone()).unwrap_err(),
+            ValidationError::WrongType {
+                name: "Greet".to_string(),
+                expected: ApplicationCommandType::ChatInput,
+                found: ApplicationCommandType::User,
+            }
+        );
+        assert!(super::MessageCommand::try_from(registered.clone()).is_err());
+
+        let command = super::UserCommand::try_from(registered)
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(
+            serde_json::to_value(command).unwrap(),
+            json!({
+                "id": 940565712559304736u64,
+                "type": 2,
+                "name": "Greet",
+                "description": ""
+            })
+        );
+    }
+}
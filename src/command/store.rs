@@ -0,0 +1,157 @@
+//! Remembers the commands registered to each scope, so that clients restarted with the same
+//! commands skip registering them.
+#[cfg(not(target_arch = "wasm32"))]
+use std::{
+    collections::BTreeMap,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use async_trait::async_trait;
+
+use super::CommandSet;
+use crate::{errors::Result, model::snowflake::Snowflake};
+
+/// Stores a hash of the commands last registered to each scope, set with
+/// [`ClientBuilder::registration_store`](crate::ClientBuilder::registration_store).
+///
+/// When the commands a client declares for a scope hash to the stored value, the scope is
+/// left alone without making any request to Discord. Commands changed outside of the client,
+/// such as from another deployment of the bot, go unnoticed until the declared ones change.
+#[async_trait]
+pub trait RegistrationStore: Send + Sync {
+    /// Returns the hash of the commands last registered to a scope: a guild, or globally if
+    /// `guild_id` is `None`.
+    async fn load(&self, guild_id: Option<Snowflake>) -> Result<Option<String>>;
+    /// Replace the hash of the commands registered to a scope.
+    async fn save(&self, guild_id: Option<Snowflake>, hash: &str) -> Result<()>;
+}
+
+/// A [`RegistrationStore`] keeping the hashes in a JSON file, which is created once commands
+/// are first registered. Defaults to `.slash-commands.json` in the working directory.
+///
+/// Not available on `wasm32`, where there is no file system.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug)]
+pub struct FileStore {
+    path: PathBuf,
+    /// Serializes writes, which read the file back first.
+    lock: Mutex<()>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl FileStore {
+    /// Returns a store keeping the hashes in the file at the given path.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            lock: Mutex::new(()),
+        }
+    }
+
+    /// Returns the path of the file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Read every hash, keyed by scope. A missing file holds none.
+    fn read(&self) -> Result<BTreeMap<String, String>> {
+        match std::fs::read(&self.path) {
+            Ok(bytes) => Ok(serde_json::from_slice(&bytes)?),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(BTreeMap::new()),
+            Err(err) => Err(err.into()),
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Default for FileStore {
+    fn default() -> Self {
+        Self::new(".slash-commands.json")
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[async_trait]
+impl RegistrationStore for FileStore {
+    async fn load(&self, guild_id: Option<Snowflake>) -> Result<Option<String>> {
+        Ok(self.read()?.remove(&key(guild_id)))
+    }
+
+    async fn save(&self, guild_id: Option<Snowflake>, hash: &str) -> Result<()> {
+        let _lock = self.lock.lock().unwrap_or_else(|err| err.into_inner());
+        let mut hashes = self.read()?;
+        hashes.insert(key(guild_id), hash.to_owned());
+        std::fs::write(&self.path, serde_json::to_vec_pretty(&hashes)?)?;
+        Ok(())
+    }
+}
+
+/// Returns the key of a scope in a [`FileStore`].
+#[cfg(not(target_arch = "wasm32"))]
+fn key(guild_id: Option<Snowflake>) -> String {
+    match guild_id {
+        Some(guild_id) => guild_id.to_string(),
+        None => "global".to_owned(),
+    }
+}
+
+/// Hash the payload registering the commands of a scope for an application, with 64-bit
+/// FNV-1a, which unlike the standard library's hasher is the same across builds.
+pub(crate) fn hash(application_id: Snowflake, set: &CommandSet) -> Result<String> {
+    const OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    let payload = serde_json::to_vec(&set.commands)?;
+    let hash = application_id
+        .to_string()
+        .as_bytes()
+        .iter()
+        .chain(b":")
+        .chain(&payload)
+        .fold(OFFSET, |hash, &byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(PRIME)
+        });
+    Ok(format!("{:016x}", hash))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{hash, FileStore, RegistrationStore};
+    use crate::command::{command, CommandSet};
+
+    fn set(description: &str) -> CommandSet {
+        CommandSet {
+            guild_id: None,
+            commands: vec![command("ping").description(description).build().unwrap()],
+        }
+    }
+
+    #[test]
+    fn test_hash() {
+        let a = hash(1.into(), &set("Pong!")).unwrap();
+        assert_eq!(a, hash(1.into(), &set("Pong!")).unwrap());
+        assert_ne!(a, hash(1.into(), &set("Pong?")).unwrap());
+        assert_ne!(a, hash(2.into(), &set("Pong!")).unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_file_store() {
+        let path = std::env::temp_dir().join(format!("slash-store-{}.json", std::process::id()));
+        let store = FileStore::new(&path);
+        assert_eq!(store.load(None).await.unwrap(), None);
+
+        store.save(None, "a").await.unwrap();
+        store.save(Some(1.into()), "b").await.unwrap();
+        store.save(None, "c").await.unwrap();
+        let store = FileStore::new(&path);
+        assert_eq!(store.load(None).await.unwrap().as_deref(), Some("c"));
+        assert_eq!(
+            store.load(Some(1.into())).await.unwrap().as_deref(),
+            Some("b")
+        );
+        assert_eq!(store.load(Some(2.into())).await.unwrap(), None);
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,202 @@
+//! Planning of command registration, by comparing the commands a client declares with those
+//! registered with Discord.
+use std::fmt;
+
+use crate::{
+    errors::Error,
+    model::{command::ApplicationCommand, snowflake::Snowflake},
+};
+
+/// The commands registered to a single scope: globally, or to a guild.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommandSet {
+    /// The guild the commands are registered to, or `None` if they are global.
+    pub guild_id: Option<Snowflake>,
+    /// The commands to register.
+    pub commands: Vec<ApplicationCommand>,
+}
+
+/// A change registering the declared commands would make to the registered ones.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SyncOperation {
+    /// A declared command isn't registered yet, and would be created.
+    Create(ApplicationCommand),
+    /// A registered command differs from its declaration, and would be updated.
+    Update {
+        /// The command as it is registered.
+        registered: ApplicationCommand,
+        /// The command as it is declared.
+        declared: ApplicationCommand,
+    },
+    /// A registered command is no longer declared, and would be deleted.
+    Delete(ApplicationCommand),
+}
+
+impl SyncOperation {
+    /// Returns the name of the command the operation applies to.
+    pub fn name(&self) -> &str {
+        match self {
+            SyncOperation::Create(command) | SyncOperation::Delete(command) => &command.name,
+            SyncOperation::Update { declared, .. } => &declared.name,
+        }
+    }
+}
+
+/// The changes registering the declared commands would make, computed without making them by
+/// [`Client::plan_commands`](crate::Client::plan_commands).
+///
+/// Its `Display` implementation lists one operation per line, for operators to review.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SyncPlan {
+    /// The guild the commands are registered to, or `None` if they are global.
+    pub guild_id: Option<Snowflake>,
+    /// The operations to carry out, in the order the commands are declared and then
+    /// registered.
+    pub operations: Vec<SyncOperation>,
+    /// The number of declared commands that are already registered as they are.
+    pub unchanged: usize,
+}
+
+impl SyncPlan {
+    /// Compare the declared commands with the registered ones. Commands are matched by type
+    /// and name.
+    pub(crate) fn new(
+        guild_id: Option<Snowflake>,
+        declared: &[ApplicationCommand],
+        registered: &[ApplicationCommand],
+    ) -> Self {
+        let mut plan = SyncPlan {
+            guild_id,
+            ..Default::default()
+        };
+        for command in declared {
+            match registered.iter().find(|other| is_same(command, other)) {
+                None => plan.operations.push(SyncOperation::Create(command.clone())),
+                Some(other) if is_up_to_date(command, other) => plan.unchanged += 1,
+                Some(other) => plan.operations.push(SyncOperation::Update {
+                    registered: other.clone(),
+                    declared: command.clone(),
+                }),
+            }
+        }
+        for command in registered {
+            if !declared.iter().any(|other| is_same(command, other)) {
+                plan.operations.push(SyncOperation::Delete(command.clone()));
+            }
+        }
+        plan
+    }
+
+    /// Returns whether the registered commands are already up to date.
+    pub fn is_empty(&self) -> bool {
+        self.operations.is_empty()
+    }
+}
+
+impl fmt::Display for SyncPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.guild_id {
+            Some(guild_id) => write!(f, "commands of guild {}", guild_id)?,
+            None => write!(f, "global commands")?,
+        }
+        write!(
+            f,
+            ": {} to change, {} unchanged",
+            self.operations.len(),
+            self.unchanged
+        )?;
+        for operation in &self.operations {
+            let symbol = match operation {
+                SyncOperation::Create(_) => '+',
+                SyncOperation::Update { .. } => '~',
+                SyncOperation::Delete(_) => '-',
+            };
+            write!(f, "\n  {} {}", symbol, operation.name())?;
+        }
+        Ok(())
+    }
+}
+
+/// The progress of registering commands, reported once each scope is done.
+#[derive(Debug)]
+pub struct RegistrationProgress<'a> {
+    /// The guild of the scope, or `None` for global commands.
+    pub guild_id: Option<Snowflake>,
+    /// The number of scopes done so far, including this one.
+    pub completed: usize,
+    /// The number of scopes to register.
+    pub total: usize,
+    /// How registering the scope went.
+    pub status: RegistrationStatus<'a>,
+}
+
+/// How registering the commands of a scope went.
+#[derive(Debug)]
+pub enum RegistrationStatus<'a> {
+    /// The commands were registered, replacing the previous ones.
+    Registered(&'a [ApplicationCommand]),
+    /// The registered commands were already up to date, so were left alone.
+    Unchanged,
+    /// The commands couldn't be registered, and the previous ones were kept.
+    Failed(&'a Error),
+}
+
+/// Whether two commands are the same command, which may differ in their other fields.
+fn is_same(a: &ApplicationCommand, b: &ApplicationCommand) -> bool {
+    a.ty == b.ty && a.name == b.name
+}
+
+/// Whether a registered command matches its declaration. Installation and interaction
+/// contexts that aren't declared are left to the defaults Discord fills in.
+fn is_up_to_date(declared: &ApplicationCommand, registered: &ApplicationCommand) -> bool {
+    declared.description == registered.description
+        && declared.options == registered.options
+        && (declared.integration_types.is_none()
+            || declared.integration_types == registered.integration_types)
+        && (declared.contexts.is_none() || declared.contexts == registered.contexts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SyncOperation, SyncPlan};
+    use crate::model::command::{ApplicationCommand, ApplicationCommandType};
+
+    fn command(name: &str, description: &str) -> ApplicationCommand {
+        ApplicationCommand {
+            description: description.to_string(),
+            ..ApplicationCommand::new(ApplicationCommandType::ChatInput, name)
+        }
+    }
+
+    #[test]
+    fn test_plan_diffs_commands() {
+        let declared = [
+            command("ping", "Ping the bot"),
+            command("echo", "Echo a message"),
+            command("new", "A new command"),
+        ];
+        let registered = [
+            ApplicationCommand {
+                id: Some(1.into()),
+                ..command("ping", "Ping the bot")
+            },
+            command("echo", "Echo text"),
+            command("old", "An old command"),
+        ];
+        let plan = SyncPlan::new(None, &declared, &registered);
+
+        assert_eq!(plan.unchanged, 1);
+        assert!(matches!(
+            &plan.operations[..],
+            [
+                SyncOperation::Update { .. },
+                SyncOperation::Create(_),
+                SyncOperation::Delete(_),
+            ]
+        ));
+        assert_eq!(
+            plan.to_string(),
+            "global commands: 3 to change, 1 unchanged\n  ~ echo\n  + new\n  - old"
+        );
+    }
+}
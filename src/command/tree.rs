@@ -0,0 +1,190 @@
+//! The [`commands!`](crate::commands) macro, declaring a tree of commands in one block.
+
+/// Declare commands, their subcommands and groups, arguments and handlers in one block,
+/// expanding to the filter joining them all with [`or`](crate::Filter::or).
+///
+/// Each command or subcommand is given its arguments in parentheses, which may be left out if
+/// it has none, and the handler it is [`then`](crate::Filter::then)'d into, which is given the
+/// [`Context`](crate::Context) followed by the values extracted by the arguments. Commands with
+/// subcommands list them in braces instead, and subcommands can be grouped once.
+///
+/// # Example
+///
+/// ```
+/// use slash::{arg, Context};
+///
+/// async fn ping(_context: Context) {}
+/// async fn add_role(_context: Context, _role: String) {}
+/// async fn purge(_context: Context) {}
+///
+/// let commands = slash::commands! {
+///     command "ping": "Test the bot's latency" => ping;
+///     command "role": "Manage roles" {
+///         subcommand "add": "Give yourself a role" (arg::string("role", "The role")) => add_role;
+///         group "admin": "Administer roles" {
+///             subcommand "purge": "Delete unused roles" => purge;
+///         }
+///     }
+/// };
+/// # let _ = slash::Router::new().command(commands);
+/// ```
+#[macro_export]
+macro_rules! commands {
+    // Commands at the top level, joined once every one was munched.
+    (@commands ($($filter:expr),*)) => {
+        $crate::commands!(@join $($filter),*)
+    };
+    (@commands ($($filter:expr),*)
+        command $name:literal : $description:literal $(($($arg:expr),* $(,)?))? => $handler:expr;
+        $($rest:tt)*
+    ) => {
+        $crate::commands!(@commands ($($filter,)* $crate::commands!(
+            @handle ($crate::command($name).description($description)) [$($($arg),*)?] $handler
+        )) $($rest)*)
+    };
+    (@commands ($($filter:expr),*)
+        command $name:literal : $description:literal { $($body:tt)+ }
+        $($rest:tt)*
+    ) => {
+        $crate::commands!(@commands ($($filter,)* $crate::Filter::and(
+            $crate::command($name).description($description),
+            $crate::commands!(@subcommands [] () $($body)+),
+        )) $($rest)*)
+    };
+
+    // The subcommands of a command, or of the group in brackets.
+    (@subcommands $group:tt ($($filter:expr),*)) => {
+        $crate::commands!(@join $($filter),*)
+    };
+    (@subcommands $group:tt ($($filter:expr),*)
+        subcommand $name:literal : $description:literal $(($($arg:expr),* $(,)?))? => $handler:expr;
+        $($rest:tt)*
+    ) => {
+        $crate::commands!(@subcommands $group ($($filter,)* $crate::commands!(
+            @handle ($crate::commands!(@subcommand $group $name $description))
+            [$($($arg),*)?] $handler
+        )) $($rest)*)
+    };
+    (@subcommands [] ($($filter:expr),*)
+        group $name:literal : $description:literal { $($body:tt)+ }
+        $($rest:tt)*
+    ) => {
+        $crate::commands!(@subcommands [] ($($filter,)* $crate::commands!(
+            @subcommands [$name $description] () $($body)+
+        )) $($rest)*)
+    };
+
+    (@subcommand [] $name:literal $description:literal) => {
+        $crate::command::subcommand($name, $description)
+    };
+    (@subcommand [$group:literal $group_description:literal] $name:literal $description:literal) => {
+        $crate::command::group($group, $group_description).subcommand($name, $description)
+    };
+
+    // Chain the arguments of a command or subcommand, then its handler.
+    (@handle ($command:expr) [$($arg:expr),*] $handler:expr) => {{
+        let filter = $command;
+        $(let filter = $crate::Filter::and(filter, $arg);)*
+        $crate::Filter::then(filter, $handler)
+    }};
+
+    (@join $first:expr $(, $filter:expr)*) => {{
+        let filter = $first;
+        $(let filter = $crate::Filter::or(filter, $filter);)*
+        filter
+    }};
+
+    ($($tree:tt)+) => {
+        $crate::commands!(@commands () $($tree)+)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use serde_json::json;
+
+    use crate::{arg, context::Context, dispatch::Handler, test_util};
+
+    #[tokio::test]
+    async fn test_command_tree() {
+        let ran = Arc::new(Mutex::new(vec![]));
+        let handler = |name: &'static str| {
+            let ran = ran.clone();
+            move |_: Context| {
+                ran.lock().unwrap().push(name);
+                async {}
+            }
+        };
+        let commands = crate::commands! {
+            command "ping": "Test the bot's latency" => handler("ping");
+            command "role": "Manage roles" {
+                subcommand "list": "List your roles" => handler("list");
+                group "admin": "Administer roles" {
+                    subcommand "purge": "Delete unused roles" (
+                        arg::string("reason", "Why the roles are deleted"),
+                    ) => |_: Context, _: String| async {};
+                    subcommand "sync": "Sync roles" => handler("sync");
+                }
+            }
+        };
+
+        assert_eq!(
+            serde_json::to_value(crate::command::commands(&commands).unwrap()).unwrap(),
+            json!([
+                { "type": 1, "name": "ping", "description": "Test the bot's latency" },
+                {
+                    "type": 1,
+                    "name": "role",
+                    "description": "Manage roles",
+                    "options": [
+                        { "type": 1, "name": "list", "description": "List your roles", "required": false },
+                        {
+                            "type": 2,
+                            "name": "admin",
+                            "description": "Administer roles",
+                            "required": false,
+                            "options": [
+                                {
+                                    "type": 1,
+                                    "name": "purge",
+                                    "description": "Delete unused roles",
+                                    "required": false,
+                                    "options": [{
+                                        "type": 3,
+                                        "name": "reason",
+                                        "description": "Why the roles are deleted",
+                                        "required": true
+                                    }]
+                                },
+                                { "type": 1, "name": "sync", "description": "Sync roles", "required": false }
+                            ]
+                        }
+                    ]
+                }
+            ])
+        );
+
+        let sync =
+            json!([{ "name": "admin", "type": 2, "options": [{ "name": "sync", "type": 1 }] }]);
+        commands
+            .handle(test_util::command("role", sync))
+            .await
+            .unwrap();
+        commands
+            .handle(test_util::command(
+                "role",
+                json!([{ "name": "list", "type": 1 }]),
+            ))
+            .await
+            .unwrap();
+        let unknown = json!([{ "name": "sync", "type": 1 }]);
+        assert!(commands
+            .handle(test_util::command("role", unknown))
+            .await
+            .unwrap_err()
+            .is_not_found());
+        assert_eq!(*ran.lock().unwrap(), ["sync", "list"]);
+    }
+}
@@ -0,0 +1,262 @@
+//! Validation of command metadata against Discord's limits, so payloads Discord would reject are
+//! caught before registration.
+use std::collections::HashSet;
+
+use thiserror::Error;
+
+use crate::model::command::{ApplicationCommand, ApplicationCommandOption, ApplicationCommandType};
+
+/// The maximum number of options or choices on a command or option.
+const MAX_OPTIONS: usize = 25;
+/// The maximum length of a command or option name.
+const MAX_NAME_LENGTH: usize = 32;
+/// The maximum length of a command or option description.
+const MAX_DESCRIPTION_LENGTH: usize = 100;
+
+/// An error raised when a command does not meet Discord's requirements.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ValidationError {
+    #[error("`{0}` must be 1-32 letters, numbers, `-` or `_`")]
+    InvalidName(String),
+    #[error("`{0}` must be lowercase")]
+    UppercaseName(String),
+    #[error("the description of `{0}` must be 1-100 characters")]
+    InvalidDescription(String),
+    #[error("`{0}` is a context-menu command, and cannot have a description")]
+    UnexpectedDescription(String),
+    #[error("`{name}` has {count} options, but at most 25 are allowed")]
+    TooManyOptions { name: String, count: usize },
+    #[error("`{name}` has {count} choices, but at most 25 are allowed")]
+    TooManyChoices { name: String, count: usize },
+    #[error("the required option `{0}` must come before any optional options")]
+    RequiredAfterOptional(String),
+    #[error("`{name}` is declared more than once in `{parent}`")]
+    DuplicateName { parent: String, name: String },
+    #[error("`{name}` is a {found:?} command, not a {expected:?} one")]
+    WrongType {
+        name: String,
+        expected: ApplicationCommandType,
+        found: ApplicationCommandType,
+    },
+}
+
+impl ApplicationCommand {
+    /// Check this command against Discord's limits, including duplicate option and subcommand
+    /// names, which Discord otherwise only reports as an opaque form body error.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        command(self)
+    }
+}
+
+/// Validate a set of commands registered together, which must each have a unique name per
+/// command type.
+pub(crate) fn commands(commands: &[ApplicationCommand]) -> Result<(), ValidationError> {
+    let mut seen = HashSet::new();
+    for command in commands {
+        if !seen.insert((command.ty, command.name.as_str())) {
+            return Err(ValidationError::DuplicateName {
+                parent: "the application".to_string(),
+                name: command.name.clone(),
+            });
+        }
+        self::command(command)?;
+    }
+    Ok(())
+}
+
+/// Validate a command and all of its options.
+pub(crate) fn command(command: &ApplicationCommand) -> Result<(), ValidationError> {
+    match command.ty {
+        ApplicationCommandType::ChatInput => {
+            name(&command.name)?;
+            description(&command.name, &command.description)?;
+            options(&command.name, &command.options)
+        }
+        _ => {
+            let length = command.name.chars().count();
+            if length == 0 || length > MAX_NAME_LENGTH {
+                return Err(ValidationError::InvalidName(command.name.clone()));
+            }
+            if !command.description.is_empty() {
+                return Err(ValidationError::UnexpectedDescription(command.name.clone()));
+            }
+            Ok(())
+        }
+    }
+}
+
+fn name(name: &str) -> Result<(), ValidationError> {
+    let length = name.chars().count();
+    let valid = name
+        .chars()
+        .all(|c| c == '-' || c == '_' || c.is_alphabetic() || c.is_numeric());
+    if length == 0 || length > MAX_NAME_LENGTH || !valid {
+        return Err(ValidationError::InvalidName(name.to_string()));
+    }
+    if name.chars().any(char::is_uppercase) {
+        return Err(ValidationError::UppercaseName(name.to_string()));
+    }
+    Ok(())
+}
+
+fn description(name: &str, description: &str) -> Result<(), ValidationError> {
+    let length = description.chars().count();
+    if length == 0 || length > MAX_DESCRIPTION_LENGTH {
+        return Err(ValidationError::InvalidDescription(name.to_string()));
+    }
+    Ok(())
+}
+
+fn options(parent: &str, children: &[ApplicationCommandOption]) -> Result<(), ValidationError> {
+    if children.len() > MAX_OPTIONS {
+        return Err(ValidationError::TooManyOptions {
+            name: parent.to_string(),
+            count: children.len(),
+        });
+    }
+    if let Some(option) = children
+        .iter()
+        .skip_while(|option| option.required)
+        .find(|option| option.required)
+    {
+        return Err(ValidationError::RequiredAfterOptional(option.name.clone()));
+    }
+    let mut seen = HashSet::new();
+    if let Some(option) = children
+        .iter()
+        .find(|option| !seen.insert(option.name.as_str()))
+    {
+        return Err(ValidationError::DuplicateName {
+            parent: parent.to_string(),
+            name: option.name.clone(),
+        });
+    }
+    for option in children {
+        name(&option.name)?;
+        description(&option.name, &option.description)?;
+        if option.choices.len() > MAX_OPTIONS {
+            return Err(ValidationError::TooManyChoices {
+                name: option.name.clone(),
+                count: option.choices.len(),
+            });
+        }
+        options(&option.name, &option.options)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ValidationError;
+    use crate::model::command::{
+        ApplicationCommand, ApplicationCommandOption, ApplicationCommandType, CommandOptionType,
+    };
+
+    fn chat_input(name: &str, description: &str) -> ApplicationCommand {
+        ApplicationCommand {
+            description: description.to_string(),
+            ..ApplicationCommand::new(ApplicationCommandType::ChatInput, name)
+        }
+    }
+
+    #[test]
+    fn test_validate_names() {
+        assert!(super::command(&chat_input("ping", "Ping the bot")).is_ok());
+        assert!(super::command(&chat_input("ping-pong_2", "Ping the bot")).is_ok());
+        assert_eq!(
+            super::command(&chat_input("Ping", "Ping the bot")),
+            Err(ValidationError::UppercaseName("Ping".to_string()))
+        );
+        assert_eq!(
+            super::command(&chat_input("ping pong", "Ping the bot")),
+            Err(ValidationError::InvalidName("ping pong".to_string()))
+        );
+        assert_eq!(
+            super::command(&chat_input("", "Ping the bot")),
+            Err(ValidationError::InvalidName("".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_validate_descriptions() {
+        assert_eq!(
+            super::command(&chat_input("ping", "")),
+            Err(ValidationError::InvalidDescription("ping".to_string()))
+        );
+        let quote = ApplicationCommand {
+            description: "Quote a message".to_string(),
+            ..ApplicationCommand::new(ApplicationCommandType::Message, "Quote Message")
+        };
+        assert_eq!(
+            super::command(&quote),
+            Err(ValidationError::UnexpectedDescription(
+                "Quote Message".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_validate_options() {
+        let option = |name: &str, required: bool| ApplicationCommandOption {
+            required,
+            ..ApplicationCommandOption::new(CommandOptionType::String, name, "An option")
+        };
+
+        let mut command = chat_input("echo", "Echo a message");
+        command.options = vec![option("text", true), option("times", false)];
+        assert!(super::command(&command).is_ok());
+
+        command.options.reverse();
+        assert_eq!(
+            super::command(&command),
+            Err(ValidationError::RequiredAfterOptional("text".to_string()))
+        );
+
+        command.options = vec![option("text", true), option("text", false)];
+        assert_eq!(
+            super::command(&command),
+            Err(ValidationError::DuplicateName {
+                parent: "echo".to_string(),
+                name: "text".to_string()
+            })
+        );
+
+        command.options = (0..26).map(|i| option(&format!("o{}", i), false)).collect();
+        assert_eq!(
+            super::command(&command),
+            Err(ValidationError::TooManyOptions {
+                name: "echo".to_string(),
+                count: 26
+            })
+        );
+    }
+
+    #[test]
+    fn test_validate_duplicate_subcommands() {
+        let subcommand = |name: &str| {
+            ApplicationCommandOption::new(CommandOptionType::SubCommand, name, "A subcommand")
+        };
+        let mut group =
+            ApplicationCommandOption::new(CommandOptionType::SubCommandGroup, "role", "Roles");
+        group.options = vec![subcommand("add"), subcommand("add")];
+
+        let mut command = chat_input("admin", "Administration");
+        command.options = vec![group];
+        assert_eq!(
+            super::command(&command),
+            Err(ValidationError::DuplicateName {
+                parent: "role".to_string(),
+                name: "add".to_string()
+            })
+        );
+
+        let commands = [chat_input("ping", "Ping"), chat_input("ping", "Pong")];
+        assert_eq!(
+            super::commands(&commands),
+            Err(ValidationError::DuplicateName {
+                parent: "the application".to_string(),
+                name: "ping".to_string()
+            })
+        );
+    }
+}
@@ -0,0 +1,173 @@
+//! Defines the `Config` loaded from environment variables.
+#[cfg(feature = "webhook")]
+use std::net::SocketAddr;
+use std::{env, fmt, str::FromStr};
+
+use crate::{
+    errors::{Error, Result},
+    model::snowflake::Snowflake,
+};
+
+/// The settings of a bot, loaded from environment variables.
+///
+/// | Variable                  | Setting            | Required |
+/// |---------------------------|--------------------|----------|
+/// | `DISCORD_TOKEN`           | [`token`]          | yes      |
+/// | `DISCORD_APPLICATION_ID`  | [`application_id`] | yes      |
+/// | `DISCORD_PUBLIC_KEY`      | [`public_key`]     | no       |
+/// | `DISCORD_LISTEN_ADDRESS`  | [`address`]        | no       |
+/// | `DISCORD_GUILD_ID`        | [`guild_id`]       | no       |
+/// | `DISCORD_PRODUCTION`      | [`production`]     | no       |
+/// | `DISCORD_SHARD_COUNT`     | [`shard_count`]    | no       |
+/// | `DISCORD_SHARD_ID`        | [`shard_id`]       | no       |
+///
+/// [`token`]: Config::token
+/// [`application_id`]: Config::application_id
+/// [`public_key`]: Config::public_key
+/// [`address`]: Config::address
+/// [`guild_id`]: Config::guild_id
+/// [`production`]: Config::production
+/// [`shard_count`]: Config::shard_count
+/// [`shard_id`]: Config::shard_id
+#[derive(Clone)]
+pub struct Config {
+    /// The bot token to authenticate with.
+    pub token: String,
+    /// The ID of the application the commands are registered to.
+    pub application_id: Snowflake,
+    /// The application's public key, used to verify HTTP interactions.
+    pub public_key: Option<String>,
+    /// The address to receive interactions on as HTTP requests, instead of over the gateway.
+    #[cfg(feature = "webhook")]
+    pub address: Option<SocketAddr>,
+    /// The dev guild to register commands to, instead of registering them globally.
+    pub guild_id: Option<Snowflake>,
+    /// Whether to register commands globally even if a dev guild is set, from `true` or
+    /// `false`.
+    pub production: bool,
+    /// The total number of shards.
+    pub shard_count: Option<u32>,
+    /// The only shard to run, out of [`shard_count`](Config::shard_count). Every shard is
+    /// run when unset.
+    pub shard_id: Option<u32>,
+}
+
+impl Config {
+    /// Load the settings from environment variables.
+    pub fn from_env() -> Result<Self> {
+        Self::from_lookup(|name| env::var(name).ok())
+    }
+
+    /// Load the settings from the variables returned by `lookup`.
+    pub(crate) fn from_lookup<F>(lookup: F) -> Result<Self>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        // Blank variables are treated as unset, as they often are in `.env` files.
+        let var = |name: &'static str| lookup(name).filter(|value| !value.trim().is_empty());
+        let id = |name: &'static str| parse::<u64>(name, var(name));
+
+        let shard_count = parse::<u32>("DISCORD_SHARD_COUNT", var("DISCORD_SHARD_COUNT"))?;
+        let shard_id = parse::<u32>("DISCORD_SHARD_ID", var("DISCORD_SHARD_ID"))?;
+        match (shard_id, shard_count) {
+            (Some(_), None) => return Err(Error::MissingEnv("DISCORD_SHARD_COUNT")),
+            (Some(id), Some(count)) if id >= count => {
+                return Err(Error::InvalidEnv("DISCORD_SHARD_ID"))
+            }
+            _ => {}
+        }
+
+        Ok(Self {
+            token: var("DISCORD_TOKEN").ok_or(Error::MissingEnv("DISCORD_TOKEN"))?,
+            application_id: id("DISCORD_APPLICATION_ID")?
+                .ok_or(Error::MissingEnv("DISCORD_APPLICATION_ID"))?
+                .into(),
+            public_key: var("DISCORD_PUBLIC_KEY"),
+            #[cfg(feature = "webhook")]
+            address: parse("DISCORD_LISTEN_ADDRESS", var("DISCORD_LISTEN_ADDRESS"))?,
+            guild_id: id("DISCORD_GUILD_ID")?.map(Snowflake::from),
+            production: parse("DISCORD_PRODUCTION", var("DISCORD_PRODUCTION"))?.unwrap_or(false),
+            shard_count,
+            shard_id,
+        })
+    }
+}
+
+/// Parse an environment variable, if it is set.
+fn parse<T: FromStr>(name: &'static str, value: Option<String>) -> Result<Option<T>> {
+    value
+        .map(|value| value.trim().parse().map_err(|_| Error::InvalidEnv(name)))
+        .transpose()
+}
+
+impl fmt::Debug for Config {
+    // Leaves out the token, so that it isn't logged by accident.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug = f.debug_struct("Config");
+        debug
+            .field("application_id", &self.application_id)
+            .field("public_key", &self.public_key);
+        #[cfg(feature = "webhook")]
+        debug.field("address", &self.address);
+        debug
+            .field("guild_id", &self.guild_id)
+            .field("production", &self.production)
+            .field("shard_count", &self.shard_count)
+            .field("shard_id", &self.shard_id)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::Config;
+    use crate::{errors::Error, model::snowflake::Snowflake};
+
+    fn load(vars: &[(&str, &str)]) -> crate::Result<Config> {
+        let vars: HashMap<_, _> = vars.iter().copied().collect();
+        Config::from_lookup(|name| vars.get(name).map(|value| value.to_string()))
+    }
+
+    #[test]
+    fn test_config_from_env() {
+        let config = load(&[
+            ("DISCORD_TOKEN", "token"),
+            ("DISCORD_APPLICATION_ID", "940565712559304735"),
+            ("DISCORD_GUILD_ID", " 940565712559304736 "),
+            ("DISCORD_PUBLIC_KEY", ""),
+            ("DISCORD_SHARD_COUNT", "4"),
+            ("DISCORD_SHARD_ID", "2"),
+            ("DISCORD_PRODUCTION", "true"),
+        ])
+        .unwrap();
+        assert_eq!(config.token, "token");
+        assert_eq!(config.application_id, Snowflake::from(940565712559304735));
+        assert_eq!(config.guild_id, Some(Snowflake::from(940565712559304736)));
+        assert!(config.production);
+        assert_eq!(config.public_key, None);
+        assert_eq!((config.shard_id, config.shard_count), (Some(2), Some(4)));
+    }
+
+    #[test]
+    fn test_config_from_env_errors() {
+        let err = load(&[("DISCORD_APPLICATION_ID", "1")]).unwrap_err();
+        assert!(matches!(err, Error::MissingEnv("DISCORD_TOKEN")));
+
+        let err = load(&[
+            ("DISCORD_TOKEN", "token"),
+            ("DISCORD_APPLICATION_ID", "bot"),
+        ])
+        .unwrap_err();
+        assert!(matches!(err, Error::InvalidEnv("DISCORD_APPLICATION_ID")));
+
+        let err = load(&[
+            ("DISCORD_TOKEN", "token"),
+            ("DISCORD_APPLICATION_ID", "1"),
+            ("DISCORD_SHARD_ID", "0"),
+        ])
+        .unwrap_err();
+        assert!(matches!(err, Error::MissingEnv("DISCORD_SHARD_COUNT")));
+    }
+}
@@ -1,25 +1,706 @@
 //! Defines the `Context` struct, the primary method through which bots are
 //! capable of interacting with the Discord API.
 
-use std::cell::RefCell;
+use std::{
+    cell::RefCell,
+    sync::{
+        atomic::{AtomicU8, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
 
+use chrono::Utc;
+use futures_util::future::{self, AbortHandle};
 use scoped_tls::scoped_thread_local;
 
+use crate::{
+    collector::{Collectors, ComponentCollector},
+    errors::{Error, Result},
+    model::{
+        channel::{AutoArchiveDuration, Channel},
+        command::ApplicationCommandType,
+        interaction::{
+            ApplicationCommandData, Interaction, InteractionCallbackType, InteractionResponse,
+            InteractionType, ModalSubmitData,
+        },
+        member::{Member, PartialMember},
+        message::{CreateMessage, EditMessage, Message, MessageReferenceType},
+        modal::Modal,
+        permissions::Permissions,
+        snowflake::Snowflake,
+        user::User,
+    },
+    rest, runtime,
+};
+
+/// How long after an interaction is created its token can be used to respond to it.
+const TOKEN_LIFETIME: Duration = Duration::from_secs(15 * 60);
+
 scoped_thread_local!(
     /// Thread-scoped context value.
     static CONTEXT: RefCell<Context>
 );
 
 /// The primary context of a command.
-pub struct Context {}
+///
+/// Contexts are cheap to clone, sharing the interaction, its response state and the REST
+/// client, so handlers can move a clone into a spawned task to keep responding in the
+/// background:
+///
+/// ```no_run
+/// # use slash::{Context, MessageBuilder};
+/// # fn run(context: Context) {
+/// tokio::spawn(async move {
+///     let message = MessageBuilder::new().content("Done!");
+///     let _ = context.followup(message).await;
+/// });
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct Context {
+    inner: Arc<ContextInner>,
+}
+
+struct ContextInner {
+    interaction: Interaction,
+    http: Arc<rest::Client>,
+    collectors: Arc<Collectors>,
+    response: AtomicU8,
+    #[cfg(feature = "gateway")]
+    gateway: Option<crate::gateway::Gateway>,
+}
 
 impl Context {
-    pub fn new() -> Self {
-        Self {}
+    pub(crate) fn new(interaction: Interaction, http: Arc<rest::Client>) -> Self {
+        Self {
+            inner: Arc::new(ContextInner {
+                interaction,
+                http,
+                collectors: Arc::default(),
+                response: AtomicU8::new(ResponseState::Pending as u8),
+                #[cfg(feature = "gateway")]
+                gateway: None,
+            }),
+        }
+    }
+
+    /// Returns the fields of a context that isn't shared yet, to finish configuring it.
+    fn configure(&mut self) -> &mut ContextInner {
+        Arc::get_mut(&mut self.inner).expect("contexts are configured before they are cloned")
+    }
+
+    /// Attach the collectors shared by every interaction of a client.
+    pub(crate) fn with_collectors(mut self, collectors: Arc<Collectors>) -> Self {
+        self.configure().collectors = collectors;
+        self
+    }
+
+    /// Attach the gateway connection the interaction was received over.
+    #[cfg(feature = "gateway")]
+    pub(crate) fn with_gateway(mut self, gateway: crate::gateway::Gateway) -> Self {
+        self.configure().gateway = Some(gateway);
+        self
+    }
+
+    /// Returns the REST client of the bot, to make any call to the API from a handler.
+    ///
+    /// ```no_run
+    /// # async fn run(context: slash::Context) -> slash::Result<()> {
+    /// let user = context.http().get_user(940565712559304739).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn http(&self) -> &rest::Client {
+        &self.inner.http
+    }
+
+    /// Returns the round-trip time of the last gateway heartbeat.
+    ///
+    /// Returns `None` for interactions received over HTTP, or before the first heartbeat is
+    /// acknowledged.
+    pub fn gateway_latency(&self) -> Option<Duration> {
+        #[cfg(feature = "gateway")]
+        {
+            self.inner.gateway.as_ref()?.latency()
+        }
+        #[cfg(not(feature = "gateway"))]
+        {
+            None
+        }
+    }
+
+    /// Returns the cache of guilds, channels, roles and members kept up to date by the gateway.
+    ///
+    /// Returns `None` for interactions received over HTTP.
+    #[cfg(feature = "gateway")]
+    pub fn cache(&self) -> Option<&crate::cache::Cache> {
+        Some(self.inner.gateway.as_ref()?.cache())
+    }
+
+    /// Returns the ID of the interaction being handled, which is recorded as the `id` of the
+    /// `interaction` span every event logged while handling it is in, to correlate them.
+    pub fn interaction_id(&self) -> Snowflake {
+        self.inner.interaction.id
+    }
+
+    /// Returns the ID of the shard the interaction was received over, recorded as the `shard`
+    /// of the `interaction` span.
+    ///
+    /// Returns `None` for interactions received over HTTP.
+    #[cfg(feature = "gateway")]
+    pub fn shard_id(&self) -> Option<u32> {
+        let gateway = self.inner.gateway.as_ref()?;
+        Some(gateway.shard_for(self.inner.interaction.guild_id))
+    }
+
+    /// Create the span the interaction is handled in.
+    pub(crate) fn span(&self) -> tracing::Span {
+        let span = tracing::info_span!(
+            "interaction",
+            id = %self.inner.interaction.id,
+            shard = tracing::field::Empty,
+        );
+        #[cfg(feature = "gateway")]
+        if let Some(shard) = self.shard_id() {
+            span.record("shard", shard);
+        }
+        span
+    }
+
+    /// Returns the interaction being handled, as received from Discord.
+    ///
+    /// Prefer the other methods of the context where they exist; this gives access to the
+    /// fields they don't cover yet.
+    pub fn interaction(&self) -> &Interaction {
+        &self.inner.interaction
+    }
+
+    /// Returns the user who invoked the interaction, whether in a guild or a direct message.
+    pub fn user(&self) -> Option<&User> {
+        self.inner.interaction.user()
+    }
+
+    /// Returns the member who invoked the interaction, including their roles and
+    /// permissions, when invoked in a guild.
+    pub fn member(&self) -> Option<&Member> {
+        self.inner.interaction.member.as_ref()
+    }
+
+    /// Returns the permissions of the invoking member in the channel the interaction was
+    /// invoked in, including its overwrites.
+    ///
+    /// Returns `None` outside of guilds.
+    pub fn member_permissions(&self) -> Option<Permissions> {
+        self.member()?.permissions
+    }
+
+    /// Returns whether the invoking member has every given permission in the channel the
+    /// interaction was invoked in. Always `false` outside of guilds.
+    pub fn has(&self, permissions: Permissions) -> bool {
+        self.member_permissions()
+            .is_some_and(|member| member.has(permissions))
+    }
+
+    /// Returns the ID of the guild the interaction was invoked in, if any.
+    pub fn guild_id(&self) -> Option<Snowflake> {
+        self.inner.interaction.guild_id
+    }
+
+    /// Returns the ID of the channel the interaction was invoked in.
+    pub fn channel_id(&self) -> Option<Snowflake> {
+        self.inner.interaction.channel_id
+    }
+
+    /// Returns the command data of the interaction, if it is an application command.
+    pub(crate) fn command_data(&self) -> Option<&ApplicationCommandData> {
+        self.inner.interaction.command_data()
+    }
+
+    /// Returns the message a `MESSAGE` context-menu command was run on.
+    ///
+    /// Returns `None` for any other kind of interaction.
+    pub fn target_message(&self) -> Option<&Message> {
+        let data = self.inner.interaction.command_data()?;
+        if data.ty != ApplicationCommandType::Message {
+            return None;
+        }
+        data.resolved.messages.get(&data.target_id?)
+    }
+
+    /// Fetch the message replied to by the message a `MESSAGE` context-menu command was run
+    /// on, or by the message of a component interaction, such as for moderating a whole
+    /// exchange. Returns `None` if the message isn't a reply, or the interaction has no
+    /// message.
+    ///
+    /// Resolved messages rarely include the message they reply to, so it is fetched with
+    /// [`rest::Client::get_message`], which needs the bot to be able to read the channel.
+    pub async fn fetch_replied_message(&self) -> Result<Option<Message>> {
+        let Some(message) = self.target_message().or_else(|| self.message()) else {
+            return Ok(None);
+        };
+        if let Some(replied) = &message.referenced_message {
+            return Ok(Some(Message::clone(replied)));
+        }
+        let reference = message
+            .message_reference
+            .as_ref()
+            .filter(|reference| reference.ty == MessageReferenceType::Default);
+        let Some(message_id) = reference.and_then(|reference| reference.message_id) else {
+            return Ok(None);
+        };
+        let channel_id = reference
+            .and_then(|reference| reference.channel_id)
+            .unwrap_or(message.channel_id);
+        self.inner
+            .http
+            .get_message(channel_id, message_id)
+            .await
+            .map(Some)
+    }
+
+    /// Returns the user a `USER` context-menu command was run on.
+    ///
+    /// Returns `None` for any other kind of interaction.
+    pub fn target_user(&self) -> Option<&User> {
+        let data = self.inner.interaction.command_data()?;
+        if data.ty != ApplicationCommandType::User {
+            return None;
+        }
+        data.resolved.users.get(&data.target_id?)
+    }
+
+    /// Returns the guild member a `USER` context-menu command was run on.
+    ///
+    /// Returns `None` for any other kind of interaction, or when the command was run outside
+    /// of a guild.
+    pub fn target_member(&self) -> Option<&PartialMember> {
+        let data = self.inner.interaction.command_data()?;
+        if data.ty != ApplicationCommandType::User {
+            return None;
+        }
+        data.resolved.members.get(&data.target_id?)
+    }
+
+    /// Returns the custom ID of the component used, if this is a component interaction.
+    pub fn custom_id(&self) -> Option<&str> {
+        Some(&self.inner.interaction.component_data()?.custom_id)
+    }
+
+    /// Returns the data of the submitted modal, holding the values of its text inputs, if
+    /// this is a modal submit interaction.
+    pub fn modal_data(&self) -> Option<&ModalSubmitData> {
+        self.inner.interaction.modal_data()
+    }
+
+    /// Returns the values selected in a select menu, which are empty for any other kind of
+    /// interaction.
+    pub fn selected_values(&self) -> &[String] {
+        self.inner
+            .interaction
+            .component_data()
+            .map_or(&[], |data| &data.values)
+    }
+
+    /// Returns the message the component used is attached to, if this is a component
+    /// interaction.
+    pub fn message(&self) -> Option<&Message> {
+        self.inner.interaction.message.as_ref()
+    }
+
+    /// Collect the component interactions on a message accepted by `filter`, until `timeout`
+    /// elapses. Collected interactions aren't dispatched to the client's commands.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use std::time::Duration;
+    /// # use slash::Context;
+    /// # async fn confirm(context: Context, message_id: slash::model::snowflake::Snowflake) -> slash::Result<()> {
+    /// let mut clicks = context.await_component(
+    ///     message_id,
+    ///     |click| click.custom_id() == Some("confirm"),
+    ///     Duration::from_secs(60),
+    /// );
+    /// if let Some(click) = clicks.next().await {
+    ///     click.defer_update().await?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn await_component<F>(
+        &self,
+        message_id: Snowflake,
+        filter: F,
+        timeout: Duration,
+    ) -> ComponentCollector
+    where
+        F: Fn(&Context) -> bool + Send + Sync + 'static,
+    {
+        self.inner
+            .collectors
+            .register(message_id, Box::new(filter), timeout)
+    }
+
+    /// Acknowledge a component interaction without changing its message, so that the message
+    /// can be edited later.
+    pub async fn defer_update(&self) -> Result<()> {
+        self.respond(InteractionResponse {
+            ty: InteractionCallbackType::DeferredUpdateMessage,
+            data: None,
+        })
+        .await
+    }
+
+    /// Returns how the interaction has been responded to so far.
+    pub fn response_state(&self) -> ResponseState {
+        ResponseState::from_u8(self.inner.response.load(Ordering::Acquire))
+    }
+
+    /// Reply to the interaction with the given message: its content, or a
+    /// [`MessageBuilder`](crate::MessageBuilder) combining content, embeds, components and
+    /// flags. Fails without sending anything if the message exceeds Discord's limits.
+    ///
+    /// If the interaction was already responded to, the message is sent as a followup instead.
+    ///
+    /// ```no_run
+    /// # use slash::MessageBuilder;
+    /// # async fn run(context: slash::Context) -> slash::Result<()> {
+    /// context
+    ///     .reply(MessageBuilder::new().content("https://discord.com").suppress_embeds())
+    ///     .await
+    /// # }
+    /// ```
+    pub async fn reply<M: Into<CreateMessage>>(&self, message: M) -> Result<()> {
+        let message = message.into();
+        message.validate()?;
+        let mut message = Some(message);
+        let result = self
+            .respond_once(ResponseState::Replied, || {
+                self.send_response(InteractionResponse {
+                    ty: InteractionCallbackType::ChannelMessageWithSource,
+                    data: message.take().map(Into::into),
+                })
+            })
+            .await;
+        match (result, message) {
+            // The message is only taken to be sent if the interaction was still pending.
+            (Err(Error::AlreadyResponded), Some(message)) => self.followup(message).await.map(drop),
+            (result, _) => result,
+        }
+    }
+
+    /// Replace the content, embeds, components and attachments of the reply to the
+    /// interaction with those of the given message, returning the edited reply.
+    pub async fn edit_reply<M: Into<CreateMessage>>(&self, message: M) -> Result<Message> {
+        let message = message.into();
+        message.validate()?;
+        self.edit_original_response(&message.into()).await
+    }
+
+    /// Respond to the interaction with a popup modal, built with a
+    /// [`ModalBuilder`](crate::ModalBuilder). Fails without showing anything if the modal
+    /// exceeds Discord's limits.
+    pub async fn show_modal<M: Into<Modal>>(&self, modal: M) -> Result<()> {
+        let modal = modal.into();
+        modal.validate()?;
+        self.respond_once(ResponseState::Replied, || {
+            self.inner.http.create_modal_response(
+                self.inner.interaction.id,
+                &self.inner.interaction.token,
+                &modal,
+            )
+        })
+        .await
     }
-    /// Reply to the interaction with the given message.
-    pub fn reply<S: AsRef<str>>(&self, content: S) {
-        todo!()
+
+    /// Reply to the interaction with a premium upgrade button, prompting the user to purchase
+    /// one of the application's SKUs.
+    ///
+    /// Only available for applications with monetization enabled.
+    pub async fn reply_premium_required(&self) -> Result<()> {
+        self.respond(InteractionResponse {
+            ty: InteractionCallbackType::PremiumRequired,
+            data: None,
+        })
+        .await
+    }
+
+    /// Send a direct message to a user, opening a DM channel with them first. Returns the sent
+    /// message.
+    ///
+    /// Fails if the user doesn't share a guild with the bot, or has direct messages disabled.
+    pub async fn dm_user<U, M>(&self, user_id: U, message: M) -> Result<Message>
+    where
+        U: Into<Snowflake>,
+        M: Into<CreateMessage>,
+    {
+        let message = message.into();
+        message.validate()?;
+        let channel = self.inner.http.create_dm(user_id).await?;
+        self.inner.http.create_message(channel.id, &message).await
+    }
+
+    /// Send a followup message to the interaction, once it has been responded to. Returns the
+    /// sent message.
+    pub async fn followup<M: Into<CreateMessage>>(&self, message: M) -> Result<Message> {
+        let message = message.into();
+        message.validate()?;
+        self.inner
+            .http
+            .create_followup_message(
+                self.inner.interaction.application_id,
+                &self.inner.interaction.token,
+                &message,
+            )
+            .await
+    }
+
+    /// Send a followup message to the interaction once `after` elapses, in the background.
+    /// Returns a handle to cancel the followup before it's sent.
+    ///
+    /// Fails without scheduling anything if the message exceeds Discord's limits, or with
+    /// [`Error::FollowupTooLate`] if it would be sent once the interaction's token expires,
+    /// 15 minutes after the interaction was created.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use std::time::Duration;
+    /// # async fn remind(context: slash::Context) -> slash::Result<()> {
+    /// context.reply("Reminder set, I'll ping you in 10 minutes.").await?;
+    /// let reminder = context.followup_after(Duration::from_secs(600), "Time's up!")?;
+    /// // Later, if the user changes their mind:
+    /// reminder.cancel();
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn followup_after<M: Into<CreateMessage>>(
+        &self,
+        after: Duration,
+        message: M,
+    ) -> Result<FollowupHandle> {
+        let message = message.into();
+        message.validate()?;
+        let elapsed = (Utc::now() - self.inner.interaction.id.timestamp())
+            .to_std()
+            .unwrap_or_default();
+        if elapsed.saturating_add(after) >= TOKEN_LIFETIME {
+            return Err(Error::FollowupTooLate);
+        }
+        let context = self.clone();
+        let (send, abort) = future::abortable(async move {
+            runtime::sleep(after).await;
+            if let Err(err) = context.followup(message).await {
+                tracing::warn!(error = %err, "failed to send scheduled followup");
+            }
+        });
+        runtime::spawn(async move {
+            let _ = send.await;
+        });
+        Ok(FollowupHandle { abort })
+    }
+
+    /// Fetch the message sent in response to the interaction.
+    pub async fn original_response(&self) -> Result<Message> {
+        self.inner
+            .http
+            .get_original_interaction_response(
+                self.inner.interaction.application_id,
+                &self.inner.interaction.token,
+            )
+            .await
+    }
+
+    /// Edit the message sent in response to the interaction, returning the edited message.
+    pub async fn edit_original_response(&self, changes: &EditMessage) -> Result<Message> {
+        self.inner
+            .http
+            .edit_original_interaction_response(
+                self.inner.interaction.application_id,
+                &self.inner.interaction.token,
+                changes,
+            )
+            .await
+    }
+
+    /// Start a public thread from the message sent in response to the interaction, returning
+    /// the thread, such as to hold the discussion of each use of a command. Without an
+    /// `auto_archive` duration, the thread uses the default of the channel.
+    ///
+    /// The interaction must have been responded to with a message, rather than a modal, and
+    /// the bot needs the `CREATE_PUBLIC_THREADS` permission in the channel.
+    pub async fn start_thread(
+        &self,
+        name: &str,
+        auto_archive: Option<AutoArchiveDuration>,
+    ) -> Result<Channel> {
+        let message = self.original_response().await?;
+        self.inner
+            .http
+            .start_thread_from_message(message.channel_id, message.id, name, auto_archive)
+            .await
+    }
+
+    /// Disable the components of the response to the interaction once `after` elapses, so that
+    /// users can't click them after their handlers stop listening. Link buttons are left
+    /// enabled.
+    ///
+    /// The components are disabled in the background, so this should be called once the
+    /// response is sent. Since the interaction's token expires after 15 minutes, `after` must
+    /// be shorter than that.
+    pub fn expire_components(&self, after: Duration) {
+        let context = self.clone();
+        runtime::spawn(async move {
+            runtime::sleep(after).await;
+            if let Err(err) = context.disable_components().await {
+                tracing::warn!(error = %err, "failed to disable expired components");
+            }
+        });
+    }
+
+    async fn disable_components(&self) -> Result<()> {
+        let mut components = self.original_response().await?.components;
+        if components.is_empty() {
+            return Ok(());
+        }
+        for component in &mut components {
+            component.disable();
+        }
+        self.edit_original_response(&EditMessage {
+            components: Some(components),
+            ..Default::default()
+        })
+        .await?;
+        Ok(())
+    }
+
+    /// Returns the ID of the user who invoked the interaction.
+    pub(crate) fn user_id(&self) -> Option<Snowflake> {
+        self.inner.interaction.user_id()
+    }
+
+    /// Returns whether the interaction is a `PING`, which isn't dispatched to any handler.
+    pub(crate) fn is_ping(&self) -> bool {
+        self.inner.interaction.ty == InteractionType::Ping
+    }
+
+    /// Send the initial response to the interaction, failing with
+    /// [`Error::AlreadyResponded`] if it was already responded to.
+    pub(crate) async fn respond(&self, response: InteractionResponse) -> Result<()> {
+        self.respond_once(ResponseState::of(response.ty), || {
+            self.send_response(response)
+        })
+        .await
+    }
+
+    /// Send a response to the interaction, whatever its state.
+    async fn send_response(&self, response: InteractionResponse) -> Result<()> {
+        self.inner
+            .http
+            .create_interaction_response(
+                self.inner.interaction.id,
+                &self.inner.interaction.token,
+                &response,
+            )
+            .await
+    }
+
+    /// Send an initial response unless one was already sent, moving to the given state. The
+    /// response is only created by calling `send` once the move succeeded, so that concurrent
+    /// responses can't both be sent.
+    async fn respond_once<F, R>(&self, state: ResponseState, send: F) -> Result<()>
+    where
+        F: FnOnce() -> R,
+        R: std::future::Future<Output = Result<()>>,
+    {
+        let pending = ResponseState::Pending as u8;
+        if self
+            .inner
+            .response
+            .compare_exchange(pending, state as u8, Ordering::AcqRel, Ordering::Acquire)
+            .is_err()
+        {
+            return Err(Error::AlreadyResponded);
+        }
+        let result = send().await;
+        if matches!(result, Err(ref error) if response_unsent(error)) {
+            // Lets the handler try again, such as with a smaller message.
+            self.inner.response.store(pending, Ordering::Release);
+        }
+        result
+    }
+}
+
+/// Whether an error raised while sending an initial response proves it didn't reach Discord.
+/// Any other error, such as a timeout, leaves it unknown whether the interaction was
+/// responded to, so it's considered responded to.
+fn response_unsent(error: &Error) -> bool {
+    match error {
+        Error::Validation(_)
+        | Error::Message(_)
+        | Error::Modal(_)
+        | Error::MissingConfig(_)
+        | Error::InvalidConfig(_) => true,
+        Error::Http(err) if err.is_builder() => true,
+        error => rest::unsent(error),
+    }
+}
+
+/// How an interaction has been responded to, as returned by [`Context::response_state`].
+///
+/// Every interaction takes a single initial response; anything sent afterwards is a
+/// followup, or an edit of the response.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ResponseState {
+    /// The interaction hasn't been responded to yet.
+    Pending = 0,
+    /// A message was sent in reply, or a modal or upgrade button was shown.
+    Replied = 1,
+    /// The reply was deferred, showing a loading state until it's followed up.
+    Deferred = 2,
+    /// The message of the component used was updated, or its update was deferred.
+    Updated = 3,
+}
+
+impl ResponseState {
+    /// Returns the state an interaction is in after the given response.
+    fn of(ty: InteractionCallbackType) -> Self {
+        match ty {
+            InteractionCallbackType::DeferredChannelMessageWithSource => ResponseState::Deferred,
+            InteractionCallbackType::DeferredUpdateMessage
+            | InteractionCallbackType::UpdateMessage => ResponseState::Updated,
+            _ => ResponseState::Replied,
+        }
+    }
+
+    fn from_u8(value: u8) -> Self {
+        match value {
+            1 => ResponseState::Replied,
+            2 => ResponseState::Deferred,
+            3 => ResponseState::Updated,
+            _ => ResponseState::Pending,
+        }
+    }
+}
+
+/// A followup scheduled with [`Context::followup_after`], which can be cancelled until it's
+/// sent. Dropping the handle doesn't cancel the followup.
+#[derive(Debug, Clone)]
+pub struct FollowupHandle {
+    abort: AbortHandle,
+}
+
+impl FollowupHandle {
+    /// Cancel the followup, if it hasn't been sent yet.
+    pub fn cancel(&self) {
+        self.abort.abort();
+    }
+
+    /// Returns whether the followup was cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.abort.is_aborted()
     }
 }
 
@@ -30,13 +711,135 @@ where
     CONTEXT.set(r, func)
 }
 
-pub(crate) fn is_set() -> bool {
-    CONTEXT.is_set()
-}
-
 pub(crate) fn with<F, R>(func: F) -> R
 where
     F: FnOnce(&mut Context) -> R,
 {
-    CONTEXT.with(move |route| func(&mut *route.borrow_mut()))
+    CONTEXT.with(move |route| func(&mut route.borrow_mut()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::atomic::Ordering, time::Duration};
+
+    use serde_json::{json, Value};
+
+    use super::ResponseState;
+    use crate::{errors::Error, model::snowflake::Snowflake, reply::MessageError, test_util};
+
+    #[tokio::test]
+    async fn test_second_response_fails() {
+        let context = test_util::context(json!({ "type": 3 }));
+        assert_eq!(context.response_state(), ResponseState::Pending);
+
+        context
+            .inner
+            .response
+            .store(ResponseState::Updated as u8, Ordering::Release);
+        assert_eq!(context.response_state(), ResponseState::Updated);
+        assert!(matches!(
+            context.defer_update().await,
+            Err(Error::AlreadyResponded)
+        ));
+        assert_eq!(context.interaction_id(), 940565712559304734.into());
+    }
+
+    #[tokio::test]
+    async fn test_respond_once_claims_pending_only() {
+        let context = test_util::context(json!({}));
+        let respond = || async { Ok(()) };
+        assert!(context
+            .respond_once(ResponseState::Deferred, respond)
+            .await
+            .is_ok());
+        assert_eq!(context.response_state(), ResponseState::Deferred);
+        let result = context
+            .respond_once(ResponseState::Replied, || async {
+                unreachable!("the interaction was already responded to")
+            })
+            .await;
+        assert!(matches!(result, Err(Error::AlreadyResponded)));
+        assert_eq!(context.response_state(), ResponseState::Deferred);
+    }
+
+    #[tokio::test]
+    async fn test_respond_once_resets_only_unsent() {
+        let context = test_util::context(json!({}));
+        let result = context
+            .respond_once(ResponseState::Replied, || async {
+                Err(Error::MissingConfig("token"))
+            })
+            .await;
+        assert!(matches!(result, Err(Error::MissingConfig(_))));
+        assert_eq!(context.response_state(), ResponseState::Pending);
+
+        let result = context
+            .respond_once(ResponseState::Replied, || async { Err(Error::Timeout) })
+            .await;
+        assert!(matches!(result, Err(Error::Timeout)));
+        assert_eq!(context.response_state(), ResponseState::Replied);
+    }
+
+    #[tokio::test]
+    async fn test_followup_after_fails_early() {
+        let context = test_util::context(json!({ "id": Snowflake::now() }));
+        let result = context.followup_after(Duration::from_secs(60), "a".repeat(2001));
+        assert!(matches!(
+            result,
+            Err(Error::Message(MessageError::ContentTooLong(2001)))
+        ));
+        let result = context.followup_after(Duration::from_secs(15 * 60), "Time's up!");
+        assert!(matches!(result, Err(Error::FollowupTooLate)));
+        context
+            .followup_after(Duration::from_secs(60), "Time's up!")
+            .unwrap()
+            .cancel();
+
+        let expired = test_util::context(json!({}));
+        let result = expired.followup_after(Duration::ZERO, "Time's up!");
+        assert!(matches!(result, Err(Error::FollowupTooLate)));
+    }
+
+    #[tokio::test]
+    async fn test_dm_user_validates_message() {
+        let context = test_util::context(json!({}));
+        let result = context.dm_user(1, "a".repeat(2001)).await;
+        assert!(matches!(
+            result,
+            Err(Error::Message(MessageError::ContentTooLong(2001)))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_replied_message() {
+        let message = |id: &str, replied: Value| {
+            json!({
+                "id": id,
+                "channel_id": "2",
+                "author": { "id": "3", "username": "slash", "discriminator": "0" },
+                "content": "",
+                "timestamp": "2024-01-01T00:00:00+00:00",
+                "type": 19,
+                "referenced_message": replied,
+            })
+        };
+        let context = |target: Value| {
+            test_util::context(json!({
+                "data": {
+                    "id": "940565712559304736",
+                    "name": "Report",
+                    "type": 3,
+                    "target_id": "5",
+                    "resolved": { "messages": { "5": target } }
+                }
+            }))
+        };
+
+        let reply = context(message("5", message("4", Value::Null)));
+        let replied = reply.fetch_replied_message().await.unwrap().unwrap();
+        assert_eq!(replied.id, 4.into());
+
+        let standalone = context(message("5", Value::Null));
+        assert!(standalone.fetch_replied_message().await.unwrap().is_none());
+    }
 }
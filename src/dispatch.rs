@@ -0,0 +1,175 @@
+//! Runs filters against incoming interactions.
+use std::{
+    cell::RefCell,
+    future::Future,
+    pin::Pin,
+    task::{self, Poll},
+};
+
+use futures_util::{future::BoxFuture, FutureExt, TryFutureExt};
+use pin_project::pin_project;
+
+use crate::{
+    context::{self, Context},
+    filter::{Filter, FilterBase, FilterMeta, Internal},
+    reject::{self, Rejection},
+};
+
+/// A type-erased filter, so filters extracting different values can be dispatched together.
+pub(crate) trait Handler: Send + Sync {
+    /// Visits the commands, options and checks declared by the filter.
+    fn meta(&self, visitor: &mut dyn FilterMeta);
+
+    /// Runs the filter against an interaction, discarding the extracted value.
+    fn handle(&self, context: Context) -> BoxFuture<'static, Result<(), Rejection>>;
+}
+
+impl<F> Handler for F
+where
+    F: Filter + Send + Sync + 'static,
+    F::Future: 'static,
+    F::Error: Into<Rejection>,
+{
+    fn meta(&self, visitor: &mut dyn FilterMeta) {
+        FilterBase::meta(self, Internal, visitor);
+    }
+
+    fn handle(&self, context: Context) -> BoxFuture<'static, Result<(), Rejection>> {
+        let context = RefCell::new(context);
+        let future = context::set(&context, || self.filter(Internal));
+        FilteredFuture { future, context }
+            .map_ok(|_| ())
+            .map_err(Into::into)
+            .boxed()
+    }
+}
+
+/// Polls a filter's future with its context set, so filters can read the interaction.
+#[pin_project]
+struct FilteredFuture<F> {
+    #[pin]
+    future: F,
+    context: RefCell<Context>,
+}
+
+impl<F: Future> Future for FilteredFuture<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let future = this.future;
+        context::set(this.context, || future.poll(cx))
+    }
+}
+
+/// A handler of the component interactions whose custom ID starts with a prefix.
+pub(crate) struct ComponentHandler {
+    pub(crate) prefix: String,
+    pub(crate) handler: Box<dyn Handler>,
+}
+
+/// Runs the component handler with the longest prefix of the interaction's custom ID. Returns
+/// the context back if there is none, or it doesn't accept the interaction.
+pub(crate) async fn dispatch_component(
+    handlers: &[ComponentHandler],
+    context: Context,
+) -> Option<Context> {
+    let handler = context.custom_id().and_then(|custom_id| {
+        handlers
+            .iter()
+            .filter(|handler| custom_id.starts_with(&handler.prefix))
+            .max_by_key(|handler| handler.prefix.len())
+    });
+    let handler = match handler {
+        Some(handler) => handler,
+        None => return Some(context),
+    };
+    match handler.handler.handle(context.clone()).await {
+        Ok(()) => None,
+        Err(err) if err.is_not_found() => Some(context),
+        Err(rejection) => {
+            tracing::warn!(?rejection, "interaction was rejected");
+            None
+        }
+    }
+}
+
+/// Runs each handler in turn until one accepts the interaction, logging and returning the
+/// rejection if none does.
+pub(crate) async fn dispatch<'a, I>(handlers: I, context: Context) -> Result<(), Rejection>
+where
+    I: IntoIterator<Item = &'a dyn Handler>,
+{
+    let mut rejection = None;
+    for handler in handlers {
+        match handler.handle(context.clone()).await {
+            Ok(()) => return Ok(()),
+            Err(err) if err.is_not_found() => {}
+            Err(err) => rejection = Some(err),
+        }
+    }
+    match rejection {
+        Some(rejection) => {
+            tracing::warn!(?rejection, "interaction was rejected");
+            Err(rejection)
+        }
+        None => {
+            tracing::debug!("no command matched the interaction");
+            Err(reject::not_found())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use serde_json::json;
+
+    use super::{dispatch_component, ComponentHandler, Handler};
+    use crate::{context::Context, test_util, Filter};
+
+    #[tokio::test]
+    async fn test_command_matches_name() {
+        let ping = crate::command("ping").map(|_: Context| "pong");
+
+        assert!(ping
+            .handle(test_util::command("ping", json!([])))
+            .await
+            .is_ok());
+        let rejection = ping
+            .handle(test_util::command("echo", json!([])))
+            .await
+            .unwrap_err();
+        assert!(rejection.is_not_found());
+    }
+
+    #[tokio::test]
+    async fn test_longest_component_prefix_wins() {
+        let matched = Arc::new(Mutex::new(None));
+        let handler = |prefix: &'static str| {
+            let matched = matched.clone();
+            ComponentHandler {
+                prefix: prefix.to_string(),
+                handler: Box::new(
+                    crate::any::any().map(move || *matched.lock().unwrap() = Some(prefix)),
+                ),
+            }
+        };
+        let handlers = [handler("vote"), handler("vote:poll")];
+        let component = |custom_id: &str| {
+            test_util::context(json!({
+                "type": 3,
+                "data": { "custom_id": custom_id, "component_type": 2 }
+            }))
+        };
+
+        assert!(dispatch_component(&handlers, component("vote:poll:1"))
+            .await
+            .is_none());
+        assert_eq!(*matched.lock().unwrap(), Some("vote:poll"));
+        assert!(dispatch_component(&handlers, component("page:1"))
+            .await
+            .is_some());
+    }
+}
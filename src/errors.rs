@@ -0,0 +1,154 @@
+//! Defines the crate-wide error type.
+use std::{fmt, time::Duration};
+
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::{
+    command::ValidationError,
+    reply::{MessageError, ModalError},
+};
+
+/// A `Result` alias where the error defaults to the crate-wide [`Error`].
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// The errors that can be raised by Slash.
+///
+/// Features add variants, so matching on it needs a wildcard arm.
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum Error {
+    /// A command did not meet Discord's requirements.
+    #[error("invalid command: {0}")]
+    Validation(#[from] ValidationError),
+    /// A message exceeded Discord's limits.
+    #[error("invalid message: {0}")]
+    Message(#[from] MessageError),
+    /// A modal exceeded Discord's limits.
+    #[error("invalid modal: {0}")]
+    Modal(#[from] ModalError),
+    /// A payload could not be serialized or deserialized.
+    #[error("failed to (de)serialize payload: {0}")]
+    Serde(#[from] serde_json::Error),
+    /// An HTTP request could not be sent, or its response could not be read.
+    #[error("HTTP request failed: {0}")]
+    Http(#[from] reqwest::Error),
+    /// An HTTP request took longer than the REST client's timeout.
+    #[error("HTTP request timed out")]
+    Timeout,
+    /// Discord responded to a request with an error.
+    #[error(transparent)]
+    Api(#[from] DiscordApiError),
+    /// Discord rate limited a request, and kept doing so until the REST client ran out of
+    /// retries, or asked to wait longer than the client's
+    /// [`max_retry_after`](crate::rest::ClientBuilder::max_retry_after).
+    #[error("rate limited by Discord, retry after {retry_after:?}")]
+    RateLimited {
+        /// How long until the request can be made again.
+        retry_after: Duration,
+        /// Whether the limit applies to every request, rather than to this endpoint.
+        global: bool,
+    },
+    /// The gateway connection failed.
+    #[error(transparent)]
+    Gateway(#[from] GatewayError),
+    /// An initial response was sent to an interaction that was already responded to.
+    #[error("the interaction has already been responded to")]
+    AlreadyResponded,
+    /// A followup was scheduled to be sent after the interaction's token expires, 15 minutes
+    /// after the interaction was created.
+    #[error("the followup would be sent after the interaction's token expires")]
+    FollowupTooLate,
+    /// The client was not given a setting it needs.
+    #[error("the client is missing its {0}")]
+    MissingConfig(&'static str),
+    /// The client was given a setting that isn't valid.
+    #[error("the client's {0} is invalid")]
+    InvalidConfig(&'static str),
+    /// A required environment variable is not set.
+    #[error("the environment variable {0} is not set")]
+    MissingEnv(&'static str),
+    /// An environment variable is set to a value that isn't valid.
+    #[error("the environment variable {0} is invalid")]
+    InvalidEnv(&'static str),
+    /// The HTTP server could not be started.
+    #[cfg(feature = "webhook")]
+    #[error("HTTP server failed: {0}")]
+    Server(#[from] warp::Error),
+    /// A file could not be read.
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    /// The TLS configuration of the HTTP server is invalid.
+    #[cfg(feature = "tls")]
+    #[error("invalid TLS configuration: {0}")]
+    Tls(#[from] tokio_rustls::rustls::Error),
+    /// The cache backend failed to read or write an entity.
+    #[error("cache backend failed: {0}")]
+    Cache(Box<dyn std::error::Error + Send + Sync>),
+    /// The Lambda runtime failed.
+    #[cfg(feature = "lambda")]
+    #[error("Lambda runtime failed: {0}")]
+    Lambda(lambda_runtime::Error),
+}
+
+/// An error returned by the Discord API.
+#[derive(Debug, Clone, Deserialize)]
+pub struct DiscordApiError {
+    /// The HTTP status code of the response.
+    #[serde(skip)]
+    pub status: u16,
+    /// The JSON error code, see
+    /// <https://discord.com/developers/docs/topics/opcodes-and-status-codes#json>.
+    #[serde(default)]
+    pub code: u32,
+    /// A human-readable description of the error.
+    #[serde(default)]
+    pub message: String,
+    /// Detailed errors for each invalid field of the request's form body.
+    pub errors: Option<serde_json::Value>,
+}
+
+impl fmt::Display for DiscordApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Discord API error {} (HTTP {}): {}",
+            self.code, self.status, self.message
+        )
+    }
+}
+
+impl std::error::Error for DiscordApiError {}
+
+/// An error raised by the gateway connection.
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum GatewayError {
+    /// The gateway closed the connection.
+    #[error("the gateway closed the connection with code {code}: {reason}")]
+    Closed { code: u16, reason: String },
+    /// The bot can't start as many sessions as it has shards to connect, until its daily
+    /// session start limit resets.
+    #[error("the session start limit is exhausted, {remaining} sessions remain until it resets in {reset_after:?}")]
+    SessionStartLimit {
+        /// The number of sessions the bot may still start.
+        remaining: u32,
+        /// How long until the limit resets.
+        reset_after: Duration,
+    },
+    /// The gateway sent a payload that could not be understood.
+    #[error("unexpected gateway payload: {0}")]
+    Protocol(String),
+    /// The websocket connection failed.
+    #[cfg(feature = "gateway")]
+    #[error("websocket error: {0}")]
+    WebSocket(Box<tokio_tungstenite::tungstenite::Error>),
+}
+
+// Boxed, since websocket errors are large enough to bloat every `Result` carrying an `Error`.
+#[cfg(feature = "gateway")]
+impl From<tokio_tungstenite::tungstenite::Error> for GatewayError {
+    fn from(err: tokio_tungstenite::tungstenite::Error) -> Self {
+        GatewayError::WebSocket(Box::new(err))
+    }
+}
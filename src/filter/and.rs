@@ -11,7 +11,7 @@ use crate::{
     reject::CombineRejection,
 };
 
-use super::{Filter, FilterBase, Internal};
+use super::{Filter, FilterBase, FilterMeta, Internal};
 
 #[derive(Clone, Copy, Debug)]
 pub struct And<T, U> {
@@ -37,6 +37,11 @@ where
             state: State::First(self.first.filter(Internal), self.second.clone()),
         }
     }
+
+    fn meta(&self, _: Internal, visitor: &mut dyn FilterMeta) {
+        self.first.meta(Internal, visitor);
+        self.second.meta(Internal, visitor);
+    }
 }
 
 #[allow(missing_debug_implementations)]
@@ -5,7 +5,7 @@ use std::task::{Context, Poll};
 use futures_util::{ready, TryFuture};
 use pin_project::pin_project;
 
-use super::{Filter, FilterBase, Internal};
+use super::{Filter, FilterBase, FilterMeta, Internal};
 use crate::generic::Func;
 use crate::reject::CombineRejection;
 
@@ -31,6 +31,10 @@ where
             state: State::First(self.filter.filter(Internal), self.callback.clone()),
         }
     }
+
+    fn meta(&self, _: Internal, visitor: &mut dyn FilterMeta) {
+        self.filter.meta(Internal, visitor);
+    }
 }
 
 #[allow(missing_debug_implementations)]
@@ -5,7 +5,7 @@ use std::sync::Arc;
 
 use futures_util::TryFutureExt;
 
-use super::{Filter, FilterBase, Internal, Tuple};
+use super::{Filter, FilterBase, FilterMeta, Internal, Tuple};
 use crate::reject::Rejection;
 
 /// A type representing a boxed `Filter` trait object.
@@ -27,6 +27,7 @@ use crate::reject::Rejection;
 /// }
 /// ```
 ///
+#[allow(clippy::type_complexity)]
 pub struct BoxedFilter<T: Tuple> {
     filter: Arc<
         dyn Filter<
@@ -79,6 +80,10 @@ impl<T: Tuple + Send> FilterBase for BoxedFilter<T> {
     fn filter(&self, _: Internal) -> Self::Future {
         self.filter.filter(Internal)
     }
+
+    fn meta(&self, _: Internal, visitor: &mut dyn FilterMeta) {
+        self.filter.meta(Internal, visitor);
+    }
 }
 
 struct BoxingFilter<F> {
@@ -97,4 +102,8 @@ where
     fn filter(&self, _: Internal) -> Self::Future {
         Box::pin(self.filter.filter(Internal).into_future())
     }
+
+    fn meta(&self, _: Internal, visitor: &mut dyn FilterMeta) {
+        self.filter.meta(Internal, visitor);
+    }
 }
@@ -5,7 +5,7 @@ use std::task::{Context, Poll};
 use futures_util::TryFuture;
 use pin_project::pin_project;
 
-use super::{Filter, FilterBase, Internal};
+use super::{Filter, FilterBase, FilterMeta, Internal};
 use crate::reject::IsReject;
 
 #[derive(Clone, Copy, Debug)]
@@ -30,6 +30,10 @@ where
             callback: self.callback.clone(),
         }
     }
+
+    fn meta(&self, _: Internal, visitor: &mut dyn FilterMeta) {
+        self.filter.meta(Internal, visitor);
+    }
 }
 
 #[allow(missing_debug_implementations)]
@@ -56,3 +60,45 @@ where
             .map_err(|err| (self.callback)(err))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::{
+        arg,
+        dispatch::Handler,
+        options::InvalidOption,
+        reject::{Reject, Rejection},
+        test_util, Filter,
+    };
+
+    #[derive(Debug)]
+    struct NoAmount;
+
+    impl Reject for NoAmount {}
+
+    #[tokio::test]
+    async fn test_map_rejection() {
+        let pay = crate::command("pay").and(arg::int("amount", "How much to pay").map_rejection(
+            |rejection: Rejection| match rejection.find::<InvalidOption>() {
+                Some(invalid) if invalid.got.is_none() => NoAmount.into(),
+                _ => rejection,
+            },
+        ));
+
+        let rejection = pay
+            .handle(test_util::command("pay", json!([])))
+            .await
+            .unwrap_err();
+        assert!(rejection.find::<NoAmount>().is_some());
+        assert!(rejection.find::<InvalidOption>().is_none());
+
+        let options = json!([{ "name": "amount", "type": 3, "value": "all" }]);
+        let rejection = pay
+            .handle(test_util::command("pay", options))
+            .await
+            .unwrap_err();
+        assert!(rejection.find::<InvalidOption>().is_some());
+    }
+}
@@ -0,0 +1,138 @@
+use std::time::Duration;
+
+use crate::{
+    filters::concurrency::Scope,
+    model::{
+        command::{ApplicationCommand, ApplicationCommandOption},
+        permissions::Permissions,
+        snowflake::Snowflake,
+    },
+};
+
+/// A visitor of what a filter declares, walked with [`Filter::visit`](super::Filter::visit)
+/// without running anything, such as to generate help text or documentation of the commands.
+///
+/// Declarations are visited in the order they are chained: the options of arguments follow
+/// the command or subcommand they belong to, as they would be registered. Every method does
+/// nothing by default, so visitors only implement those they need.
+pub trait FilterMeta {
+    /// Visit a command, without the options declared by the filters chained after it.
+    fn command(&mut self, _command: &ApplicationCommand) {}
+
+    /// Visit a subcommand of the command visited last, and the group it belongs to if any.
+    fn subcommand(
+        &mut self,
+        _group: Option<&ApplicationCommandOption>,
+        _subcommand: &ApplicationCommandOption,
+    ) {
+    }
+
+    /// Visit an option of the command or subcommand visited last, declared by an argument.
+    fn option(&mut self, _option: &ApplicationCommandOption) {}
+
+    /// Visit a check made before the handler runs, which could reject the interaction.
+    fn check(&mut self, _check: Check<'_>) {}
+}
+
+/// A check made by a filter, visited by [`FilterMeta::check`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Check<'a> {
+    /// A limit on how many interactions are handled at once, set with
+    /// [`limit_concurrency`](crate::limit_concurrency).
+    ConcurrencyLimit {
+        /// How many interactions can be handled at once.
+        limit: usize,
+        /// What interactions share the limit.
+        scope: Scope,
+    },
+    /// A wait between uses of a command, set with [`cooldown`](fn@crate::cooldown).
+    Cooldown {
+        /// How long to wait between uses.
+        duration: Duration,
+        /// What interactions share the cooldown.
+        scope: Scope,
+    },
+    /// Permissions the invoking member must have, required with
+    /// [`permission::require`](crate::permission::require).
+    Permissions(Permissions),
+    /// A role the invoking member must have, required with
+    /// [`permission::require_role`](crate::permission::require_role).
+    Role(Snowflake),
+    /// A requirement that the interaction is invoked in a guild, set with
+    /// [`permission::guild_only`](crate::permission::guild_only).
+    GuildOnly,
+    /// A match on the component interactions whose custom ID starts with a prefix.
+    Component {
+        /// The prefix of the custom IDs matched.
+        prefix: &'a str,
+    },
+    /// A limit on how long the filter, and the handler it runs, can take, set with
+    /// [`Filter::timeout`](super::Filter::timeout).
+    Timeout(Duration),
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{Check, FilterMeta};
+    use crate::{
+        arg,
+        command::subcommand,
+        model::command::{ApplicationCommand, ApplicationCommandOption},
+        Filter, Scope,
+    };
+
+    #[derive(Default)]
+    struct Recorder(Vec<String>);
+
+    impl FilterMeta for Recorder {
+        fn command(&mut self, command: &ApplicationCommand) {
+            self.0.push(format!("command {}", command.name));
+        }
+
+        fn subcommand(
+            &mut self,
+            _: Option<&ApplicationCommandOption>,
+            subcommand: &ApplicationCommandOption,
+        ) {
+            self.0.push(format!("subcommand {}", subcommand.name));
+        }
+
+        fn option(&mut self, option: &ApplicationCommandOption) {
+            self.0.push(format!("option {}", option.name));
+        }
+
+        fn check(&mut self, check: Check<'_>) {
+            self.0.push(format!("{:?}", check));
+        }
+    }
+
+    #[test]
+    fn test_visit_declarations() {
+        let filter = crate::command("tag")
+            .description("Manage tags")
+            .and(
+                subcommand("add", "Add a tag")
+                    .and(arg::string("name", "Name of the tag"))
+                    .or(subcommand("list", "List the tags")),
+            )
+            .and(crate::limit_concurrency(1, Scope::PerUser))
+            .timeout(Duration::from_secs(3));
+
+        let mut recorder = Recorder::default();
+        filter.visit(&mut recorder);
+        assert_eq!(
+            recorder.0,
+            [
+                "command tag",
+                "subcommand add",
+                "option name",
+                "subcommand list",
+                "ConcurrencyLimit { limit: 1, scope: PerUser }",
+                "Timeout(3s)",
+            ]
+        );
+    }
+}
@@ -3,19 +3,22 @@ mod and_then;
 mod boxed;
 mod map;
 mod map_err;
+mod meta;
 mod or;
 mod or_else;
 mod recover;
 mod then;
+#[cfg(not(target_arch = "wasm32"))]
+mod timeout;
 mod unify;
 mod untuple_one;
 mod wrap;
 
 use std::future::Future;
 
-use futures_util::{future, TryFuture, TryFutureExt};
+use futures_util::{future, TryFuture};
 
-use crate::context::{self, Context};
+use crate::context::Context;
 pub(crate) use crate::generic::{Combine, Either, Func, Tuple};
 use crate::reject::{CombineRejection, IsReject, Rejection};
 
@@ -24,10 +27,15 @@ use self::and_then::AndThen;
 pub use self::boxed::BoxedFilter;
 pub(crate) use self::map::Map;
 pub(crate) use self::map_err::MapErr;
+pub use self::meta::{Check, FilterMeta};
 pub(crate) use self::or::Or;
 use self::or_else::OrElse;
-use self::recover::Recover;
+use self::recover::{Recover, WithContext};
 use self::then::Then;
+#[cfg(not(target_arch = "wasm32"))]
+pub use self::timeout::TimedOut;
+#[cfg(not(target_arch = "wasm32"))]
+use self::timeout::Timeout;
 use self::unify::Unify;
 use self::untuple_one::UntupleOne;
 pub use self::wrap::wrap_fn;
@@ -42,6 +50,10 @@ pub trait FilterBase {
 
     fn filter(&self, internal: Internal) -> Self::Future;
 
+    // Visits the commands, options and checks this filter declares, so they can
+    // be registered with Discord ahead of any interaction being received.
+    fn meta(&self, _internal: Internal, _visitor: &mut dyn FilterMeta) {}
+
     fn map_err<F, E>(self, _internal: Internal, fun: F) -> MapErr<Self, F>
     where
         Self: Sized,
@@ -267,7 +279,30 @@ pub trait Filter: FilterBase {
     /// Compose this `Filter` with a function receiving an error.
     ///
     /// The function should return some `TryFuture` type yielding the
-    /// same item and error types.
+    /// same item and error types. It is only called when this filter
+    /// rejects, such as to fall back to fetching a value over REST when it
+    /// isn't cached.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use slash::{any::any, reject::{self, Reject, Rejection}, Filter};
+    ///
+    /// #[derive(Debug)]
+    /// struct NotCached;
+    ///
+    /// impl Reject for NotCached {}
+    ///
+    /// let prefix = any()
+    ///     .and_then(|| async {
+    ///         // Look the prefix up in the cache...
+    ///         Err::<String, _>(reject::custom(NotCached))
+    ///     })
+    ///     .or_else(|_rejection: Rejection| async {
+    ///         // ...else fetch it over REST.
+    ///         Ok::<_, Rejection>(("!".to_string(),))
+    ///     });
+    /// ```
     fn or_else<F>(self, fun: F) -> OrElse<Self, F>
     where
         Self: Filter<Error = Rejection> + Sized,
@@ -281,10 +316,43 @@ pub trait Filter: FilterBase {
         }
     }
 
+    /// Transforms the rejection of this `Filter`, such as to turn a low-level
+    /// rejection into one meaningful to the rest of the application, without
+    /// recovering from it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use slash::{arg, options::InvalidOption, reject::{Reject, Rejection}, Filter};
+    ///
+    /// #[derive(Debug)]
+    /// struct InvalidAmount;
+    ///
+    /// impl Reject for InvalidAmount {}
+    ///
+    /// let amount = arg::int("amount", "How much to pay").map_rejection(|rejection: Rejection| {
+    ///     match rejection.find::<InvalidOption>() {
+    ///         Some(_) => InvalidAmount.into(),
+    ///         None => rejection,
+    ///     }
+    /// });
+    /// ```
+    fn map_rejection<F>(self, fun: F) -> MapErr<Self, F>
+    where
+        Self: Filter<Error = Rejection> + Sized,
+        F: Fn(Rejection) -> Rejection + Clone + Send,
+    {
+        MapErr {
+            filter: self,
+            callback: fun,
+        }
+    }
+
     /// Compose this `Filter` with a function receiving an error and
     /// returning a *new* type, instead of the *same* type.
     ///
     /// This is useful for "customizing" rejections into new response types.
+    /// Use [`recover_with_context`](Filter::recover_with_context) to reply to the user.
     /// See also the [rejections example][ex].
     ///
     /// [ex]: https://github.com/seanmonstar/warp/blob/master/examples/rejections.rs
@@ -301,6 +369,42 @@ pub trait Filter: FilterBase {
         }
     }
 
+    /// Compose this `Filter` with a function receiving an error and the [`Context`] of the
+    /// interaction, such as to reply to the user with why the command couldn't be used.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use slash::{
+    ///     model::permissions::Permissions, permission, reject::Rejection, Context, Filter,
+    ///     MessageBuilder,
+    /// };
+    ///
+    /// let purge = slash::command("purge")
+    ///     .and(permission::require(Permissions::MANAGE_MESSAGES))
+    ///     .map(|_context: Context| {})
+    ///     .recover_with_context(|rejection: Rejection, context: Context| async move {
+    ///         if !rejection.is_missing_permissions() {
+    ///             return Err(rejection);
+    ///         }
+    ///         let reply = MessageBuilder::new().content("You can't purge messages here.");
+    ///         context.reply(reply.ephemeral()).await?;
+    ///         Ok(())
+    ///     });
+    /// ```
+    fn recover_with_context<F, R>(self, fun: F) -> Recover<Self, WithContext<F>>
+    where
+        Self: Filter<Error = Rejection> + Sized,
+        F: Fn(Rejection, Context) -> R + Clone + Send,
+        R: TryFuture + Send,
+        R::Error: IsReject,
+    {
+        Recover {
+            filter: self,
+            callback: WithContext { callback: fun },
+        }
+    }
+
     /// Unifies the extracted value of `Filter`s composed with `or`.
     ///
     /// When a `Filter` extracts some `Either<T, T>`, where both sides
@@ -334,36 +438,40 @@ pub trait Filter: FilterBase {
     /// Convenience method to remove one layer of tupling.
     ///
     /// This is useful for when things like `map` don't return a new value,
-    /// but just `()`, since warp will wrap it up into a `((),)`.
+    /// but just `()`, since slash will wrap it up into a `((),)`. Once untupled,
+    /// the `()` is dropped when combined with other filters, so handlers aren't
+    /// given a `()` argument.
     ///
     /// # Example
     ///
     /// ```
-    /// use warp::Filter;
+    /// use slash::{any::any, Context, Filter};
     ///
-    /// let route = warp::path::param()
-    ///     .map(|num: u64| {
-    ///         println!("just logging: {}", num);
+    /// let ping = any()
+    ///     .map(|| {
+    ///         println!("an interaction was received");
     ///         // returning "nothing"
     ///     })
     ///     .untuple_one()
-    ///     .map(|| {
+    ///     .and(slash::command("ping"))
+    ///     .map(|_context: Context| {
     ///         println!("the ((),) was removed");
-    ///         warp::reply()
     ///     });
     /// ```
     ///
+    /// It also spreads a tuple returned by `map` into separate arguments:
+    ///
     /// ```
-    /// use warp::Filter;
+    /// use slash::{Context, Filter};
     ///
-    /// let route = warp::any()
-    ///     .map(|| {
+    /// let roll = slash::command("roll")
+    ///     .map(|context: Context| {
     ///         // wanting to return a tuple
-    ///         (true, 33)
+    ///         (context, 6)
     ///     })
     ///     .untuple_one()
-    ///     .map(|is_enabled: bool, count: i32| {
-    ///         println!("untupled: ({}, {})", is_enabled, count);
+    ///     .map(|_context: Context, sides: i64| {
+    ///         println!("rolling a {}-sided die", sides);
     ///     });
     /// ```
     fn untuple_one<T>(self) -> UntupleOne<Self>
@@ -398,6 +506,65 @@ pub trait Filter: FilterBase {
         wrapper.wrap(self)
     }
 
+    /// Gives up on this filter, and the handler it runs, if it takes longer than `duration`,
+    /// rejecting the interaction with [`TimedOut`] instead.
+    ///
+    /// This keeps a stuck handler from piling up tasks indefinitely. Not available on
+    /// `wasm32`, where timers can't be sent between threads.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use slash::{Context, Filter};
+    ///
+    /// let search = slash::command("search")
+    ///     .description("Search the archives")
+    ///     .then(|context: Context| async move {
+    ///         let _ = context.reply("Nothing found").await;
+    ///     })
+    ///     .timeout(Duration::from_secs(10));
+    /// ```
+    #[cfg(not(target_arch = "wasm32"))]
+    fn timeout(self, duration: std::time::Duration) -> Timeout<Self>
+    where
+        Self: Sized,
+        Self::Error: Into<Rejection>,
+    {
+        Timeout {
+            filter: self,
+            duration,
+        }
+    }
+
+    /// Walks the commands, options and checks declared by this filter with a visitor, without
+    /// running it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use slash::{arg, model::command::ApplicationCommand, Filter, FilterMeta};
+    ///
+    /// #[derive(Default)]
+    /// struct Help(Vec<String>);
+    ///
+    /// impl FilterMeta for Help {
+    ///     fn command(&mut self, command: &ApplicationCommand) {
+    ///         self.0.push(format!("/{}: {}", command.name, command.description));
+    ///     }
+    /// }
+    ///
+    /// let echo = slash::command("echo")
+    ///     .description("Echo a message")
+    ///     .and(arg::string("text", "The text to echo"));
+    /// let mut help = Help::default();
+    /// echo.visit(&mut help);
+    /// assert_eq!(help.0, ["/echo: Echo a message"]);
+    /// ```
+    fn visit(&self, visitor: &mut dyn FilterMeta) {
+        self.meta(Internal, visitor);
+    }
+
     /// Boxes this filter into a trait object, making it easier to name the type.
     ///
     /// # Example
@@ -434,58 +601,6 @@ pub trait Filter: FilterBase {
 
 impl<T: FilterBase> Filter for T {}
 
-pub trait FilterClone: Filter + Clone {}
-
-impl<T: Filter + Clone> FilterClone for T {}
-
 fn _assert_object_safe() {
     fn _assert(_f: &dyn Filter<Extract = (), Error = (), Future = future::Ready<()>>) {}
 }
-
-// ===== FilterFn =====
-
-pub(crate) fn filter_fn<F, U>(func: F) -> FilterFn<F>
-where
-    F: Fn(&mut Context) -> U,
-    U: TryFuture,
-    U::Ok: Tuple,
-    U::Error: IsReject,
-{
-    FilterFn { func }
-}
-
-pub(crate) fn filter_fn_one<F, U>(
-    func: F,
-) -> impl Filter<Extract = (U::Ok,), Error = U::Error> + Copy
-where
-    F: Fn(&mut Context) -> U + Copy,
-    U: TryFuture + Send + 'static,
-    U::Ok: Send,
-    U::Error: IsReject,
-{
-    filter_fn(move |context| func(context).map_ok(|item| (item,)))
-}
-
-#[derive(Copy, Clone)]
-#[allow(missing_debug_implementations)]
-pub(crate) struct FilterFn<F> {
-    // TODO: could include a `debug_str: &'static str` to be used in Debug impl
-    func: F,
-}
-
-impl<F, U> FilterBase for FilterFn<F>
-where
-    F: Fn(&mut Context) -> U,
-    U: TryFuture + Send + 'static,
-    U::Ok: Tuple + Send,
-    U::Error: IsReject,
-{
-    type Extract = U::Ok;
-    type Error = U::Error;
-    type Future = future::IntoFuture<U>;
-
-    #[inline]
-    fn filter(&self, _: Internal) -> Self::Future {
-        context::with(|context| (self.func)(context)).into_future()
-    }
-}
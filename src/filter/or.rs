@@ -5,7 +5,7 @@ use std::task::{Context, Poll};
 use futures_util::{ready, TryFuture};
 use pin_project::pin_project;
 
-use super::{Filter, FilterBase, Internal};
+use super::{Filter, FilterBase, FilterMeta, Internal};
 use crate::generic::Either;
 use crate::reject::CombineRejection;
 
@@ -33,6 +33,11 @@ where
             state: State::First(self.first.filter(Internal), self.second.clone()),
         }
     }
+
+    fn meta(&self, _: Internal, visitor: &mut dyn FilterMeta) {
+        self.first.meta(Internal, visitor);
+        self.second.meta(Internal, visitor);
+    }
 }
 
 #[allow(missing_debug_implementations)]
@@ -75,10 +80,7 @@ where
                             Err(e.combine(err1))
                         }
                     };
-                    self.set(EitherFuture {
-                        state: State::Done,
-                        ..*self
-                    });
+                    self.set(EitherFuture { state: State::Done });
                     return Poll::Ready(ex2);
                 }
                 StateProj::Done => panic!("polled after complete"),
@@ -86,7 +88,6 @@ where
 
             self.set(EitherFuture {
                 state: State::Second(Some(err1), fut2),
-                ..*self
             });
         }
     }
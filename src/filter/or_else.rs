@@ -5,7 +5,7 @@ use std::task::{Context, Poll};
 use futures_util::{ready, TryFuture};
 use pin_project::pin_project;
 
-use super::{Filter, FilterBase, Internal};
+use super::{Filter, FilterBase, FilterMeta, Internal};
 use crate::generic::Func;
 use crate::reject::IsReject;
 
@@ -31,11 +31,15 @@ where
             state: State::First(self.filter.filter(Internal), self.callback.clone()),
         }
     }
+
+    fn meta(&self, _: Internal, visitor: &mut dyn FilterMeta) {
+        self.filter.meta(Internal, visitor);
+    }
 }
 
 #[allow(missing_debug_implementations)]
 #[pin_project]
-pub struct OrElseFuture<T: Filter, F>
+pub struct OrElseFuture<T, F>
 where
     T: Filter,
     F: Func<T::Error>,
@@ -75,10 +79,7 @@ where
                 },
                 StateProj::Second(second) => {
                     let ex2 = ready!(second.try_poll(cx));
-                    self.set(OrElseFuture {
-                        state: State::Done,
-                        ..*self
-                    });
+                    self.set(OrElseFuture { state: State::Done });
                     return Poll::Ready(ex2);
                 }
                 StateProj::Done => panic!("polled after complete"),
@@ -87,8 +88,51 @@ where
             let fut2 = second.call(err);
             self.set(OrElseFuture {
                 state: State::Second(fut2),
-                ..*self
             });
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    use serde_json::json;
+
+    use crate::{
+        dispatch::Handler,
+        reject::{self, Reject, Rejection},
+        test_util, Filter,
+    };
+
+    #[derive(Debug)]
+    struct NotCached;
+
+    impl Reject for NotCached {}
+
+    #[tokio::test]
+    async fn test_or_else_falls_back_on_rejection() {
+        let context = test_util::command("ping", json!([]));
+
+        let fallbacks = Arc::new(AtomicUsize::new(0));
+        let lookup = |cached: Option<u32>| {
+            let fallbacks = fallbacks.clone();
+            crate::any::any()
+                .and_then(move || async move { cached.ok_or_else(|| reject::custom(NotCached)) })
+                .or_else(move |rejection: Rejection| {
+                    assert!(rejection.find::<NotCached>().is_some());
+                    fallbacks.fetch_add(1, Ordering::SeqCst);
+                    async { Ok::<_, Rejection>((7,)) }
+                })
+                .map(|value: u32| assert_eq!(value, 7))
+        };
+
+        lookup(Some(7)).handle(context.clone()).await.unwrap();
+        assert_eq!(fallbacks.load(Ordering::SeqCst), 0);
+        lookup(None).handle(context).await.unwrap();
+        assert_eq!(fallbacks.load(Ordering::SeqCst), 1);
+    }
+}
@@ -5,9 +5,10 @@ use std::task::{Context, Poll};
 use futures_util::{ready, TryFuture};
 use pin_project::pin_project;
 
-use super::{Filter, FilterBase, Internal};
+use super::{Filter, FilterBase, FilterMeta, Internal};
+use crate::context;
 use crate::generic::{Either, Func};
-use crate::reject::IsReject;
+use crate::reject::{IsReject, Rejection};
 
 #[derive(Clone, Copy, Debug)]
 pub struct Recover<T, F> {
@@ -31,11 +32,36 @@ where
             state: State::First(self.filter.filter(Internal), self.callback.clone()),
         }
     }
+
+    fn meta(&self, _: Internal, visitor: &mut dyn FilterMeta) {
+        self.filter.meta(Internal, visitor);
+    }
+}
+
+/// A recovery callback given the context of the interaction alongside the rejection, created
+/// by [`Filter::recover_with_context`](super::Filter::recover_with_context).
+#[derive(Clone, Copy, Debug)]
+pub struct WithContext<F> {
+    pub(super) callback: F,
+}
+
+impl<F, R> Func<Rejection> for WithContext<F>
+where
+    F: Fn(Rejection, crate::Context) -> R,
+{
+    type Output = R;
+
+    #[inline]
+    fn call(&self, rejection: Rejection) -> Self::Output {
+        // Callbacks are called while the filter is polled, with the context set.
+        let context = context::with(|context| context.clone());
+        (self.callback)(rejection, context)
+    }
 }
 
 #[allow(missing_debug_implementations)]
 #[pin_project]
-pub struct RecoverFuture<T: Filter, F>
+pub struct RecoverFuture<T, F>
 where
     T: Filter,
     F: Func<T::Error>,
@@ -84,10 +110,7 @@ where
                         Ok(ex2) => Ok((Either::B((ex2,)),)),
                         Err(e) => Err(e),
                     };
-                    self.set(RecoverFuture {
-                        state: State::Done,
-                        ..*self
-                    });
+                    self.set(RecoverFuture { state: State::Done });
                     return Poll::Ready(ex2);
                 }
                 StateProj::Done => panic!("polled after complete"),
@@ -96,8 +119,29 @@ where
             let fut2 = second.call(err);
             self.set(RecoverFuture {
                 state: State::Second(fut2),
-                ..*self
             });
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::{context::Context, dispatch::Handler, reject::Rejection, test_util, Filter};
+
+    #[tokio::test]
+    async fn test_recover_with_context() {
+        let context = test_util::command("pong", json!([]));
+
+        let ping = crate::command("ping")
+            .map(|_: Context| {})
+            .recover_with_context(|rejection: Rejection, context: Context| async move {
+                match context.command_data() {
+                    Some(data) if data.name == "pong" => Ok(()),
+                    _ => Err(rejection),
+                }
+            });
+        ping.handle(context).await.unwrap();
+    }
+}
@@ -7,7 +7,7 @@ use pin_project::pin_project;
 
 use crate::generic::Func;
 
-use super::{Filter, FilterBase, Internal};
+use super::{Filter, FilterBase, FilterMeta, Internal};
 
 #[derive(Clone, Copy, Debug)]
 pub struct Then<T, F> {
@@ -30,6 +30,10 @@ where
             state: State::First(self.filter.filter(Internal), self.callback.clone()),
         }
     }
+
+    fn meta(&self, _: Internal, visitor: &mut dyn FilterMeta) {
+        self.filter.meta(Internal, visitor);
+    }
 }
 
 #[allow(missing_debug_implementations)]
@@ -0,0 +1,110 @@
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use futures_util::TryFuture;
+use pin_project::pin_project;
+
+use super::{Check, Filter, FilterBase, FilterMeta, Internal};
+use crate::reject::{self, Reject, Rejection};
+use crate::runtime::{self, Timer};
+
+/// The rejection of an interaction whose filter took longer than its
+/// [`timeout`](crate::Filter::timeout).
+#[derive(Debug, Clone, Copy)]
+pub struct TimedOut {
+    /// How long the filter was given.
+    pub duration: Duration,
+}
+
+impl Reject for TimedOut {}
+
+impl fmt::Display for TimedOut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "filter timed out after {:?}", self.duration)
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Timeout<T> {
+    pub(super) filter: T,
+    pub(super) duration: Duration,
+}
+
+impl<T> FilterBase for Timeout<T>
+where
+    T: Filter,
+    T::Error: Into<Rejection>,
+{
+    type Extract = T::Extract;
+    type Error = Rejection;
+    type Future = TimeoutFuture<T>;
+    #[inline]
+    fn filter(&self, _: Internal) -> Self::Future {
+        TimeoutFuture {
+            extract: self.filter.filter(Internal),
+            timer: runtime::timer(self.duration),
+            duration: self.duration,
+        }
+    }
+
+    fn meta(&self, _: Internal, visitor: &mut dyn FilterMeta) {
+        self.filter.meta(Internal, visitor);
+        visitor.check(Check::Timeout(self.duration));
+    }
+}
+
+#[allow(missing_debug_implementations)]
+#[pin_project]
+pub struct TimeoutFuture<T: Filter> {
+    #[pin]
+    extract: T::Future,
+    timer: Timer,
+    duration: Duration,
+}
+
+impl<T> Future for TimeoutFuture<T>
+where
+    T: Filter,
+    T::Error: Into<Rejection>,
+{
+    type Output = Result<T::Extract, Rejection>;
+
+    #[inline]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        if let Poll::Ready(result) = this.extract.try_poll(cx) {
+            return Poll::Ready(result.map_err(Into::into));
+        }
+        match this.timer.as_mut().poll(cx) {
+            Poll::Ready(()) => Poll::Ready(Err(reject::custom(TimedOut {
+                duration: *this.duration,
+            }))),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use futures_util::future;
+    use serde_json::json;
+
+    use super::TimedOut;
+    use crate::{context::Context, dispatch::Handler, test_util, Filter};
+
+    #[tokio::test]
+    async fn test_stuck_filter_times_out() {
+        let stuck = crate::command("stuck")
+            .then(|_: Context| future::pending::<()>())
+            .timeout(Duration::from_millis(10));
+        let context = test_util::command("stuck", json!([]));
+
+        let rejection = stuck.handle(context).await.unwrap_err();
+        assert!(rejection.find::<TimedOut>().is_some());
+    }
+}
@@ -7,7 +7,7 @@ use pin_project::pin_project;
 
 use crate::generic::Either;
 
-use super::{Filter, FilterBase, Internal, Tuple};
+use super::{Filter, FilterBase, FilterMeta, Internal, Tuple};
 
 #[derive(Clone, Copy, Debug)]
 pub struct Unify<F> {
@@ -28,6 +28,10 @@ where
             inner: self.filter.filter(Internal),
         }
     }
+
+    fn meta(&self, _: Internal, visitor: &mut dyn FilterMeta) {
+        self.filter.meta(Internal, visitor);
+    }
 }
 
 #[allow(missing_debug_implementations)]
@@ -5,7 +5,7 @@ use std::task::{Context, Poll};
 use futures_util::{ready, TryFuture};
 use pin_project::pin_project;
 
-use super::{Filter, FilterBase, Internal, Tuple};
+use super::{Filter, FilterBase, FilterMeta, Internal, Tuple};
 
 #[derive(Clone, Copy, Debug)]
 pub struct UntupleOne<F> {
@@ -26,6 +26,10 @@ where
             extract: self.filter.filter(Internal),
         }
     }
+
+    fn meta(&self, _: Internal, visitor: &mut dyn FilterMeta) {
+        self.filter.meta(Internal, visitor);
+    }
 }
 
 #[allow(missing_debug_implementations)]
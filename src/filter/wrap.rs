@@ -6,7 +6,7 @@ pub trait WrapSealed<F: Filter> {
     fn wrap(&self, filter: F) -> Self::Wrapped;
 }
 
-impl<'a, T, F> WrapSealed<F> for &'a T
+impl<T, F> WrapSealed<F> for &T
 where
     T: WrapSealed<F>,
     F: Filter,
@@ -31,7 +31,7 @@ where
 /// # Example
 ///
 /// ```
-/// use crate::warp::Filter;
+/// use warp::Filter;
 ///
 /// let route = warp::any()
 ///     .map(|| "hello world")
@@ -1,63 +1,438 @@
-use std::{pin::Pin, task::Poll};
+use std::{fmt, marker::PhantomData};
 
-use futures_util::Future;
+use futures_util::future;
 
 use crate::{
-    filter::{FilterBase, Internal},
+    context::{self, Context},
+    filter::{FilterBase, FilterMeta, Internal},
     generic::One,
-    reject::Rejection,
+    model::{
+        channel::{Channel, ChannelType},
+        command::{ApplicationCommandOption, ChoiceValue, CommandOptionType},
+        interaction::OptionValue,
+    },
+    options::{CommandOptions, FromOption, InvalidOption, MissingOption, WrongOptionType},
+    reject::{self, Rejection},
 };
 
+/// Create the option declared by an argument. Arguments extract a value, so they are required.
+fn required_option<S: AsRef<str>>(
+    ty: CommandOptionType,
+    name: S,
+    description: S,
+) -> ApplicationCommandOption {
+    ApplicationCommandOption {
+        required: true,
+        ..ApplicationCommandOption::new(ty, name, description)
+    }
+}
+
+/// Read the option of an argument from the current interaction, rejecting with
+/// [`InvalidOption`] if it wasn't given, was given as another type, or is out of range.
+fn extract<T: FromOption>(option: &ApplicationCommandOption) -> Result<One<T>, Rejection> {
+    context::with(|context| {
+        check_range(context, option)?;
+        context
+            .option(&option.name)
+            .map(|value| (value,))
+            .map_err(|_| invalid(context, option))
+    })
+}
+
+/// The rejection of a declared option, with the value it was given.
+fn invalid(context: &Context, option: &ApplicationCommandOption) -> Rejection {
+    let got = context
+        .command_data()
+        .and_then(|data| data.option(&option.name))
+        .and_then(|given| given.value.clone());
+    reject::custom(InvalidOption {
+        name: option.name.clone(),
+        expected: option.ty,
+        got,
+    })
+}
+
+/// Reject a number given below the `min_value` or above the `max_value` of its option.
+/// Discord checks these bounds too, but they can't be trusted from a modified client.
+fn check_range(context: &Context, option: &ApplicationCommandOption) -> Result<(), Rejection> {
+    let given = context
+        .command_data()
+        .and_then(|data| data.option(&option.name))
+        .and_then(|given| given.value.as_ref());
+    let value = match given {
+        Some(OptionValue::Integer(value)) => *value as f64,
+        Some(OptionValue::Number(value)) => *value,
+        _ => return Ok(()),
+    };
+    let bound = |bound: &Option<ChoiceValue>| match *bound {
+        Some(ChoiceValue::Integer(bound)) => Some(bound as f64),
+        Some(ChoiceValue::Double(bound)) => Some(bound),
+        _ => None,
+    };
+    let below = bound(&option.min_value).is_some_and(|min| value < min);
+    let above = bound(&option.max_value).is_some_and(|max| value > max);
+    if below || above {
+        Err(invalid(context, option))
+    } else {
+        Ok(())
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct StringArgument {
-    name: String,
-    description: String,
+    option: ApplicationCommandOption,
 }
 
 impl FilterBase for StringArgument {
     type Extract = One<String>;
     type Error = Rejection;
-    type Future = StringArgFut;
+    type Future = future::Ready<Result<One<String>, Rejection>>;
 
     fn filter(&self, _: Internal) -> Self::Future {
-        todo!()
+        future::ready(extract(&self.option))
     }
-}
-
-pub struct StringArgFut {}
-
-impl Future for StringArgFut {
-    type Output = Result<One<String>, Rejection>;
 
-    fn poll(self: Pin<&mut Self>, _: &mut std::task::Context<'_>) -> std::task::Poll<Self::Output> {
-        Poll::Ready(Ok(("".to_string(),)))
+    fn meta(&self, _: Internal, visitor: &mut dyn FilterMeta) {
+        visitor.option(&self.option);
     }
 }
 
 /// Create a new string argument.
 pub fn string<S: AsRef<str>>(name: S, description: S) -> StringArgument {
     StringArgument {
-        name: name.as_ref().to_string(),
-        description: description.as_ref().to_string(),
+        option: required_option(CommandOptionType::String, name, description),
     }
 }
 
+#[derive(Debug, Clone)]
 pub struct IntArg {
-    name: String,
-    description: String,
+    option: ApplicationCommandOption,
+}
+
+impl IntArg {
+    /// Set the smallest integer that can be given.
+    pub fn min_value(mut self, min_value: i64) -> Self {
+        self.option.min_value = Some(ChoiceValue::Integer(min_value));
+        self
+    }
+
+    /// Set the largest integer that can be given.
+    pub fn max_value(mut self, max_value: i64) -> Self {
+        self.option.max_value = Some(ChoiceValue::Integer(max_value));
+        self
+    }
+}
+
+impl FilterBase for IntArg {
+    type Extract = One<i64>;
+    type Error = Rejection;
+    type Future = future::Ready<Result<One<i64>, Rejection>>;
+
+    fn filter(&self, _: Internal) -> Self::Future {
+        future::ready(extract(&self.option))
+    }
+
+    fn meta(&self, _: Internal, visitor: &mut dyn FilterMeta) {
+        visitor.option(&self.option);
+    }
 }
 
 /// Create a new integer argument.
-pub fn int(name: String, description: String) -> IntArg {
-    IntArg { name, description }
+pub fn int<S: AsRef<str>>(name: S, description: S) -> IntArg {
+    IntArg {
+        option: required_option(CommandOptionType::Integer, name, description),
+    }
 }
 
+#[derive(Debug, Clone)]
 pub struct FloatArg {
-    name: String,
-    description: String,
+    option: ApplicationCommandOption,
+}
+
+impl FloatArg {
+    /// Set the smallest number that can be given.
+    pub fn min_value(mut self, min_value: f64) -> Self {
+        self.option.min_value = Some(ChoiceValue::Double(min_value));
+        self
+    }
+
+    /// Set the largest number that can be given.
+    pub fn max_value(mut self, max_value: f64) -> Self {
+        self.option.max_value = Some(ChoiceValue::Double(max_value));
+        self
+    }
+}
+
+impl FilterBase for FloatArg {
+    type Extract = One<f64>;
+    type Error = Rejection;
+    type Future = future::Ready<Result<One<f64>, Rejection>>;
+
+    fn filter(&self, _: Internal) -> Self::Future {
+        future::ready(extract(&self.option))
+    }
+
+    fn meta(&self, _: Internal, visitor: &mut dyn FilterMeta) {
+        visitor.option(&self.option);
+    }
 }
 
 /// Create a new float argument.
-pub fn float(name: String, description: String) -> FloatArg {
-    FloatArg { name, description }
+pub fn float<S: AsRef<str>>(name: S, description: S) -> FloatArg {
+    FloatArg {
+        option: required_option(CommandOptionType::Number, name, description),
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ChannelArg {
+    option: ApplicationCommandOption,
+}
+
+impl ChannelArg {
+    /// Only show channels of the given types to pick from, such as text, forum or stage
+    /// channels. Defaults to channels of any type.
+    ///
+    /// Channels of other types are rejected with [`InvalidOption`], as a modified client
+    /// could still give them.
+    pub fn channel_types<I: IntoIterator<Item = ChannelType>>(mut self, channel_types: I) -> Self {
+        self.option.channel_types = channel_types.into_iter().collect();
+        self
+    }
+}
+
+impl FilterBase for ChannelArg {
+    type Extract = One<Channel>;
+    type Error = Rejection;
+    type Future = future::Ready<Result<One<Channel>, Rejection>>;
+
+    fn filter(&self, _: Internal) -> Self::Future {
+        let allowed = &self.option.channel_types;
+        future::ready(extract::<Channel>(&self.option).and_then(|(channel,)| {
+            if allowed.is_empty() || allowed.contains(&channel.ty) {
+                Ok((channel,))
+            } else {
+                Err(context::with(|context| invalid(context, &self.option)))
+            }
+        }))
+    }
+
+    fn meta(&self, _: Internal, visitor: &mut dyn FilterMeta) {
+        visitor.option(&self.option);
+    }
+}
+
+/// Create a new channel argument.
+pub fn channel<S: AsRef<str>>(name: S, description: S) -> ChannelArg {
+    ChannelArg {
+        option: required_option(CommandOptionType::Channel, name, description),
+    }
+}
+
+/// A filter declaring and extracting the options of a [`CommandOptions`] struct.
+pub struct OptionsArg<T> {
+    _options: PhantomData<fn() -> T>,
+}
+
+impl<T> Clone for OptionsArg<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for OptionsArg<T> {}
+
+impl<T> fmt::Debug for OptionsArg<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OptionsArg").finish()
+    }
+}
+
+impl<T: CommandOptions + Send + 'static> FilterBase for OptionsArg<T> {
+    type Extract = One<T>;
+    type Error = Rejection;
+    type Future = future::Ready<Result<One<T>, Rejection>>;
+
+    fn filter(&self, _: Internal) -> Self::Future {
+        future::ready(context::with(|context| {
+            let declared = T::options();
+            for option in &declared {
+                check_range(context, option)?;
+            }
+            T::from_context(context)
+                .map(|options| (options,))
+                .map_err(|rejection| {
+                    let name = rejection
+                        .find::<MissingOption>()
+                        .map(|missing| missing.name.clone())
+                        .or_else(|| {
+                            let wrong = rejection.find::<WrongOptionType>()?;
+                            Some(wrong.name.clone())
+                        });
+                    match declared
+                        .iter()
+                        .find(|option| Some(&option.name) == name.as_ref())
+                    {
+                        Some(option) => invalid(context, option),
+                        None => rejection,
+                    }
+                })
+        }))
+    }
+
+    fn meta(&self, _: Internal, visitor: &mut dyn FilterMeta) {
+        for option in T::options() {
+            visitor.option(&option);
+        }
+    }
+}
+
+/// Declare every option of a [`CommandOptions`] struct, and extract the struct from the
+/// options given to the command. Like other arguments, options that can't be read are
+/// rejected with [`InvalidOption`].
+pub fn options<T: CommandOptions>() -> OptionsArg<T> {
+    OptionsArg {
+        _options: PhantomData,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use serde_json::json;
+
+    use super::{channel, float, int, string};
+    use crate::{
+        context::Context,
+        dispatch::Handler,
+        model::channel::{Channel, ChannelType},
+        model::{command::CommandOptionType, interaction::OptionValue},
+        options::InvalidOption,
+        test_util, Filter,
+    };
+
+    #[tokio::test]
+    async fn test_string_argument() {
+        let echoed = Arc::new(Mutex::new(None));
+        let echo = {
+            let echoed = echoed.clone();
+            crate::command("echo")
+                .and(string("text", "The text to echo"))
+                .map(move |_: Context, text: String| *echoed.lock().unwrap() = Some(text))
+        };
+
+        let options = json!([{ "name": "text", "type": 3, "value": "hello" }]);
+        echo.handle(test_util::command("echo", options))
+            .await
+            .unwrap();
+        assert_eq!(echoed.lock().unwrap().as_deref(), Some("hello"));
+
+        let rejection = echo
+            .handle(test_util::command("echo", json!([])))
+            .await
+            .unwrap_err();
+        let invalid = rejection.find::<InvalidOption>().unwrap();
+        assert_eq!(invalid.name, "text");
+        assert_eq!(invalid.got, None);
+        assert_eq!(invalid.to_string(), "missing required option `text`");
+        let options = json!([{ "name": "text", "type": 4, "value": 3 }]);
+        let rejection = echo
+            .handle(test_util::command("echo", options))
+            .await
+            .unwrap_err();
+        assert_eq!(
+            rejection.find::<InvalidOption>().unwrap().got,
+            Some(OptionValue::Integer(3))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_numeric_arguments() {
+        let context = test_util::command(
+            "roll",
+            json!([
+                { "name": "sides", "type": 4, "value": 20 },
+                { "name": "bonus", "type": 10, "value": 1.5 }
+            ]),
+        );
+
+        let rolled = Arc::new(Mutex::new(None));
+        let roll = {
+            let rolled = rolled.clone();
+            crate::command("roll")
+                .and(int("sides", "Sides of the die").min_value(2))
+                .and(float("bonus", "Bonus added to the roll"))
+                .map(move |_: Context, sides: i64, bonus: f64| {
+                    *rolled.lock().unwrap() = Some((sides, bonus));
+                })
+        };
+        roll.handle(context.clone()).await.unwrap();
+        assert_eq!(*rolled.lock().unwrap(), Some((20, 1.5)));
+
+        let wrong = crate::command("roll").and(int("bonus", "Bonus added to the roll"));
+        let rejection = wrong.handle(context.clone()).await.unwrap_err();
+        let invalid = rejection.find::<InvalidOption>().unwrap();
+        assert_eq!(invalid.expected, CommandOptionType::Integer);
+        assert_eq!(invalid.got, Some(OptionValue::Number(1.5)));
+
+        let out_of_range = crate::command("roll").and(int("sides", "Sides").max_value(12));
+        let rejection = out_of_range.handle(context).await.unwrap_err();
+        assert_eq!(
+            rejection.find::<InvalidOption>().unwrap().got,
+            Some(OptionValue::Integer(20))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_channel_argument() {
+        let context = test_util::context(json!({
+            "data": {
+                "id": "940565712559304736",
+                "name": "stage",
+                "type": 1,
+                "options": [{ "name": "target", "type": 7, "value": "5" }],
+                "resolved": { "channels": { "5": { "id": "5", "type": 13 } } }
+            }
+        }));
+
+        let opened = Arc::new(Mutex::new(None));
+        let stage = {
+            let opened = opened.clone();
+            crate::command("stage")
+                .and(
+                    channel("target", "The stage to open")
+                        .channel_types([ChannelType::GuildStageVoice]),
+                )
+                .map(move |_: Context, target: Channel| {
+                    *opened.lock().unwrap() = Some(target.ty);
+                })
+        };
+        stage.handle(context.clone()).await.unwrap();
+        assert_eq!(*opened.lock().unwrap(), Some(ChannelType::GuildStageVoice));
+
+        let forum = crate::command("stage").and(
+            channel("target", "The forum to post in").channel_types([ChannelType::GuildForum]),
+        );
+        let rejection = forum.handle(context).await.unwrap_err();
+        assert_eq!(rejection.find::<InvalidOption>().unwrap().name, "target");
+    }
+
+    #[test]
+    fn test_channel_types() {
+        let arg = channel("target", "The channel to post in")
+            .channel_types([ChannelType::GuildText, ChannelType::GuildAnnouncement]);
+        assert_eq!(
+            serde_json::to_value(&arg.option).unwrap(),
+            json!({
+                "type": 7,
+                "name": "target",
+                "description": "The channel to post in",
+                "required": true,
+                "channel_types": [0, 5],
+            })
+        );
+        let any = channel("target", "The channel to post in");
+        assert!(serde_json::to_value(&any.option).unwrap()["channel_types"].is_null());
+    }
 }
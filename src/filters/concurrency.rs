@@ -0,0 +1,177 @@
+//! A filter limiting how many interactions are handled at once.
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{Arc, Mutex},
+};
+
+use futures_util::future::{BoxFuture, FutureExt};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use crate::{
+    context::{self, Context},
+    filter::{Check, FilterBase, FilterMeta, Internal},
+    generic::One,
+    model::snowflake::Snowflake,
+    reject::{self, Reject, Rejection},
+};
+
+/// What interactions share a concurrency limit, or a cooldown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scope {
+    /// Every interaction shares the limit.
+    Global,
+    /// Each user has their own limit.
+    PerUser,
+    /// Each guild has its own limit. Interactions outside of guilds are limited per user.
+    PerGuild,
+}
+
+impl Scope {
+    /// Returns the key of the limit an interaction counts against in this scope.
+    pub(crate) fn key(self, context: &Context) -> Option<Snowflake> {
+        match self {
+            Scope::Global => None,
+            Scope::PerUser => context.user_id(),
+            Scope::PerGuild => context.guild_id().or_else(|| context.user_id()),
+        }
+    }
+}
+
+/// The rejection of an interaction beyond a concurrency limit.
+#[derive(Debug, Clone, Copy)]
+pub struct ConcurrencyLimited {
+    /// The scope of the limit that was reached.
+    pub scope: Scope,
+}
+
+impl Reject for ConcurrencyLimited {}
+
+impl fmt::Display for ConcurrencyLimited {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "concurrency limit reached ({:?})", self.scope)
+    }
+}
+
+/// A slot of a concurrency limit, held for as long as an interaction is being handled.
+///
+/// The slot is freed once the permit is dropped, so handlers should take it as an argument.
+#[derive(Debug)]
+pub struct ConcurrencyPermit {
+    _permit: OwnedSemaphorePermit,
+}
+
+/// A filter limiting how many interactions are handled at once, created by
+/// [`limit_concurrency`].
+#[derive(Debug, Clone)]
+pub struct LimitConcurrency {
+    limit: usize,
+    scope: Scope,
+    queue: bool,
+    semaphores: Arc<Mutex<HashMap<Option<Snowflake>, Arc<Semaphore>>>>,
+}
+
+/// Create a filter allowing at most `limit` interactions to be handled at once in each
+/// `scope`, rejecting any beyond it with [`ConcurrencyLimited`].
+///
+/// The filter extracts a [`ConcurrencyPermit`], which counts against the limit until the
+/// handler drops it. It should be combined after the commands it limits, so that other
+/// interactions don't count against the limit.
+///
+/// # Example
+///
+/// ```
+/// use slash::{concurrency::ConcurrencyPermit, limit_concurrency, Context, Filter, Scope};
+///
+/// let render = slash::command("render")
+///     .description("Render an expensive image")
+///     .and(limit_concurrency(1, Scope::PerUser))
+///     .map(|context: Context, _permit: ConcurrencyPermit| {
+///         // The user can't render another image until this one is done.
+///     });
+/// ```
+pub fn limit_concurrency(limit: usize, scope: Scope) -> LimitConcurrency {
+    LimitConcurrency {
+        limit,
+        scope,
+        queue: false,
+        semaphores: Arc::default(),
+    }
+}
+
+impl LimitConcurrency {
+    /// Set whether interactions beyond the limit wait for a slot to free up, instead of being
+    /// rejected. Defaults to `false`.
+    ///
+    /// Interactions must be responded to within 3 seconds, so waiting handlers should defer
+    /// their response first.
+    pub fn queue(mut self, queue: bool) -> Self {
+        self.queue = queue;
+        self
+    }
+
+    /// Returns the semaphore of the given key, creating it if needed.
+    fn semaphore(&self, key: Option<Snowflake>) -> Arc<Semaphore> {
+        let mut semaphores = self
+            .semaphores
+            .lock()
+            .unwrap_or_else(|err| err.into_inner());
+        // Semaphores are only referenced elsewhere by their permits and waiters, so the rest
+        // are idle and can be dropped.
+        semaphores.retain(|_, semaphore| Arc::strong_count(semaphore) > 1);
+        semaphores
+            .entry(key)
+            .or_insert_with(|| Arc::new(Semaphore::new(self.limit)))
+            .clone()
+    }
+}
+
+impl FilterBase for LimitConcurrency {
+    type Extract = One<ConcurrencyPermit>;
+    type Error = Rejection;
+    type Future = BoxFuture<'static, Result<Self::Extract, Self::Error>>;
+
+    fn filter(&self, _: Internal) -> Self::Future {
+        let key = context::with(|context| self.scope.key(context));
+        let semaphore = self.semaphore(key);
+        let (queue, scope) = (self.queue, self.scope);
+        async move {
+            let permit = if queue {
+                semaphore
+                    .acquire_owned()
+                    .await
+                    .expect("the semaphore is never closed")
+            } else {
+                semaphore
+                    .try_acquire_owned()
+                    .map_err(|_| reject::custom(ConcurrencyLimited { scope }))?
+            };
+            Ok((ConcurrencyPermit { _permit: permit },))
+        }
+        .boxed()
+    }
+
+    fn meta(&self, _: Internal, visitor: &mut dyn FilterMeta) {
+        visitor.check(Check::ConcurrencyLimit {
+            limit: self.limit,
+            scope: self.scope,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{limit_concurrency, Scope};
+
+    #[tokio::test]
+    async fn test_idle_semaphores_are_dropped() {
+        let limit = limit_concurrency(1, Scope::PerUser);
+        let permit = limit.semaphore(Some(1.into())).try_acquire_owned().unwrap();
+        assert!(limit.semaphore(Some(1.into())).try_acquire_owned().is_err());
+
+        let _other = limit.semaphore(Some(2.into())).try_acquire_owned().unwrap();
+        drop(permit);
+        limit.semaphore(Some(2.into()));
+        assert_eq!(limit.semaphores.lock().unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,131 @@
+//! A filter making users wait between uses of a command.
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use futures_util::future;
+
+use crate::{
+    context,
+    filter::{Check, FilterBase, FilterMeta, Internal},
+    filters::concurrency::Scope,
+    model::snowflake::Snowflake,
+    reject::{self, Reject, Rejection},
+};
+
+/// The rejection of an interaction made before the end of a cooldown.
+#[derive(Debug, Clone, Copy)]
+pub struct OnCooldown {
+    /// How long until the command can be used again.
+    pub retry_after: Duration,
+    /// The scope of the cooldown.
+    pub scope: Scope,
+}
+
+impl Reject for OnCooldown {}
+
+impl fmt::Display for OnCooldown {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "on cooldown, retry after {:?}", self.retry_after)
+    }
+}
+
+/// A filter making users wait between uses of a command, created by [`cooldown`].
+#[derive(Debug, Clone)]
+pub struct Cooldown {
+    duration: Duration,
+    scope: Scope,
+    last_used: Arc<Mutex<HashMap<Option<Snowflake>, Instant>>>,
+}
+
+/// Create a filter rejecting interactions made less than `duration` after the last one
+/// accepted in each `scope`, with [`OnCooldown`].
+///
+/// It should be combined after the commands it applies to, so that other interactions don't
+/// start the cooldown. Not available on `wasm32`, where the system clock can't be read.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+/// use slash::{cooldown, Context, Filter, Scope};
+///
+/// let daily = slash::command("daily")
+///     .description("Claim your daily reward")
+///     .and(cooldown(Duration::from_secs(24 * 60 * 60), Scope::PerUser))
+///     .map(|_context: Context| {});
+/// ```
+pub fn cooldown(duration: Duration, scope: Scope) -> Cooldown {
+    Cooldown {
+        duration,
+        scope,
+        last_used: Arc::default(),
+    }
+}
+
+impl Cooldown {
+    /// Starts the cooldown of the given key, or returns how long is left of it.
+    fn start(&self, key: Option<Snowflake>, now: Instant) -> Result<(), Duration> {
+        let mut last_used = self.last_used.lock().unwrap_or_else(|err| err.into_inner());
+        // Cooldowns that are over would be started again anyway, so they can be dropped.
+        last_used.retain(|_, used| now.duration_since(*used) < self.duration);
+        match last_used.get(&key) {
+            Some(used) => Err(self.duration - now.duration_since(*used)),
+            None => {
+                last_used.insert(key, now);
+                Ok(())
+            }
+        }
+    }
+}
+
+impl FilterBase for Cooldown {
+    type Extract = ();
+    type Error = Rejection;
+    type Future = future::Ready<Result<(), Rejection>>;
+
+    fn filter(&self, _: Internal) -> Self::Future {
+        let key = context::with(|context| self.scope.key(context));
+        future::ready(self.start(key, Instant::now()).map_err(|retry_after| {
+            reject::custom(OnCooldown {
+                retry_after,
+                scope: self.scope,
+            })
+        }))
+    }
+
+    fn meta(&self, _: Internal, visitor: &mut dyn FilterMeta) {
+        visitor.check(Check::Cooldown {
+            duration: self.duration,
+            scope: self.scope,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::cooldown;
+    use crate::Scope;
+
+    #[test]
+    fn test_cooldown_per_key() {
+        let cooldown = cooldown(Duration::from_secs(10), Scope::PerUser);
+        let now = Instant::now();
+        assert_eq!(cooldown.start(Some(1.into()), now), Ok(()));
+        assert_eq!(cooldown.start(Some(2.into()), now), Ok(()));
+
+        let later = now + Duration::from_secs(4);
+        assert_eq!(
+            cooldown.start(Some(1.into()), later),
+            Err(Duration::from_secs(6))
+        );
+        let over = now + Duration::from_secs(10);
+        assert_eq!(cooldown.start(Some(1.into()), over), Ok(()));
+        assert_eq!(cooldown.last_used.lock().unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,279 @@
+//! Filters matching component interactions by custom ID, with state encoded in the ID itself.
+use std::{fmt, marker::PhantomData};
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use futures_util::future;
+use hmac::{Hmac, Mac};
+use serde::{de::DeserializeOwned, Serialize};
+use sha2::Sha256;
+use thiserror::Error;
+
+use crate::{
+    context::{self, Context},
+    filter::{Check, FilterBase, FilterMeta, Internal},
+    reject::{self, Reject, Rejection},
+};
+
+/// The longest custom ID Discord accepts.
+pub const MAX_CUSTOM_ID_LENGTH: usize = 100;
+
+/// The number of bytes of the HMAC kept in signed custom IDs.
+const TAG_LENGTH: usize = 8;
+
+/// The errors raised while encoding or decoding a [`CustomId`].
+#[derive(Debug, Error)]
+pub enum CustomIdError {
+    /// The state could not be serialized or deserialized.
+    #[error("failed to (de)serialize custom ID state: {0}")]
+    Serde(#[from] postcard::Error),
+    /// The encoded custom ID is longer than the 100 characters Discord accepts.
+    #[error("custom ID is {0} characters long, over the limit of 100")]
+    TooLong(usize),
+    /// The custom ID doesn't start with the expected prefix.
+    #[error("custom ID doesn't have the expected prefix")]
+    Mismatch,
+    /// The custom ID has the expected prefix, but its state isn't valid base64.
+    #[error("custom ID is malformed")]
+    Malformed,
+    /// The signature of the custom ID doesn't match its state, so it wasn't created with the
+    /// same key.
+    #[error("custom ID has an invalid signature")]
+    InvalidSignature,
+}
+
+impl Reject for CustomIdError {}
+
+/// Encodes small state structs into the custom ID of a component, and decodes them back when
+/// the component is used, so handlers don't need to store the state anywhere else.
+///
+/// Custom IDs are made of a prefix, telling handlers apart, followed by a colon and the state
+/// in a compact binary encoding. As users can send any custom ID they like, the state can be
+/// signed with a secret key, so that IDs which weren't created by the bot are rejected.
+///
+/// # Example
+///
+/// ```
+/// use serde::{Deserialize, Serialize};
+/// use slash::{custom_id::CustomId, Context, Filter};
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Vote {
+///     poll: u64,
+///     option: u8,
+/// }
+///
+/// let votes = CustomId::<Vote>::new("vote").signed(b"secret");
+/// let button_id = votes.encode(&Vote { poll: 7, option: 2 }).unwrap();
+///
+/// let handler = votes.filter().map(|_context: Context, vote: Vote| {
+///     println!("voted for option {} of poll {}", vote.option, vote.poll);
+/// });
+/// ```
+pub struct CustomId<T> {
+    prefix: String,
+    key: Option<Vec<u8>>,
+    _state: PhantomData<fn() -> T>,
+}
+
+impl<T> CustomId<T>
+where
+    T: Serialize + DeserializeOwned,
+{
+    /// Create a codec for custom IDs starting with the given prefix, which should differ
+    /// between handlers.
+    pub fn new<S: Into<String>>(prefix: S) -> Self {
+        Self {
+            prefix: prefix.into(),
+            key: None,
+            _state: PhantomData,
+        }
+    }
+
+    /// Sign the state with an HMAC-SHA256 of the given key, adding 11 characters to each ID.
+    pub fn signed<K: AsRef<[u8]>>(mut self, key: K) -> Self {
+        self.key = Some(key.as_ref().to_vec());
+        self
+    }
+
+    /// Returns the prefix of the custom IDs.
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// Encode the state into a custom ID, failing if it's longer than Discord accepts.
+    pub fn encode(&self, state: &T) -> Result<String, CustomIdError> {
+        let mut bytes = postcard::to_allocvec(state)?;
+        if let Some(mac) = self.mac(&bytes) {
+            bytes.extend_from_slice(&mac.finalize().into_bytes()[..TAG_LENGTH]);
+        }
+        let custom_id = format!("{}:{}", self.prefix, URL_SAFE_NO_PAD.encode(bytes));
+        if custom_id.len() > MAX_CUSTOM_ID_LENGTH {
+            return Err(CustomIdError::TooLong(custom_id.len()));
+        }
+        Ok(custom_id)
+    }
+
+    /// Decode the state from a custom ID created by [`encode`](Self::encode).
+    pub fn decode(&self, custom_id: &str) -> Result<T, CustomIdError> {
+        let encoded = custom_id
+            .strip_prefix(self.prefix.as_str())
+            .and_then(|rest| rest.strip_prefix(':'))
+            .ok_or(CustomIdError::Mismatch)?;
+        let mut bytes = URL_SAFE_NO_PAD
+            .decode(encoded)
+            .map_err(|_| CustomIdError::Malformed)?;
+        if self.key.is_some() {
+            let split = bytes
+                .len()
+                .checked_sub(TAG_LENGTH)
+                .ok_or(CustomIdError::InvalidSignature)?;
+            let tag = bytes.split_off(split);
+            self.mac(&bytes)
+                .expect("the codec has a key")
+                .verify_truncated_left(&tag)
+                .map_err(|_| CustomIdError::InvalidSignature)?;
+        }
+        Ok(postcard::from_bytes(&bytes)?)
+    }
+
+    /// Create a filter matching component interactions whose custom ID has this prefix,
+    /// extracting the context and the decoded state.
+    ///
+    /// Interactions with another prefix are passed on to the next handler, while those whose
+    /// state can't be decoded are rejected with a [`CustomIdError`].
+    pub fn filter(&self) -> ComponentFilter<T> {
+        ComponentFilter { id: self.clone() }
+    }
+
+    /// Create the HMAC of the prefix and state, if the codec has a key.
+    fn mac(&self, state: &[u8]) -> Option<Hmac<Sha256>> {
+        let key = self.key.as_ref()?;
+        let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any size");
+        // Covering the prefix keeps IDs of one handler from being replayed to another.
+        mac.update(self.prefix.as_bytes());
+        mac.update(b":");
+        mac.update(state);
+        Some(mac)
+    }
+}
+
+impl<T> Clone for CustomId<T> {
+    fn clone(&self) -> Self {
+        Self {
+            prefix: self.prefix.clone(),
+            key: self.key.clone(),
+            _state: PhantomData,
+        }
+    }
+}
+
+impl<T> fmt::Debug for CustomId<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Leaves out the key, so that it isn't logged by accident.
+        f.debug_struct("CustomId")
+            .field("prefix", &self.prefix)
+            .field("signed", &self.key.is_some())
+            .finish()
+    }
+}
+
+/// A filter matching the component interactions of a [`CustomId`], created by
+/// [`CustomId::filter`].
+#[derive(Debug, Clone)]
+pub struct ComponentFilter<T> {
+    id: CustomId<T>,
+}
+
+impl<T> FilterBase for ComponentFilter<T>
+where
+    T: Serialize + DeserializeOwned + Send + 'static,
+{
+    type Extract = (Context, T);
+    type Error = Rejection;
+    type Future = future::Ready<Result<(Context, T), Rejection>>;
+
+    fn filter(&self, _: Internal) -> Self::Future {
+        future::ready(context::with(|context| {
+            let custom_id = context.custom_id().ok_or_else(reject::not_found)?;
+            match self.id.decode(custom_id) {
+                Ok(state) => Ok((context.clone(), state)),
+                Err(CustomIdError::Mismatch) => Err(reject::not_found()),
+                Err(err) => Err(reject::custom(err)),
+            }
+        }))
+    }
+
+    fn meta(&self, _: Internal, visitor: &mut dyn FilterMeta) {
+        visitor.check(Check::Component {
+            prefix: &self.id.prefix,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    use super::{CustomId, CustomIdError, MAX_CUSTOM_ID_LENGTH};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Page {
+        message: u64,
+        page: u32,
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let page = Page {
+            message: 940565712559304734,
+            page: 3,
+        };
+        for id in [
+            CustomId::<Page>::new("page"),
+            CustomId::new("page").signed("key"),
+        ] {
+            let custom_id = id.encode(&page).unwrap();
+            assert!(custom_id.starts_with("page:"));
+            assert_eq!(id.decode(&custom_id).unwrap(), page);
+        }
+    }
+
+    #[test]
+    fn test_rejects_tampering() {
+        let id = CustomId::<Page>::new("page").signed("key");
+        let custom_id = id
+            .encode(&Page {
+                message: 1,
+                page: 1,
+            })
+            .unwrap();
+
+        let forged = CustomId::<Page>::new("page").signed("other key");
+        let forged_id = forged
+            .encode(&Page {
+                message: 1,
+                page: 2,
+            })
+            .unwrap();
+        assert!(matches!(
+            id.decode(&forged_id),
+            Err(CustomIdError::InvalidSignature)
+        ));
+
+        let replayed = custom_id.replacen("page", "other", 1);
+        let other = CustomId::<Page>::new("other").signed("key");
+        assert!(matches!(
+            other.decode(&replayed),
+            Err(CustomIdError::InvalidSignature)
+        ));
+        assert!(matches!(id.decode("button"), Err(CustomIdError::Mismatch)));
+    }
+
+    #[test]
+    fn test_too_long() {
+        let id = CustomId::<String>::new("text");
+        assert!(id.encode(&"a".repeat(60)).is_ok());
+        let err = id.encode(&"a".repeat(80)).unwrap_err();
+        assert!(matches!(err, CustomIdError::TooLong(len) if len > MAX_CUSTOM_ID_LENGTH));
+    }
+}
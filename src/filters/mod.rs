@@ -1,3 +1,8 @@
 pub mod any;
 pub mod arg;
+pub mod concurrency;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod cooldown;
+#[cfg(feature = "custom-id")]
+pub mod custom_id;
 pub mod permission;
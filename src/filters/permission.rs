@@ -1 +1,506 @@
+//! Filters requiring the invoking member to have permissions or roles, and a
+//! [`recover`] layer replying to their rejections.
+use std::fmt;
 
+use futures_util::future;
+
+use crate::{
+    context,
+    filter::{Check, FilterBase, FilterMeta, Internal},
+    model::{permissions::Permissions, snowflake::Snowflake},
+    reject::{self, Reject, Rejection},
+    MessageBuilder,
+};
+
+/// The rejection of an interaction whose member lacks permissions required by a command.
+#[derive(Debug, Clone, Copy)]
+pub struct MissingPermissions {
+    /// The required permissions the member doesn't have.
+    pub missing: Permissions,
+}
+
+impl Reject for MissingPermissions {}
+
+impl fmt::Display for MissingPermissions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "missing permissions: {}", permission_names(self.missing))
+    }
+}
+
+/// The rejection of an interaction whose member lacks a role required by a command.
+#[derive(Debug, Clone, Copy)]
+pub struct MissingRole {
+    /// The required role.
+    pub role: Snowflake,
+}
+
+impl Reject for MissingRole {}
+
+impl fmt::Display for MissingRole {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "missing role {}", self.role)
+    }
+}
+
+/// The rejection of an interaction invoked outside of a guild, by a command that can only be
+/// used in one.
+#[derive(Debug, Clone, Copy)]
+pub struct GuildOnly;
+
+impl Reject for GuildOnly {}
+
+impl fmt::Display for GuildOnly {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("command can only be used in a guild")
+    }
+}
+
+/// A filter requiring the invoking member to have permissions, created by [`require`].
+#[derive(Debug, Clone, Copy)]
+pub struct RequirePermissions {
+    permissions: Permissions,
+}
+
+/// Create a filter rejecting interactions whose member doesn't have every given permission in
+/// the channel, with [`MissingPermissions`]. Interactions outside of guilds are always
+/// rejected.
+///
+/// Unlike a command's default member permissions, which guild admins can override, this is
+/// checked every time the command is used.
+///
+/// # Example
+///
+/// ```
+/// use slash::{model::permissions::Permissions, permission, Context, Filter};
+///
+/// let purge = slash::command("purge")
+///     .description("Delete messages in bulk")
+///     .and(permission::require(Permissions::MANAGE_MESSAGES))
+///     .map(|_context: Context| {});
+/// ```
+pub fn require(permissions: Permissions) -> RequirePermissions {
+    RequirePermissions { permissions }
+}
+
+impl FilterBase for RequirePermissions {
+    type Extract = ();
+    type Error = Rejection;
+    type Future = future::Ready<Result<(), Rejection>>;
+
+    fn filter(&self, _: Internal) -> Self::Future {
+        future::ready(context::with(|context| {
+            let granted = context.member_permissions().unwrap_or_default();
+            if granted.has(self.permissions) {
+                Ok(())
+            } else {
+                Err(reject::custom(MissingPermissions {
+                    missing: self.permissions - granted,
+                }))
+            }
+        }))
+    }
+
+    fn meta(&self, _: Internal, visitor: &mut dyn FilterMeta) {
+        visitor.check(Check::Permissions(self.permissions));
+    }
+}
+
+/// A filter requiring the invoking member to have a role, created by [`require_role`].
+#[derive(Debug, Clone, Copy)]
+pub struct RequireRole {
+    role: Snowflake,
+}
+
+/// Create a filter rejecting interactions whose member doesn't have the given role, with
+/// [`MissingRole`]. Interactions outside of guilds are always rejected.
+pub fn require_role<S: Into<Snowflake>>(role: S) -> RequireRole {
+    RequireRole { role: role.into() }
+}
+
+impl FilterBase for RequireRole {
+    type Extract = ();
+    type Error = Rejection;
+    type Future = future::Ready<Result<(), Rejection>>;
+
+    fn filter(&self, _: Internal) -> Self::Future {
+        future::ready(context::with(|context| match context.member() {
+            Some(member) if member.roles.contains(&self.role) => Ok(()),
+            _ => Err(reject::custom(MissingRole { role: self.role })),
+        }))
+    }
+
+    fn meta(&self, _: Internal, visitor: &mut dyn FilterMeta) {
+        visitor.check(Check::Role(self.role));
+    }
+}
+
+/// A filter requiring interactions to be invoked in a guild, created by [`guild_only`].
+#[derive(Debug, Clone, Copy)]
+pub struct RequireGuild {
+    _priv: (),
+}
+
+/// Create a filter rejecting interactions invoked outside of a guild, such as in direct
+/// messages, with [`GuildOnly`].
+pub fn guild_only() -> RequireGuild {
+    RequireGuild { _priv: () }
+}
+
+impl FilterBase for RequireGuild {
+    type Extract = ();
+    type Error = Rejection;
+    type Future = future::Ready<Result<(), Rejection>>;
+
+    fn filter(&self, _: Internal) -> Self::Future {
+        future::ready(context::with(|context| match context.guild_id() {
+            Some(_) => Ok(()),
+            None => Err(reject::custom(GuildOnly)),
+        }))
+    }
+
+    fn meta(&self, _: Internal, visitor: &mut dyn FilterMeta) {
+        visitor.check(Check::GuildOnly);
+    }
+}
+
+/// Reply to [`MissingPermissions`], [`MissingRole`] and [`GuildOnly`] rejections with an
+/// ephemeral message explaining why the command can't be used, in the language of the user.
+/// Any other rejection is passed on.
+///
+/// Replies are written in English, French, German, Spanish and Brazilian Portuguese, falling
+/// back to the guild's locale and then to English. Permissions are named as in English.
+///
+/// # Example
+///
+/// ```
+/// use slash::{model::permissions::Permissions, permission, Context, Filter};
+///
+/// let purge = slash::command("purge")
+///     .description("Delete messages in bulk")
+///     .and(permission::guild_only())
+///     .and(permission::require(Permissions::MANAGE_MESSAGES))
+///     .map(|_context: Context| {})
+///     .recover(permission::recover);
+/// ```
+pub async fn recover(rejection: Rejection) -> Result<(), Rejection> {
+    let context = context::with(|context| context.clone());
+    let interaction = context.interaction();
+    let replies = Replies::select(
+        interaction.locale.as_deref(),
+        interaction.guild_locale.as_deref(),
+    );
+    match reply(&rejection, replies) {
+        Some(content) => {
+            context
+                .reply(MessageBuilder::new().content(content).ephemeral())
+                .await?;
+            Ok(())
+        }
+        None => Err(rejection),
+    }
+}
+
+/// The replies of [`recover`] in one language, with `{}` standing for the permissions or
+/// role.
+struct Replies {
+    missing_permissions: &'static str,
+    missing_role: &'static str,
+    guild_only: &'static str,
+}
+
+/// The replies in English, used when neither the user's nor the guild's language is known.
+static ENGLISH: Replies = Replies {
+    missing_permissions: "You need the following permissions to use this command: {}.",
+    missing_role: "You need the {} role to use this command.",
+    guild_only: "This command can only be used in a server.",
+};
+
+impl Replies {
+    /// Returns the replies in the user's language, or else in the guild's, or else in English.
+    fn select(locale: Option<&str>, guild_locale: Option<&str>) -> &'static Replies {
+        Replies::of(locale)
+            .or_else(|| Replies::of(guild_locale))
+            .unwrap_or(&ENGLISH)
+    }
+
+    /// Returns the replies in the language of a locale, if they are written in it.
+    fn of(locale: Option<&str>) -> Option<&'static Replies> {
+        match locale?.split('-').next()? {
+            "en" => Some(&ENGLISH),
+            "fr" => Some(&Replies {
+                missing_permissions:
+                    "Vous avez besoin des permissions suivantes pour utiliser cette commande : {}.",
+                missing_role: "Vous avez besoin du rôle {} pour utiliser cette commande.",
+                guild_only: "Cette commande ne peut être utilisée que sur un serveur.",
+            }),
+            "de" => Some(&Replies {
+                missing_permissions:
+                    "Du benötigst die folgenden Berechtigungen, um diesen Befehl zu verwenden: {}.",
+                missing_role: "Du benötigst die Rolle {}, um diesen Befehl zu verwenden.",
+                guild_only: "Dieser Befehl kann nur auf einem Server verwendet werden.",
+            }),
+            "es" => Some(&Replies {
+                missing_permissions:
+                    "Necesitas los siguientes permisos para usar este comando: {}.",
+                missing_role: "Necesitas el rol {} para usar este comando.",
+                guild_only: "Este comando solo se puede usar en un servidor.",
+            }),
+            "pt" => Some(&Replies {
+                missing_permissions:
+                    "Você precisa das seguintes permissões para usar este comando: {}.",
+                missing_role: "Você precisa do cargo {} para usar este comando.",
+                guild_only: "Este comando só pode ser usado em um servidor.",
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Returns the reply of [`recover`] to a rejection, if it replies to it.
+fn reply(rejection: &Rejection, replies: &Replies) -> Option<String> {
+    if rejection.find::<GuildOnly>().is_some() {
+        Some(replies.guild_only.to_string())
+    } else if let Some(missing) = rejection.find::<MissingPermissions>() {
+        Some(
+            replies
+                .missing_permissions
+                .replace("{}", &permission_names(missing.missing)),
+        )
+    } else {
+        let missing = rejection.find::<MissingRole>()?;
+        Some(
+            replies
+                .missing_role
+                .replace("{}", &format!("<@&{}>", missing.role)),
+        )
+    }
+}
+
+/// The names Discord gives permissions in its settings, in the order of their bits.
+const PERMISSION_NAMES: &[(Permissions, &str)] = &[
+    (Permissions::CREATE_INSTANT_INVITE, "Create Invite"),
+    (Permissions::KICK_MEMBERS, "Kick Members"),
+    (Permissions::BAN_MEMBERS, "Ban Members"),
+    (Permissions::ADMINISTRATOR, "Administrator"),
+    (Permissions::MANAGE_CHANNELS, "Manage Channels"),
+    (Permissions::MANAGE_GUILD, "Manage Server"),
+    (Permissions::ADD_REACTIONS, "Add Reactions"),
+    (Permissions::VIEW_AUDIT_LOG, "View Audit Log"),
+    (Permissions::PRIORITY_SPEAKER, "Priority Speaker"),
+    (Permissions::STREAM, "Video"),
+    (Permissions::VIEW_CHANNEL, "View Channels"),
+    (Permissions::SEND_MESSAGES, "Send Messages"),
+    (
+        Permissions::SEND_TTS_MESSAGES,
+        "Send Text-to-Speech Messages",
+    ),
+    (Permissions::MANAGE_MESSAGES, "Manage Messages"),
+    (Permissions::EMBED_LINKS, "Embed Links"),
+    (Permissions::ATTACH_FILES, "Attach Files"),
+    (Permissions::READ_MESSAGE_HISTORY, "Read Message History"),
+    (
+        Permissions::MENTION_EVERYONE,
+        "Mention @everyone, @here, and All Roles",
+    ),
+    (Permissions::USE_EXTERNAL_EMOJIS, "Use External Emojis"),
+    (Permissions::VIEW_GUILD_INSIGHTS, "View Server Insights"),
+    (Permissions::CONNECT, "Connect"),
+    (Permissions::SPEAK, "Speak"),
+    (Permissions::MUTE_MEMBERS, "Mute Members"),
+    (Permissions::DEAFEN_MEMBERS, "Deafen Members"),
+    (Permissions::MOVE_MEMBERS, "Move Members"),
+    (Permissions::USE_VAD, "Use Voice Activity"),
+    (Permissions::CHANGE_NICKNAME, "Change Nickname"),
+    (Permissions::MANAGE_NICKNAMES, "Manage Nicknames"),
+    (Permissions::MANAGE_ROLES, "Manage Roles"),
+    (Permissions::MANAGE_WEBHOOKS, "Manage Webhooks"),
+    (Permissions::MANAGE_GUILD_EXPRESSIONS, "Manage Expressions"),
+    (
+        Permissions::USE_APPLICATION_COMMANDS,
+        "Use Application Commands",
+    ),
+    (Permissions::REQUEST_TO_SPEAK, "Request to Speak"),
+    (Permissions::MANAGE_EVENTS, "Manage Events"),
+    (Permissions::MANAGE_THREADS, "Manage Threads"),
+    (Permissions::CREATE_PUBLIC_THREADS, "Create Public Threads"),
+    (
+        Permissions::CREATE_PRIVATE_THREADS,
+        "Create Private Threads",
+    ),
+    (Permissions::USE_EXTERNAL_STICKERS, "Use External Stickers"),
+    (
+        Permissions::SEND_MESSAGES_IN_THREADS,
+        "Send Messages in Threads",
+    ),
+    (Permissions::USE_EMBEDDED_ACTIVITIES, "Use Activities"),
+    (Permissions::MODERATE_MEMBERS, "Timeout Members"),
+    (
+        Permissions::VIEW_CREATOR_MONETIZATION_ANALYTICS,
+        "View Server Subscription Insights",
+    ),
+    (Permissions::USE_SOUNDBOARD, "Use Soundboard"),
+    (Permissions::CREATE_GUILD_EXPRESSIONS, "Create Expressions"),
+    (Permissions::CREATE_EVENTS, "Create Events"),
+    (Permissions::USE_EXTERNAL_SOUNDS, "Use External Sounds"),
+    (Permissions::SEND_VOICE_MESSAGES, "Send Voice Messages"),
+    (Permissions::SEND_POLLS, "Create Polls"),
+    (Permissions::USE_EXTERNAL_APPS, "Use External Apps"),
+];
+
+/// Name permissions as Discord does, such as `Manage Messages, Read Message History`. Bits
+/// unknown to this version are left out.
+fn permission_names(permissions: Permissions) -> String {
+    PERMISSION_NAMES
+        .iter()
+        .filter(|(permission, _)| permissions.contains(*permission))
+        .map(|(_, name)| *name)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{
+        guild_only, permission_names, recover, reply, require, require_role, GuildOnly,
+        MissingPermissions, MissingRole, Replies, ENGLISH, PERMISSION_NAMES,
+    };
+    use crate::{
+        context::Context,
+        dispatch::Handler,
+        model::permissions::Permissions,
+        reject::{self, Rejection},
+        test_util, Filter,
+    };
+
+    /// A context of the `purge` command, used by a member with the given permissions.
+    fn context(permissions: Permissions) -> Context {
+        test_util::context(json!({
+            "guild_id": "940565712559304737",
+            "member": {
+                "user": {
+                    "id": "940565712559304739",
+                    "username": "slash",
+                    "discriminator": "0001",
+                    "avatar": null
+                },
+                "roles": [],
+                "joined_at": "2022-02-08T00:00:00+00:00",
+                "deaf": false,
+                "mute": false,
+                "permissions": permissions.bits().to_string()
+            },
+            "data": { "id": "940565712559304736", "name": "purge", "type": 1 }
+        }))
+    }
+
+    #[tokio::test]
+    async fn test_require_permissions() {
+        let purge = crate::command("purge")
+            .and(require(
+                Permissions::MANAGE_MESSAGES | Permissions::READ_MESSAGE_HISTORY,
+            ))
+            .map(|_: Context| {});
+
+        let rejection = purge
+            .handle(context(Permissions::READ_MESSAGE_HISTORY))
+            .await
+            .unwrap_err();
+        assert!(rejection.is_missing_permissions());
+        assert_eq!(
+            rejection.find::<MissingPermissions>().unwrap().missing,
+            Permissions::MANAGE_MESSAGES
+        );
+        assert!(purge
+            .handle(context(Permissions::ADMINISTRATOR))
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_require_role_and_guild() {
+        let ban = crate::command("purge")
+            .and(guild_only())
+            .and(require_role(940565712559304740))
+            .map(|_: Context| {});
+
+        let rejection = ban
+            .handle(context(Permissions::ADMINISTRATOR))
+            .await
+            .unwrap_err();
+        assert_eq!(
+            rejection.find::<MissingRole>().unwrap().role,
+            940565712559304740.into()
+        );
+
+        let direct = test_util::command("purge", json!([]));
+        let rejection = ban.handle(direct).await.unwrap_err();
+        assert!(rejection.find::<GuildOnly>().is_some());
+    }
+
+    #[test]
+    fn test_recover_replies() {
+        let missing = Rejection::from(MissingPermissions {
+            missing: Permissions::MANAGE_MESSAGES | Permissions::BAN_MEMBERS,
+        });
+        assert_eq!(
+            reply(&missing, Replies::of(Some("en-US")).unwrap()).unwrap(),
+            "You need the following permissions to use this command: Ban Members, Manage Messages."
+        );
+        assert_eq!(
+            reply(
+                &Rejection::from(GuildOnly),
+                Replies::of(Some("fr")).unwrap()
+            )
+            .unwrap(),
+            "Cette commande ne peut être utilisée que sur un serveur."
+        );
+        let role = Rejection::from(MissingRole {
+            role: 940565712559304740.into(),
+        });
+        assert_eq!(
+            reply(&role, Replies::of(Some("pt-BR")).unwrap()).unwrap(),
+            "Você precisa do cargo <@&940565712559304740> para usar este comando."
+        );
+        assert!(reply(&reject::not_found(), &ENGLISH).is_none());
+        assert_eq!(
+            permission_names(Permissions::ADMINISTRATOR | Permissions::MANAGE_GUILD),
+            "Administrator, Manage Server"
+        );
+        let named = PERMISSION_NAMES
+            .iter()
+            .fold(Permissions::empty(), |named, (permission, _)| {
+                named | *permission
+            });
+        assert_eq!(named, Permissions::all());
+    }
+
+    #[test]
+    fn test_recover_locale_fallback() {
+        let guild_only = |replies: &Replies| replies.guild_only;
+        assert_eq!(
+            guild_only(Replies::select(Some("de"), Some("fr"))),
+            "Dieser Befehl kann nur auf einem Server verwendet werden."
+        );
+        assert_eq!(
+            guild_only(Replies::select(Some("ja"), Some("fr"))),
+            "Cette commande ne peut être utilisée que sur un serveur."
+        );
+        assert_eq!(
+            guild_only(Replies::select(Some("ja"), Some("ko"))),
+            ENGLISH.guild_only
+        );
+        assert_eq!(guild_only(Replies::select(None, None)), ENGLISH.guild_only);
+    }
+
+    #[tokio::test]
+    async fn test_recover_passes_on_other_rejections() {
+        let filter = crate::command("ping").map(|_: Context| {}).recover(recover);
+        assert!(filter
+            .handle(context(Permissions::ADMINISTRATOR))
+            .await
+            .unwrap_err()
+            .is_not_found());
+    }
+}
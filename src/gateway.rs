@@ -1 +0,0 @@
-//! Defines the gateway listeners for the websocket connection.
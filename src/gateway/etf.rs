@@ -0,0 +1,285 @@
+//! Encoding and decoding of gateway payloads in Erlang's External Term Format.
+//!
+//! Terms are converted to and from [`Value`]s, so the models deserialize the same way whichever
+//! encoding the gateway uses. Atoms become strings, except for `nil`, `true` and `false`, and
+//! tuples and lists both become arrays.
+use serde_json::{Map, Number, Value};
+
+const VERSION: u8 = 131;
+
+const NEW_FLOAT_EXT: u8 = 70;
+const SMALL_INTEGER_EXT: u8 = 97;
+const INTEGER_EXT: u8 = 98;
+const FLOAT_EXT: u8 = 99;
+const ATOM_EXT: u8 = 100;
+const SMALL_TUPLE_EXT: u8 = 104;
+const LARGE_TUPLE_EXT: u8 = 105;
+const NIL_EXT: u8 = 106;
+const STRING_EXT: u8 = 107;
+const LIST_EXT: u8 = 108;
+const BINARY_EXT: u8 = 109;
+const SMALL_BIG_EXT: u8 = 110;
+const LARGE_BIG_EXT: u8 = 111;
+const SMALL_ATOM_EXT: u8 = 115;
+const MAP_EXT: u8 = 116;
+const ATOM_UTF8_EXT: u8 = 118;
+const SMALL_ATOM_UTF8_EXT: u8 = 119;
+
+/// Decode a term into a value, describing what was malformed on failure.
+pub(crate) fn decode(bytes: &[u8]) -> Result<Value, String> {
+    let mut decoder = Decoder { bytes };
+    if decoder.u8()? != VERSION {
+        return Err("unsupported term format version".to_string());
+    }
+    decoder.term()
+}
+
+/// Encode a value as a term.
+pub(crate) fn encode(value: &Value) -> Vec<u8> {
+    let mut bytes = vec![VERSION];
+    encode_term(value, &mut bytes);
+    bytes
+}
+
+struct Decoder<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Decoder<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], String> {
+        if self.bytes.len() < len {
+            return Err("unexpected end of term".to_string());
+        }
+        let (taken, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(taken)
+    }
+
+    fn u8(&mut self) -> Result<u8, String> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, String> {
+        Ok(u16::from_be_bytes(self.take(2)?.try_into().unwrap()))
+    }
+
+    fn u32(&mut self) -> Result<u32, String> {
+        Ok(u32::from_be_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn string(&mut self, len: usize) -> Result<String, String> {
+        let bytes = self.take(len)?;
+        String::from_utf8(bytes.to_vec()).map_err(|_| "invalid UTF-8 in term".to_string())
+    }
+
+    fn term(&mut self) -> Result<Value, String> {
+        match self.u8()? {
+            SMALL_INTEGER_EXT => Ok(self.u8()?.into()),
+            INTEGER_EXT => Ok((self.u32()? as i32).into()),
+            NEW_FLOAT_EXT => {
+                let float = f64::from_be_bytes(self.take(8)?.try_into().unwrap());
+                Ok(float_value(float))
+            }
+            FLOAT_EXT => {
+                let text = self.take(31)?;
+                let text = String::from_utf8_lossy(text);
+                let float = text
+                    .trim_end_matches('\0')
+                    .trim()
+                    .parse()
+                    .map_err(|_| "invalid float in term".to_string())?;
+                Ok(float_value(float))
+            }
+            ATOM_EXT | ATOM_UTF8_EXT => {
+                let len = self.u16()? as usize;
+                self.atom(len)
+            }
+            SMALL_ATOM_EXT | SMALL_ATOM_UTF8_EXT => {
+                let len = self.u8()? as usize;
+                self.atom(len)
+            }
+            SMALL_TUPLE_EXT => {
+                let arity = self.u8()? as usize;
+                self.elements(arity)
+            }
+            LARGE_TUPLE_EXT => {
+                let arity = self.u32()? as usize;
+                self.elements(arity)
+            }
+            NIL_EXT => Ok(Value::Array(Vec::new())),
+            STRING_EXT => {
+                let len = self.u16()? as usize;
+                Ok(self
+                    .take(len)?
+                    .iter()
+                    .map(|&byte| Value::from(byte))
+                    .collect())
+            }
+            LIST_EXT => {
+                let len = self.u32()? as usize;
+                let elements = self.elements(len)?;
+                // Proper lists end with an empty list, which isn't an element.
+                match self.term()? {
+                    Value::Array(tail) if tail.is_empty() => Ok(elements),
+                    _ => Err("improper lists are not supported".to_string()),
+                }
+            }
+            BINARY_EXT => {
+                let len = self.u32()? as usize;
+                Ok(Value::String(self.string(len)?))
+            }
+            SMALL_BIG_EXT => {
+                let len = self.u8()? as usize;
+                self.big(len)
+            }
+            LARGE_BIG_EXT => {
+                let len = self.u32()? as usize;
+                self.big(len)
+            }
+            MAP_EXT => {
+                let arity = self.u32()? as usize;
+                let mut map = Map::new();
+                for _ in 0..arity {
+                    let key = match self.term()? {
+                        Value::String(key) => key,
+                        key => key.to_string(),
+                    };
+                    map.insert(key, self.term()?);
+                }
+                Ok(Value::Object(map))
+            }
+            tag => Err(format!("unsupported term tag {}", tag)),
+        }
+    }
+
+    fn atom(&mut self, len: usize) -> Result<Value, String> {
+        Ok(match self.string(len)?.as_str() {
+            "nil" | "null" => Value::Null,
+            "true" => Value::Bool(true),
+            "false" => Value::Bool(false),
+            atom => Value::String(atom.to_string()),
+        })
+    }
+
+    fn elements(&mut self, len: usize) -> Result<Value, String> {
+        (0..len).map(|_| self.term()).collect()
+    }
+
+    fn big(&mut self, len: usize) -> Result<Value, String> {
+        let negative = self.u8()? != 0;
+        let digits = self.take(len)?;
+        if len > 8 {
+            return Err("integer in term is too large".to_string());
+        }
+        let magnitude = digits
+            .iter()
+            .rev()
+            .fold(0u64, |acc, &digit| (acc << 8) | digit as u64);
+        if !negative {
+            return Ok(magnitude.into());
+        }
+        0i64.checked_sub_unsigned(magnitude)
+            .map(Value::from)
+            .ok_or_else(|| "integer in term is too large".to_string())
+    }
+}
+
+fn float_value(float: f64) -> Value {
+    Number::from_f64(float).map_or(Value::Null, Value::Number)
+}
+
+fn encode_term(value: &Value, bytes: &mut Vec<u8>) {
+    match value {
+        Value::Null => encode_atom("nil", bytes),
+        Value::Bool(true) => encode_atom("true", bytes),
+        Value::Bool(false) => encode_atom("false", bytes),
+        Value::Number(number) => {
+            if let Some(int) = number.as_i64() {
+                if let Ok(small) = u8::try_from(int) {
+                    bytes.extend([SMALL_INTEGER_EXT, small]);
+                } else if let Ok(int) = i32::try_from(int) {
+                    bytes.push(INTEGER_EXT);
+                    bytes.extend(int.to_be_bytes());
+                } else {
+                    encode_big(int.unsigned_abs(), int < 0, bytes);
+                }
+            } else if let Some(int) = number.as_u64() {
+                encode_big(int, false, bytes);
+            } else if let Some(float) = number.as_f64() {
+                bytes.push(NEW_FLOAT_EXT);
+                bytes.extend(float.to_be_bytes());
+            }
+        }
+        Value::String(string) => {
+            bytes.push(BINARY_EXT);
+            bytes.extend((string.len() as u32).to_be_bytes());
+            bytes.extend(string.as_bytes());
+        }
+        Value::Array(elements) => {
+            if !elements.is_empty() {
+                bytes.push(LIST_EXT);
+                bytes.extend((elements.len() as u32).to_be_bytes());
+                for element in elements {
+                    encode_term(element, bytes);
+                }
+            }
+            bytes.push(NIL_EXT);
+        }
+        Value::Object(map) => {
+            bytes.push(MAP_EXT);
+            bytes.extend((map.len() as u32).to_be_bytes());
+            for (key, value) in map {
+                encode_term(&Value::String(key.clone()), bytes);
+                encode_term(value, bytes);
+            }
+        }
+    }
+}
+
+fn encode_atom(atom: &str, bytes: &mut Vec<u8>) {
+    bytes.extend([SMALL_ATOM_UTF8_EXT, atom.len() as u8]);
+    bytes.extend(atom.as_bytes());
+}
+
+fn encode_big(magnitude: u64, negative: bool, bytes: &mut Vec<u8>) {
+    let digits = magnitude.to_le_bytes();
+    let len = digits
+        .iter()
+        .rposition(|&digit| digit != 0)
+        .map_or(0, |i| i + 1);
+    bytes.extend([SMALL_BIG_EXT, len as u8, negative as u8]);
+    bytes.extend(&digits[..len]);
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{decode, encode};
+
+    #[test]
+    fn test_round_trip() {
+        let value = json!({
+            "op": 2,
+            "d": {
+                "token": "token",
+                "intents": 1u64 << 40,
+                "shard": [0, 1],
+                "large_threshold": 250,
+                "presence": null,
+                "afk": false,
+                "since": -1000,
+                "ratio": 0.5,
+                "activities": [],
+            },
+        });
+        assert_eq!(decode(&encode(&value)).unwrap(), value);
+    }
+
+    #[test]
+    fn test_decode_atoms_and_strings() {
+        // {ok, "ab"} as a tuple of an atom and a charlist.
+        let bytes = [131, 104, 2, 100, 0, 2, b'o', b'k', 107, 0, 2, b'a', b'b'];
+        assert_eq!(decode(&bytes).unwrap(), json!(["ok", [97, 98]]));
+    }
+}
@@ -0,0 +1,101 @@
+use std::{
+    collections::BTreeMap,
+    fmt,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+/// A change in the connection of a shard.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ShardEvent {
+    /// The shard is connecting to the gateway.
+    Connecting,
+    /// The shard identified a new session.
+    Identified,
+    /// The shard resumed its previous session.
+    Resumed,
+    /// The shard's connection ended, with the close code if the gateway closed it.
+    Disconnected { code: Option<u16> },
+}
+
+/// The statistics of a single shard.
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+pub struct ShardStats {
+    /// The ID of the shard, which is 0 when not sharding.
+    pub id: u32,
+    /// Whether the shard has a session, and hasn't disconnected since.
+    pub connected: bool,
+    /// The number of events dispatched to the shard.
+    pub events: u64,
+    /// The number of times the shard's connection ended.
+    pub disconnects: u64,
+    /// The round-trip time of the shard's last acknowledged heartbeat.
+    pub latency: Option<Duration>,
+}
+
+/// A callback run on every shard event.
+pub(crate) type ShardHook = Arc<dyn Fn(u32, &ShardEvent) + Send + Sync>;
+
+/// Tracks the shards of a [`Gateway`](super::Gateway).
+#[derive(Default)]
+pub struct ShardManager {
+    stats: Mutex<BTreeMap<u32, ShardStats>>,
+    pub(crate) hooks: Vec<ShardHook>,
+}
+
+impl ShardManager {
+    /// Returns the statistics of every shard that has started, ordered by ID.
+    pub fn stats(&self) -> Vec<ShardStats> {
+        self.stats.lock().unwrap().values().cloned().collect()
+    }
+
+    /// Returns the statistics of the shard with the given ID, if it has started.
+    pub fn shard(&self, id: u32) -> Option<ShardStats> {
+        self.stats.lock().unwrap().get(&id).cloned()
+    }
+
+    /// Returns the mean latency of the shards that have had a heartbeat acknowledged.
+    pub fn latency(&self) -> Option<Duration> {
+        let stats = self.stats.lock().unwrap();
+        let latencies: Vec<_> = stats.values().filter_map(|shard| shard.latency).collect();
+        let count = u32::try_from(latencies.len())
+            .ok()
+            .filter(|&count| count > 0)?;
+        Some(latencies.into_iter().sum::<Duration>() / count)
+    }
+
+    /// Update the statistics of a shard.
+    pub(crate) fn update(&self, id: u32, update: impl FnOnce(&mut ShardStats)) {
+        let mut stats = self.stats.lock().unwrap();
+        update(stats.entry(id).or_insert_with(|| ShardStats {
+            id,
+            ..ShardStats::default()
+        }));
+    }
+
+    /// Record an event of a shard, and run the hooks.
+    pub(crate) fn emit(&self, id: u32, event: ShardEvent) {
+        tracing::debug!(shard = id, ?event, "shard event");
+        self.update(id, |stats| match event {
+            ShardEvent::Connecting => {}
+            ShardEvent::Identified | ShardEvent::Resumed => stats.connected = true,
+            ShardEvent::Disconnected { .. } => {
+                stats.connected = false;
+                stats.disconnects += 1;
+            }
+        });
+        for hook in &self.hooks {
+            hook(id, &event);
+        }
+    }
+}
+
+impl fmt::Debug for ShardManager {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ShardManager")
+            .field("stats", &self.stats)
+            .finish_non_exhaustive()
+    }
+}
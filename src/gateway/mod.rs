@@ -0,0 +1,374 @@
+//! Defines the gateway listeners for the websocket connection.
+#[cfg(feature = "etf")]
+mod etf;
+mod manager;
+mod queue;
+mod shard;
+
+use std::{
+    fmt,
+    future::Future,
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use futures_util::{
+    future::{self, BoxFuture},
+    stream, FutureExt, Stream,
+};
+use tokio::sync::{broadcast, watch};
+
+use crate::{
+    backoff::Backoff,
+    cache::Cache,
+    errors::{GatewayError, Result},
+    model::gateway::{
+        Activity, GatewayEvent, GatewayIntents, Identify, IdentifyProperties, Ready, Status,
+        UpdatePresence,
+    },
+    model::snowflake::Snowflake,
+    rest,
+    shutdown::ShutdownHandle,
+};
+
+pub use self::manager::{ShardEvent, ShardManager, ShardStats};
+use self::{queue::IdentifyQueue, shard::Shard};
+
+/// The URL of the gateway.
+pub(crate) const GATEWAY_URL: &str = "wss://gateway.discord.gg";
+/// The query selecting the API version and encoding, appended to every gateway URL.
+#[cfg(not(feature = "etf"))]
+pub(crate) const GATEWAY_QUERY: &str = "?v=10&encoding=json";
+/// The query selecting the API version and encoding, appended to every gateway URL.
+#[cfg(feature = "etf")]
+pub(crate) const GATEWAY_QUERY: &str = "?v=10&encoding=etf";
+
+/// The number of events buffered for each event stream before the oldest are dropped.
+const EVENT_BUFFER: usize = 256;
+
+/// A callback run when a session is identified.
+pub(crate) type ReadyHook = Arc<dyn Fn(Ready) -> BoxFuture<'static, ()> + Send + Sync>;
+
+/// The settings shared by every shard of a gateway connection.
+pub(crate) struct Settings {
+    token: String,
+    intents: GatewayIntents,
+    /// The shards to run, each as its ID and the total number of shards. Empty when not
+    /// sharding.
+    shards: Vec<[u32; 2]>,
+    /// Whether to run the number of shards Discord recommends instead.
+    recommended_shards: bool,
+    large_threshold: Option<u8>,
+    /// The number of shards that may identify at the same time.
+    max_concurrency: u32,
+    properties: IdentifyProperties,
+    /// How long to wait before reconnecting after a connection fails.
+    backoff: Backoff,
+    pub(crate) on_ready: Option<ReadyHook>,
+}
+
+impl fmt::Debug for Settings {
+    // Leaves out the token, so that it isn't logged by accident.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Settings")
+            .field("intents", &self.intents)
+            .field("shards", &self.shards)
+            .field("recommended_shards", &self.recommended_shards)
+            .field("large_threshold", &self.large_threshold)
+            .field("max_concurrency", &self.max_concurrency)
+            .field("properties", &self.properties)
+            .field("backoff", &self.backoff)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Settings {
+    /// Create the `Identify` payload for the given shard.
+    pub(crate) fn identify(
+        &self,
+        shard: Option<[u32; 2]>,
+        presence: Option<UpdatePresence>,
+    ) -> Identify {
+        Identify {
+            token: self.token.clone(),
+            properties: self.properties.clone(),
+            large_threshold: self.large_threshold,
+            shard,
+            presence,
+            intents: self.intents,
+        }
+    }
+}
+
+/// Configures a [`Gateway`] connection.
+#[derive(Debug)]
+pub struct GatewayBuilder {
+    settings: Settings,
+    presence: Option<UpdatePresence>,
+    manager: ShardManager,
+    cache: Cache,
+}
+
+impl GatewayBuilder {
+    /// Set the intents to receive events for. Defaults to none, which is enough to receive
+    /// interactions.
+    pub fn intents(mut self, intents: GatewayIntents) -> Self {
+        self.settings.intents = intents;
+        self
+    }
+    /// Run only the given shard, out of `total` shards.
+    pub fn shard(mut self, id: u32, total: u32) -> Self {
+        self.settings.shards = vec![[id, total]];
+        self
+    }
+    /// Run every shard, out of `total` shards.
+    pub fn shards(mut self, total: u32) -> Self {
+        self.settings.shards = (0..total).map(|id| [id, total]).collect();
+        self
+    }
+    /// Run every shard, out of the number of shards Discord recommends for the bot, which is
+    /// fetched along with its session start limit when the gateway starts running. The shards
+    /// identify as concurrently as the limit allows, overriding
+    /// [`max_concurrency`](Self::max_concurrency).
+    ///
+    /// The gateway fails to run with [`GatewayError::SessionStartLimit`] if the bot can't
+    /// start a session for every shard before its daily limit resets.
+    pub fn recommended_shards(mut self) -> Self {
+        self.settings.recommended_shards = true;
+        self
+    }
+    /// Set the presence to start with.
+    pub fn presence(mut self, presence: UpdatePresence) -> Self {
+        self.presence = Some(presence);
+        self
+    }
+    /// Set the total number of members above which offline members of a guild are not sent.
+    pub fn large_threshold(mut self, threshold: u8) -> Self {
+        self.settings.large_threshold = Some(threshold);
+        self
+    }
+    /// Set the number of shards that may identify at the same time, as given by the
+    /// `session_start_limit` of the bot's gateway information. Defaults to 1.
+    pub fn max_concurrency(mut self, max_concurrency: u32) -> Self {
+        self.settings.max_concurrency = max_concurrency;
+        self
+    }
+    /// Set the connection properties sent when identifying.
+    pub fn properties(mut self, properties: IdentifyProperties) -> Self {
+        self.settings.properties = properties;
+        self
+    }
+    /// Set how long to wait before reconnecting after a connection fails. Defaults to
+    /// [`Backoff::default`].
+    pub fn backoff(mut self, backoff: Backoff) -> Self {
+        self.settings.backoff = backoff;
+        self
+    }
+    /// Set the cache updated from the gateway's events, such as one backed by Redis. Defaults
+    /// to an [`InMemoryCache`](crate::cache::InMemoryCache).
+    pub fn cache(mut self, cache: Cache) -> Self {
+        self.cache = cache;
+        self
+    }
+    /// Run a callback whenever a new session is identified, such as on startup or after the
+    /// previous session was invalidated. Resumed sessions don't run it again.
+    pub fn on_ready<F, Fut>(mut self, callback: F) -> Self
+    where
+        F: Fn(Ready) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.settings.on_ready = Some(Arc::new(move |ready| callback(ready).boxed()));
+        self
+    }
+    /// Run a callback whenever a shard connects, identifies, resumes or disconnects.
+    pub fn on_shard_event<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(u32, &ShardEvent) + Send + Sync + 'static,
+    {
+        self.manager.hooks.push(Arc::new(callback));
+        self
+    }
+    /// Create the gateway client.
+    pub fn build(self) -> Gateway {
+        let total = self.settings.shards.first().map_or(1, |&[_, total]| total);
+        Gateway {
+            total: Arc::new(AtomicU32::new(total)),
+            identify: Arc::new(IdentifyQueue::new(self.settings.max_concurrency)),
+            settings: Arc::new(self.settings),
+            presence: Arc::new(watch::channel(self.presence).0),
+            events: broadcast::channel(EVENT_BUFFER).0,
+            manager: Arc::new(self.manager),
+            cache: self.cache,
+            shutdown: ShutdownHandle::new(),
+        }
+    }
+}
+
+/// A client for the Discord gateway.
+#[derive(Debug, Clone)]
+pub struct Gateway {
+    settings: Arc<Settings>,
+    /// The current presence, watched by the shards so changes are sent as they happen.
+    presence: Arc<watch::Sender<Option<UpdatePresence>>>,
+    events: broadcast::Sender<GatewayEvent>,
+    manager: Arc<ShardManager>,
+    identify: Arc<IdentifyQueue>,
+    /// The total number of shards, once known when running the recommended number.
+    total: Arc<AtomicU32>,
+    cache: Cache,
+    shutdown: ShutdownHandle,
+}
+
+impl Gateway {
+    /// Start configuring a gateway connection authenticating with the given bot token.
+    pub fn builder<S: AsRef<str>>(token: S) -> GatewayBuilder {
+        GatewayBuilder {
+            settings: Settings {
+                token: token.as_ref().to_string(),
+                intents: GatewayIntents::empty(),
+                shards: Vec::new(),
+                recommended_shards: false,
+                large_threshold: None,
+                max_concurrency: 1,
+                properties: IdentifyProperties::default(),
+                backoff: Backoff::default(),
+                on_ready: None,
+            },
+            presence: None,
+            manager: ShardManager::default(),
+            cache: Cache::default(),
+        }
+    }
+
+    /// Connect every shard to the gateway, reconnecting and resuming sessions whenever a
+    /// connection drops. Only returns once the gateway is shut down, or when the gateway
+    /// closes a connection with an unrecoverable error, such as an invalid token or
+    /// disallowed intents.
+    pub async fn run(&self) -> Result<()> {
+        if self.settings.recommended_shards {
+            return self.run_recommended().await;
+        }
+        if self.settings.shards.is_empty() {
+            return Shard::new(self, None).run().await;
+        }
+        self.run_shards(&self.settings.shards).await
+    }
+
+    /// Run the number of shards Discord recommends, once the session start limit allows
+    /// starting them all.
+    async fn run_recommended(&self) -> Result<()> {
+        let bot = rest::Client::new(&self.settings.token)
+            .get_gateway_bot()
+            .await?;
+        let limit = bot.session_start_limit;
+        if limit.remaining < bot.shards {
+            return Err(GatewayError::SessionStartLimit {
+                remaining: limit.remaining,
+                reset_after: limit.reset_after(),
+            }
+            .into());
+        }
+        tracing::info!(
+            shards = bot.shards,
+            remaining = limit.remaining,
+            "running the recommended number of shards"
+        );
+        self.total.store(bot.shards, Ordering::Relaxed);
+        let gateway = Gateway {
+            identify: Arc::new(IdentifyQueue::new(limit.max_concurrency)),
+            ..self.clone()
+        };
+        let shards: Vec<_> = (0..bot.shards).map(|id| [id, bot.shards]).collect();
+        gateway.run_shards(&shards).await
+    }
+
+    async fn run_shards(&self, shards: &[[u32; 2]]) -> Result<()> {
+        let shards = shards
+            .iter()
+            .map(|&shard| async move { Shard::new(self, Some(shard)).run().await });
+        future::try_join_all(shards).await?;
+        Ok(())
+    }
+
+    /// Close every shard's connection with code 1000, ending their sessions, and stop
+    /// reconnecting. [`Gateway::run`] returns once every shard has disconnected.
+    pub fn shutdown(&self) {
+        self.shutdown.shutdown();
+    }
+
+    /// Returns a handle that shuts the gateway down, as [`Gateway::shutdown`] does.
+    pub fn shutdown_handle(&self) -> ShutdownHandle {
+        self.shutdown.clone()
+    }
+
+    /// Returns the manager tracking the state of each shard.
+    pub fn shard_manager(&self) -> &ShardManager {
+        &self.manager
+    }
+
+    /// Returns the ID of the shard receiving the events of a guild, or of direct messages.
+    pub(crate) fn shard_for(&self, guild_id: Option<Snowflake>) -> u32 {
+        shard_of(guild_id, self.total.load(Ordering::Relaxed))
+    }
+
+    /// The mean round-trip time of the last heartbeat of each shard, or `None` before the
+    /// first heartbeat is acknowledged.
+    pub fn latency(&self) -> Option<Duration> {
+        self.manager.latency()
+    }
+
+    /// Returns the cache of guilds, channels, roles and members, updated before each event is
+    /// dispatched to subscribers.
+    pub fn cache(&self) -> &Cache {
+        &self.cache
+    }
+
+    /// Subscribe to the events dispatched by the gateway from now on.
+    ///
+    /// Streams that fall too far behind skip the oldest events they missed.
+    pub fn events(&self) -> impl Stream<Item = GatewayEvent> {
+        stream::unfold(self.events.subscribe(), |mut receiver| async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(event) => return Some((event, receiver)),
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        tracing::warn!(skipped, "gateway event stream lagged behind");
+                    }
+                    Err(broadcast::error::RecvError::Closed) => return None,
+                }
+            }
+        })
+    }
+
+    /// Update the bot's status and activity. If the gateway isn't connected, the presence is
+    /// sent when it next identifies.
+    pub fn set_presence(&self, status: Status, activity: Option<Activity>) {
+        self.presence
+            .send_replace(Some(UpdatePresence::new(status, activity)));
+    }
+}
+
+/// Returns the ID of the shard receiving the events of a guild, out of the given total.
+/// Direct messages are only received by the first shard.
+fn shard_of(guild_id: Option<Snowflake>, total: u32) -> u32 {
+    match guild_id {
+        Some(guild_id) => ((u64::from(guild_id) >> 22) % u64::from(total.max(1))) as u32,
+        None => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::shard_of;
+
+    #[test]
+    fn test_shard_of() {
+        let guild_id = Some(81384788765712384.into());
+        assert_eq!(shard_of(guild_id, 1), 0);
+        assert_eq!(shard_of(guild_id, 16), 2);
+        assert_eq!(shard_of(None, 16), 0);
+    }
+}
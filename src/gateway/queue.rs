@@ -0,0 +1,40 @@
+use std::time::Duration;
+
+use tokio::{
+    sync::Mutex,
+    time::{self, Instant},
+};
+
+/// How long each bucket waits between identifies.
+const IDENTIFY_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Paces the identifies of shards to respect the session start limit.
+///
+/// Shards are grouped into `max_concurrency` buckets by `shard_id % max_concurrency`. Shards in
+/// different buckets may identify at the same time, but each bucket only lets a shard identify
+/// once every 5 seconds.
+#[derive(Debug)]
+pub(crate) struct IdentifyQueue {
+    /// When each bucket last let a shard identify.
+    buckets: Vec<Mutex<Option<Instant>>>,
+}
+
+impl IdentifyQueue {
+    pub(crate) fn new(max_concurrency: u32) -> Self {
+        IdentifyQueue {
+            buckets: (0..max_concurrency.max(1))
+                .map(|_| Mutex::new(None))
+                .collect(),
+        }
+    }
+
+    /// Wait until the given shard may identify.
+    pub(crate) async fn wait(&self, shard_id: u32) {
+        let bucket = &self.buckets[shard_id as usize % self.buckets.len()];
+        let mut last = bucket.lock().await;
+        if let Some(last) = *last {
+            time::sleep_until(last + IDENTIFY_INTERVAL).await;
+        }
+        *last = Some(Instant::now());
+    }
+}
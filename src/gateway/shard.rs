@@ -0,0 +1,368 @@
+use std::{sync::Arc, time::Duration};
+
+use futures_util::{SinkExt, StreamExt};
+use rand::Rng;
+use tokio::{
+    net::TcpStream,
+    sync::{broadcast, watch},
+    time,
+};
+use tokio_tungstenite::{
+    connect_async,
+    tungstenite::{
+        protocol::{frame::coding::CloseCode, CloseFrame},
+        Message,
+    },
+    MaybeTlsStream, WebSocketStream,
+};
+
+use crate::{
+    cache::Cache,
+    errors::{Error, GatewayError, Result},
+    model::{
+        self,
+        gateway::{GatewayEvent, GatewayPayload, Hello, Opcode, Ready, Resume, UpdatePresence},
+    },
+    shutdown::ShutdownHandle,
+};
+
+use super::{
+    Gateway, IdentifyQueue, Settings, ShardEvent, ShardManager, GATEWAY_QUERY, GATEWAY_URL,
+};
+
+type Socket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// A session that can be resumed after a disconnect.
+#[derive(Debug, Clone)]
+struct Session {
+    session_id: String,
+    resume_gateway_url: String,
+}
+
+/// Why a connection ended without an error.
+enum Disconnect {
+    /// The gateway asked for a reconnect, and the session can be resumed.
+    Resume,
+    /// The session was invalidated, and a new one must be identified.
+    Reidentify,
+    /// The gateway is shutting down.
+    Shutdown,
+}
+
+/// A single connection to the gateway.
+pub(crate) struct Shard {
+    settings: Arc<Settings>,
+    id: Option<[u32; 2]>,
+    /// The sequence number of the last dispatched event.
+    sequence: Option<u64>,
+    session: Option<Session>,
+    /// The number of consecutive failed connection attempts.
+    failures: u32,
+    presence: watch::Receiver<Option<UpdatePresence>>,
+    events: broadcast::Sender<GatewayEvent>,
+    manager: Arc<ShardManager>,
+    identify: Arc<IdentifyQueue>,
+    cache: Cache,
+    shutdown: ShutdownHandle,
+}
+
+impl Shard {
+    pub(crate) fn new(gateway: &Gateway, id: Option<[u32; 2]>) -> Self {
+        Shard {
+            settings: gateway.settings.clone(),
+            id,
+            sequence: None,
+            session: None,
+            failures: 0,
+            presence: gateway.presence.subscribe(),
+            events: gateway.events.clone(),
+            manager: gateway.manager.clone(),
+            identify: gateway.identify.clone(),
+            cache: gateway.cache.clone(),
+            shutdown: gateway.shutdown.clone(),
+        }
+    }
+
+    /// Run the shard, reconnecting until the gateway is shut down or closes the connection
+    /// with an unrecoverable error.
+    pub(crate) async fn run(&mut self) -> Result<()> {
+        loop {
+            if self.shutdown.is_shutdown() {
+                return Ok(());
+            }
+            let result = self.connect().await;
+            let code = match result {
+                Err(Error::Gateway(GatewayError::Closed { code, .. })) => Some(code),
+                _ => None,
+            };
+            self.manager
+                .emit(self.shard_id(), ShardEvent::Disconnected { code });
+            match result {
+                Ok(Disconnect::Resume) => {}
+                Ok(Disconnect::Shutdown) => return Ok(()),
+                Ok(Disconnect::Reidentify) => {
+                    self.session = None;
+                    self.sequence = None;
+                    // Discord asks for a random 1-5 second wait before identifying again.
+                    let wait = rand::thread_rng().gen_range(1000..=5000);
+                    self.shutdown.sleep(Duration::from_millis(wait)).await;
+                }
+                Err(Error::Gateway(GatewayError::Closed { code, reason })) if is_fatal(code) => {
+                    return Err(GatewayError::Closed { code, reason }.into());
+                }
+                Err(err) => {
+                    if let Error::Gateway(GatewayError::Closed { code, .. }) = err {
+                        if !is_resumable(code) {
+                            self.session = None;
+                            self.sequence = None;
+                        }
+                    }
+                    self.failures += 1;
+                    let delay = self.settings.backoff.delay(self.failures);
+                    tracing::warn!(error = %err, ?delay, "gateway connection lost, reconnecting");
+                    self.shutdown.sleep(delay).await;
+                }
+            }
+        }
+    }
+
+    /// Connect once, resuming the previous session if there is one, and process events until
+    /// the connection ends.
+    async fn connect(&mut self) -> Result<Disconnect> {
+        self.manager.emit(self.shard_id(), ShardEvent::Connecting);
+        let shutdown = self.shutdown.clone();
+        let (mut socket, interval) = tokio::select! {
+            opened = self.open() => opened?,
+            () = shutdown.wait() => return Ok(Disconnect::Shutdown),
+        };
+        // The first heartbeat is sent after a random fraction of the interval, so that shards
+        // reconnecting together don't heartbeat in lockstep.
+        let jitter = interval.mul_f64(rand::thread_rng().gen());
+        let mut heartbeat = time::interval_at(time::Instant::now() + jitter, interval);
+        // When the last heartbeat was sent, until it is acknowledged.
+        let mut pending: Option<time::Instant> = None;
+
+        loop {
+            tokio::select! {
+                () = shutdown.wait() => {
+                    let frame = CloseFrame {
+                        code: CloseCode::Normal,
+                        reason: "shutting down".into(),
+                    };
+                    let _ = socket.close(Some(frame)).await;
+                    return Ok(Disconnect::Shutdown);
+                }
+                _ = heartbeat.tick() => {
+                    if pending.is_some() {
+                        tracing::warn!("heartbeat was not acknowledged, reconnecting");
+                        close(&mut socket).await;
+                        return Ok(Disconnect::Resume);
+                    }
+                    send(&mut socket, Opcode::Heartbeat, self.sequence.into()).await?;
+                    pending = Some(time::Instant::now());
+                }
+                Ok(()) = self.presence.changed() => {
+                    let presence = self.presence.borrow_and_update().clone();
+                    if let Some(presence) = presence {
+                        send(&mut socket, Opcode::PresenceUpdate, serde_json::to_value(presence)?)
+                            .await?;
+                    }
+                }
+                payload = receive(&mut socket) => {
+                    let payload = payload?;
+                    match payload.op {
+                        Opcode::Dispatch => self.dispatch(payload).await?,
+                        Opcode::Heartbeat => {
+                            send(&mut socket, Opcode::Heartbeat, self.sequence.into()).await?;
+                        }
+                        Opcode::HeartbeatAck => {
+                            if let Some(sent) = pending.take() {
+                                let latency = sent.elapsed();
+                                self.manager.update(self.shard_id(), |stats| {
+                                    stats.latency = Some(latency);
+                                });
+                            }
+                        }
+                        Opcode::Reconnect => {
+                            close(&mut socket).await;
+                            return Ok(Disconnect::Resume);
+                        }
+                        Opcode::InvalidSession => {
+                            let resumable = payload.d.as_bool().unwrap_or(false);
+                            return Ok(if resumable && self.session.is_some() {
+                                close(&mut socket).await;
+                                Disconnect::Resume
+                            } else {
+                                let _ = socket.close(None).await;
+                                Disconnect::Reidentify
+                            });
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+    }
+
+    /// Open a connection, and identify or resume the session. Returns the socket and the
+    /// heartbeat interval.
+    async fn open(&mut self) -> Result<(Socket, Duration)> {
+        let url = match self.session {
+            Some(ref session) => format!("{}/{}", session.resume_gateway_url, GATEWAY_QUERY),
+            None => format!("{}/{}", GATEWAY_URL, GATEWAY_QUERY),
+        };
+        if self.session.is_none() {
+            // Waits before connecting, so that the connection isn't left idle while queued.
+            self.identify.wait(self.shard_id()).await;
+        }
+        let (mut socket, _) = connect_async(url).await.map_err(GatewayError::from)?;
+
+        let hello = receive(&mut socket).await?;
+        if hello.op != Opcode::Hello {
+            return Err(
+                GatewayError::Protocol(format!("expected Hello, got {:?}", hello.op)).into(),
+            );
+        }
+        let hello: Hello = model::from_value(hello.d)?;
+
+        match self.session {
+            Some(ref session) => {
+                let resume = Resume {
+                    token: self.settings.token.clone(),
+                    session_id: session.session_id.clone(),
+                    seq: self.sequence,
+                };
+                send(&mut socket, Opcode::Resume, serde_json::to_value(resume)?).await?;
+            }
+            None => {
+                let presence = self.presence.borrow_and_update().clone();
+                let identify = self.settings.identify(self.id, presence);
+                send(
+                    &mut socket,
+                    Opcode::Identify,
+                    serde_json::to_value(identify)?,
+                )
+                .await?;
+            }
+        }
+        Ok((socket, Duration::from_millis(hello.heartbeat_interval)))
+    }
+
+    /// The ID of the shard, which is 0 when not sharding.
+    fn shard_id(&self) -> u32 {
+        self.id.map_or(0, |[id, _]| id)
+    }
+
+    /// Handle a dispatched event.
+    async fn dispatch(&mut self, payload: GatewayPayload) -> serde_json::Result<()> {
+        self.sequence = payload.s.or(self.sequence);
+        self.manager
+            .update(self.shard_id(), |stats| stats.events += 1);
+        let name = payload.t.unwrap_or_default();
+        let event = match name.as_str() {
+            "READY" => {
+                let ready: Ready = model::from_value(payload.d)?;
+                self.session = Some(Session {
+                    session_id: ready.session_id.clone(),
+                    resume_gateway_url: ready.resume_gateway_url.clone(),
+                });
+                self.failures = 0;
+                self.manager.emit(self.shard_id(), ShardEvent::Identified);
+                if let Some(ref on_ready) = self.settings.on_ready {
+                    // Spawned so that slow startup logic doesn't hold up heartbeating.
+                    tokio::spawn(on_ready(ready.clone()));
+                }
+                GatewayEvent::Ready(Box::new(ready))
+            }
+            "RESUMED" => {
+                self.failures = 0;
+                self.manager.emit(self.shard_id(), ShardEvent::Resumed);
+                GatewayEvent::Raw {
+                    name,
+                    data: payload.d,
+                }
+            }
+            _ => {
+                // Updated before the event is sent, so that handlers see the change.
+                self.cache.update(&name, &payload.d).await;
+                GatewayEvent::Raw {
+                    name,
+                    data: payload.d,
+                }
+            }
+        };
+        // Sending only fails when nobody is subscribed.
+        let _ = self.events.send(event);
+        Ok(())
+    }
+}
+
+/// Whether a close code means reconnecting would fail again.
+fn is_fatal(code: u16) -> bool {
+    // Authentication failed, invalid shard, sharding required, invalid API version, invalid
+    // intents, and disallowed intents.
+    matches!(code, 4004 | 4010 | 4011 | 4012 | 4013 | 4014)
+}
+
+/// Whether the session can be resumed after the connection closed with the given code.
+fn is_resumable(code: u16) -> bool {
+    // Invalid sequence number, and session timed out.
+    !matches!(code, 4007 | 4009)
+}
+
+/// Read the next payload from the socket, skipping any non-text frames.
+async fn receive(socket: &mut Socket) -> Result<GatewayPayload> {
+    loop {
+        let message = match socket.next().await {
+            Some(message) => message.map_err(GatewayError::from)?,
+            None => {
+                return Err(GatewayError::Closed {
+                    code: 1006,
+                    reason: "connection lost".to_string(),
+                }
+                .into())
+            }
+        };
+        match message {
+            Message::Text(text) => return Ok(model::from_slice(text.as_bytes())?),
+            #[cfg(feature = "etf")]
+            Message::Binary(bytes) => {
+                let value = super::etf::decode(&bytes).map_err(GatewayError::Protocol)?;
+                return Ok(model::from_value(value)?);
+            }
+            Message::Close(frame) => {
+                let (code, reason) = frame
+                    .map(|frame| (frame.code.into(), frame.reason.into_owned()))
+                    .unwrap_or((1005, String::new()));
+                return Err(GatewayError::Closed { code, reason }.into());
+            }
+            _ => continue,
+        }
+    }
+}
+
+/// Close the socket without invalidating the session, so that it can be resumed.
+async fn close(socket: &mut Socket) {
+    // Closing with 1000 or 1001 would end the session.
+    let frame = CloseFrame {
+        code: CloseCode::Library(4000),
+        reason: "reconnecting".into(),
+    };
+    let _ = socket.close(Some(frame)).await;
+}
+
+/// Send a payload over the socket.
+async fn send(socket: &mut Socket, op: Opcode, d: serde_json::Value) -> Result<()> {
+    let payload = GatewayPayload {
+        op,
+        d,
+        s: None,
+        t: None,
+    };
+    #[cfg(not(feature = "etf"))]
+    let message = Message::Text(serde_json::to_string(&payload)?);
+    #[cfg(feature = "etf")]
+    let message = Message::Binary(super::etf::encode(&serde_json::to_value(&payload)?));
+    socket.send(message).await.map_err(GatewayError::from)?;
+    Ok(())
+}
@@ -3,11 +3,6 @@ pub struct Product<H, T: HList>(pub(crate) H, pub(crate) T);
 
 pub type One<T> = (T,);
 
-#[inline]
-pub(crate) fn one<T>(val: T) -> One<T> {
-    (val,)
-}
-
 #[derive(Debug)]
 pub enum Either<T, U> {
     A(T),
@@ -102,17 +97,17 @@ where
     }
 }
 
-// impl<F, R> Func<crate::Rejection> for F
-// where
-//     F: Fn(crate::Rejection) -> R,
-// {
-//     type Output = R;
+impl<F, R> Func<crate::reject::Rejection> for F
+where
+    F: Fn(crate::reject::Rejection) -> R,
+{
+    type Output = R;
 
-//     #[inline]
-//     fn call(&self, arg: crate::Rejection) -> Self::Output {
-//         (*self)(arg)
-//     }
-// }
+    #[inline]
+    fn call(&self, arg: crate::reject::Rejection) -> Self::Output {
+        (*self)(arg)
+    }
+}
 
 macro_rules! product {
     ($H:expr) => { Product($H, ()) };
@@ -248,3 +243,16 @@ generics! {
     T15,
     T16
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Tuple;
+
+    #[test]
+    fn test_combine_drops_units() {
+        assert_eq!(().combine((1,)), (1,));
+        assert_eq!((1,).combine(()), (1,));
+        assert_eq!(().combine(()), ());
+        assert_eq!((1, "two").combine((3.0,)), (1, "two", 3.0));
+    }
+}
@@ -1 +0,0 @@
-//! Defines the HTTP handling server for slash interactions.
@@ -0,0 +1,110 @@
+//! Defines how requests to the interactions endpoint are verified and handled, independently
+//! of the HTTP server receiving them.
+#[cfg(feature = "webhook")]
+pub(crate) mod server;
+#[cfg(feature = "tls")]
+pub(crate) mod tls;
+
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+
+use crate::{
+    client::Client,
+    model::{
+        self,
+        interaction::{Interaction, InteractionResponse, InteractionType},
+    },
+};
+
+/// The header holding the signature of a request.
+#[cfg(any(feature = "webhook", feature = "tower", feature = "lambda"))]
+pub(crate) const SIGNATURE_HEADER: &str = "x-signature-ed25519";
+/// The header holding the timestamp a request was signed at.
+#[cfg(any(feature = "webhook", feature = "tower", feature = "lambda"))]
+pub(crate) const TIMESTAMP_HEADER: &str = "x-signature-timestamp";
+
+/// The response to a request to the interactions endpoint, independent of the HTTP server
+/// sending it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpResponse {
+    /// The HTTP status code.
+    pub status: u16,
+    /// The JSON body of the response, if there is one.
+    pub body: Option<Vec<u8>>,
+}
+
+impl HttpResponse {
+    fn status(status: u16) -> Self {
+        Self { status, body: None }
+    }
+}
+
+/// Parse an application's hex-encoded public key.
+pub(crate) fn parse_public_key(key: &str) -> Option<VerifyingKey> {
+    let bytes: [u8; 32] = hex::decode(key).ok()?.try_into().ok()?;
+    VerifyingKey::from_bytes(&bytes).ok()
+}
+
+/// Check that a request was signed by Discord.
+pub(crate) fn verify(key: &VerifyingKey, signature: &str, timestamp: &str, body: &[u8]) -> bool {
+    let signature = match hex::decode(signature).map(|bytes| Signature::from_slice(&bytes)) {
+        Ok(Ok(signature)) => signature,
+        _ => return false,
+    };
+    let message = [timestamp.as_bytes(), body].concat();
+    key.verify(&message, &signature).is_ok()
+}
+
+/// How [`handle`] dispatches the interaction of a request before acknowledging it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Dispatch {
+    /// Handle the interaction in the background, acknowledging it right away. Used by the
+    /// servers that keep running once a response is sent.
+    #[cfg_attr(not(any(feature = "webhook", feature = "tower")), allow(dead_code))]
+    Spawn,
+    /// Handle the interaction before acknowledging it, for runtimes that may stop running
+    /// anything once a request is answered, like Lambda.
+    Await,
+}
+
+/// Verify an interactions request, and dispatch the interaction it carries.
+pub(crate) async fn handle(
+    client: &Client,
+    key: &VerifyingKey,
+    signature: Option<&str>,
+    timestamp: Option<&str>,
+    body: &[u8],
+    dispatch: Dispatch,
+) -> HttpResponse {
+    let verified = match (signature, timestamp) {
+        (Some(signature), Some(timestamp)) => verify(key, signature, timestamp, body),
+        _ => false,
+    };
+    if !verified {
+        return HttpResponse::status(401);
+    }
+    let interaction: Interaction = match model::from_slice(body) {
+        Ok(interaction) => interaction,
+        Err(err) => {
+            tracing::warn!(error = %err, "failed to deserialize interaction");
+            return HttpResponse::status(400);
+        }
+    };
+    // Discord checks the endpoint with pings when it's set, which must be answered in the
+    // response itself.
+    if interaction.ty == InteractionType::Ping {
+        return match serde_json::to_vec(&InteractionResponse::pong()) {
+            Ok(body) => HttpResponse {
+                status: 200,
+                body: Some(body),
+            },
+            Err(_) => HttpResponse::status(500),
+        };
+    }
+    // Handlers respond through the callback endpoint, so the request is only acknowledged.
+    let context = client.context(interaction);
+    match dispatch {
+        Dispatch::Spawn => client.spawn_dispatch(context),
+        Dispatch::Await => client.dispatch(context).await,
+    }
+    HttpResponse::status(202)
+}
@@ -0,0 +1,163 @@
+//! Defines the HTTP server receiving interactions, built on warp.
+use std::net::SocketAddr;
+#[cfg(feature = "tls")]
+use std::sync::Arc;
+
+use ed25519_dalek::VerifyingKey;
+use futures_util::{future::BoxFuture, FutureExt};
+#[cfg(feature = "tls")]
+use tokio_rustls::rustls::ServerConfig;
+use warp::{http::StatusCode, hyper::body::Bytes, reply::Response, Filter as _, Reply};
+
+use super::{handle, Dispatch, HttpResponse, SIGNATURE_HEADER, TIMESTAMP_HEADER};
+use crate::{
+    client::Client,
+    errors::{Error, Result},
+    runtime,
+};
+
+/// Where the built-in server listens for interactions.
+#[derive(Debug)]
+pub(crate) struct Listen {
+    pub(crate) address: SocketAddr,
+    /// Whether to serve the `/healthz` and `/readyz` probes.
+    pub(crate) health_checks: bool,
+    /// The TLS configuration, if the server terminates TLS itself.
+    #[cfg(feature = "tls")]
+    pub(crate) tls: Option<Arc<ServerConfig>>,
+}
+
+/// Serve the interactions endpoint until the client is shut down.
+pub(crate) async fn serve(listen: &Listen, client: Client) -> Result<()> {
+    let key = *client
+        .public_key()
+        .ok_or(Error::MissingConfig("public key"))?;
+    let shutdown = client.shutdown_handle();
+    let timeout = client.shutdown_timeout();
+    let route = if listen.health_checks {
+        health(client.clone())
+            .or(route(client.clone(), key))
+            .unify()
+            .boxed()
+    } else {
+        route(client.clone(), key).boxed()
+    };
+    let signal = {
+        let shutdown = shutdown.clone();
+        async move { shutdown.wait().await }
+    };
+    let server: BoxFuture<'static, ()> = match listen {
+        #[cfg(feature = "tls")]
+        Listen {
+            address,
+            tls: Some(config),
+            ..
+        } => {
+            let listener = tokio::net::TcpListener::bind(address).await?;
+            let address = listener.local_addr()?;
+            tracing::info!(%address, "listening for interactions over TLS");
+            let incoming = super::tls::incoming(listener, config.clone());
+            warp::serve(route)
+                .serve_incoming_with_graceful_shutdown(incoming, signal)
+                .boxed()
+        }
+        Listen { address, .. } => {
+            let (address, server) =
+                warp::serve(route).try_bind_with_graceful_shutdown(*address, signal)?;
+            tracing::info!(%address, "listening for interactions");
+            server.boxed()
+        }
+    };
+    tokio::pin!(server);
+    tokio::select! {
+        () = &mut server => {}
+        () = shutdown.wait() => {
+            client.set_ready(false);
+            // The server stops accepting connections, and open requests are given until the
+            // deadline to finish.
+            let _ = runtime::timeout(timeout, server).await;
+        }
+    }
+    Ok(())
+}
+
+/// Create a warp filter answering `GET` requests to `/healthz`, which always succeed, and to
+/// `/readyz`, which only succeed while the client is ready.
+fn health(
+    client: Client,
+) -> impl warp::Filter<Extract = (Response,), Error = warp::Rejection> + Clone {
+    let healthz = warp::path!("healthz").map(|| StatusCode::OK.into_response());
+    let readyz = warp::path!("readyz").map(move || {
+        if client.is_ready() {
+            StatusCode::OK.into_response()
+        } else {
+            StatusCode::SERVICE_UNAVAILABLE.into_response()
+        }
+    });
+    warp::get().and(healthz.or(readyz).unify())
+}
+
+/// Create a warp filter answering `POST` requests to the interactions endpoint.
+pub(crate) fn route(
+    client: Client,
+    key: VerifyingKey,
+) -> impl warp::Filter<Extract = (Response,), Error = warp::Rejection> + Clone {
+    warp::post()
+        .and(warp::header::optional::<String>(SIGNATURE_HEADER))
+        .and(warp::header::optional::<String>(TIMESTAMP_HEADER))
+        .and(warp::body::bytes())
+        .then(
+            move |signature: Option<String>, timestamp: Option<String>, body: Bytes| {
+                let client = client.clone();
+                async move {
+                    let response = handle(
+                        &client,
+                        &key,
+                        signature.as_deref(),
+                        timestamp.as_deref(),
+                        &body,
+                        Dispatch::Spawn,
+                    )
+                    .await;
+                    into_warp_response(response)
+                }
+            },
+        )
+}
+
+/// Convert a response into one warp can send.
+fn into_warp_response(response: HttpResponse) -> Response {
+    let status = StatusCode::from_u16(response.status).unwrap_or(StatusCode::OK);
+    match response.body {
+        Some(body) => {
+            let reply = warp::reply::with_header(body, "content-type", "application/json");
+            warp::reply::with_status(reply, status).into_response()
+        }
+        None => status.into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use warp::http::StatusCode;
+
+    use crate::Client;
+
+    #[tokio::test]
+    async fn test_readyz_follows_client() {
+        let client = Client::builder()
+            .public_key("0".repeat(64))
+            .build_webhook()
+            .unwrap();
+        let health = super::health(client.clone());
+
+        let response = warp::test::request().path("/healthz").reply(&health).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let response = warp::test::request().path("/readyz").reply(&health).await;
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+        client.set_ready(true);
+        let response = warp::test::request().path("/readyz").reply(&health).await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+}
@@ -0,0 +1,57 @@
+//! Terminates TLS for the built-in server.
+use std::{convert::Infallible, fs::File, io::BufReader, path::Path, sync::Arc};
+
+use futures_util::{future, stream, Stream, StreamExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio_rustls::{
+    rustls::{self, crypto::ring, ServerConfig},
+    server::TlsStream,
+    TlsAcceptor,
+};
+
+use crate::errors::Result;
+
+/// The number of connections that may be completing their TLS handshake at once.
+const MAX_HANDSHAKES: usize = 64;
+
+/// Load a server configuration from PEM-encoded certificate chain and private key files.
+pub(crate) fn load(cert: &Path, key: &Path) -> Result<Arc<ServerConfig>> {
+    let certs = rustls_pemfile::certs(&mut BufReader::new(File::open(cert)?))
+        .collect::<Result<Vec<_>, _>>()?;
+    let key = rustls_pemfile::private_key(&mut BufReader::new(File::open(key)?))?
+        .ok_or_else(|| rustls::Error::General("no private key found".to_string()))?;
+    let config = ServerConfig::builder_with_provider(Arc::new(ring::default_provider()))
+        .with_safe_default_protocol_versions()?
+        .with_no_client_auth()
+        .with_single_cert(certs, key)?;
+    Ok(Arc::new(config))
+}
+
+/// Accept connections from the listener, yielding those that complete their TLS handshake.
+pub(crate) fn incoming(
+    listener: TcpListener,
+    config: Arc<ServerConfig>,
+) -> impl Stream<Item = Result<TlsStream<TcpStream>, Infallible>> + Send {
+    let acceptor = TlsAcceptor::from(config);
+    let connections = stream::unfold(listener, |listener| async move {
+        loop {
+            match listener.accept().await {
+                Ok((stream, _)) => return Some((stream, listener)),
+                Err(err) => tracing::warn!(error = %err, "failed to accept connection"),
+            }
+        }
+    });
+    connections
+        .map(move |stream| acceptor.accept(stream))
+        // Handshakes run concurrently, so that a slow client doesn't hold up the others.
+        .buffer_unordered(MAX_HANDSHAKES)
+        .filter_map(|stream| {
+            future::ready(match stream {
+                Ok(stream) => Some(Ok(stream)),
+                Err(err) => {
+                    tracing::debug!(error = %err, "TLS handshake failed");
+                    None
+                }
+            })
+        })
+}
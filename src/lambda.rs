@@ -0,0 +1,182 @@
+//! Runs a [`Client`] as an AWS Lambda function, receiving interactions through API Gateway or
+//! a Lambda function URL.
+use std::collections::HashMap;
+
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use lambda_runtime::{service_fn, LambdaEvent};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    client::Client,
+    errors::{Error, Result},
+    http::{self as server, SIGNATURE_HEADER, TIMESTAMP_HEADER},
+};
+
+/// An HTTP request, as sent to a Lambda function by API Gateway (REST or HTTP APIs) or a
+/// function URL. Only the fields used to handle interactions are captured.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HttpEvent {
+    /// The request's headers. API Gateway REST APIs keep their original case.
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+    /// The request's body.
+    pub body: Option<String>,
+    /// Whether the body is base64-encoded.
+    #[serde(default)]
+    pub is_base64_encoded: bool,
+}
+
+impl HttpEvent {
+    /// Returns the value of a header, ignoring the case of its name.
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// The response to an [`HttpEvent`].
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HttpEventResponse {
+    /// The HTTP status code.
+    pub status_code: u16,
+    /// The response's headers.
+    pub headers: HashMap<String, String>,
+    /// The response's body.
+    pub body: String,
+    /// Whether the body is base64-encoded, which it never is.
+    pub is_base64_encoded: bool,
+}
+
+/// Verify that an event was sent by Discord, and dispatch the interaction it carries. The
+/// client must have been given its public key.
+///
+/// Only returns once the interaction is handled, since Lambda may freeze the function as soon
+/// as it answers, which would stop any handler left running in the background.
+pub async fn handle(client: &Client, event: HttpEvent) -> Result<HttpEventResponse> {
+    let key = client
+        .public_key()
+        .ok_or(Error::MissingConfig("public key"))?;
+    let body = match event.body {
+        Some(ref body) if event.is_base64_encoded => match STANDARD.decode(body) {
+            Ok(body) => body,
+            Err(_) => return Ok(status(400)),
+        },
+        Some(ref body) => body.clone().into_bytes(),
+        None => Vec::new(),
+    };
+    let response = server::handle(
+        client,
+        key,
+        event.header(SIGNATURE_HEADER),
+        event.header(TIMESTAMP_HEADER),
+        &body,
+        server::Dispatch::Await,
+    )
+    .await;
+    let mut headers = HashMap::new();
+    if response.body.is_some() {
+        headers.insert("content-type".to_string(), "application/json".to_string());
+    }
+    Ok(HttpEventResponse {
+        status_code: response.status,
+        headers,
+        body: String::from_utf8(response.body.unwrap_or_default()).unwrap_or_default(),
+        is_base64_encoded: false,
+    })
+}
+
+/// Create an empty response with the given status.
+fn status(status_code: u16) -> HttpEventResponse {
+    HttpEventResponse {
+        status_code,
+        headers: HashMap::new(),
+        body: String::new(),
+        is_base64_encoded: false,
+    }
+}
+
+/// Run the Lambda runtime, handling every invocation with the client until the function is
+/// shut down.
+///
+/// Commands aren't registered, since that would happen on every cold start. Register them
+/// separately with [`Client::register_commands`].
+///
+/// # Example
+///
+/// ```no_run
+/// use slash::{Client, Config, Context, Filter};
+///
+/// # async fn run() -> slash::Result<()> {
+/// let ping = slash::command("ping")
+///     .description("Test the bot's latency.")
+///     .then(|context: Context| async move { context.reply("pong!").await });
+///
+/// let client = Client::builder()
+///     .config(Config::from_env()?)
+///     .command(ping)
+///     .build()?;
+/// slash::lambda::run(client).await
+/// # }
+/// ```
+pub async fn run(client: Client) -> Result<()> {
+    if client.public_key().is_none() {
+        return Err(Error::MissingConfig("public key"));
+    }
+    let handler = service_fn(|event: LambdaEvent<HttpEvent>| {
+        let client = client.clone();
+        async move {
+            handle(&client, event.payload)
+                .await
+                .map_err(lambda_runtime::Error::from)
+        }
+    });
+    lambda_runtime::run(handler).await.map_err(Error::Lambda)
+}
+
+#[cfg(test)]
+mod tests {
+    use ed25519_dalek::{Signer, SigningKey};
+    use serde_json::json;
+
+    use super::HttpEvent;
+    use crate::Client;
+
+    #[tokio::test]
+    async fn test_handle_function_url_event() {
+        let key = SigningKey::from_bytes(&[7; 32]);
+        let client = Client::builder()
+            .public_key(hex::encode(key.verifying_key().as_bytes()))
+            .build_webhook()
+            .unwrap();
+
+        let body = r#"{"id":"1","application_id":"1234","type":1,"token":"token"}"#;
+        let timestamp = "1700000000";
+        let signature = key.sign(format!("{}{}", timestamp, body).as_bytes());
+        let event: HttpEvent = serde_json::from_value(json!({
+            "version": "2.0",
+            "rawPath": "/",
+            "headers": {
+                "X-Signature-Ed25519": hex::encode(signature.to_bytes()),
+                "X-Signature-Timestamp": timestamp,
+            },
+            "body": body,
+            "isBase64Encoded": false
+        }))
+        .unwrap();
+
+        let response = super::handle(&client, event.clone()).await.unwrap();
+        assert_eq!(response.status_code, 200);
+        assert_eq!(response.body, r#"{"type":1}"#);
+
+        let unsigned = HttpEvent {
+            headers: Default::default(),
+            ..event
+        };
+        let response = super::handle(&client, unsigned).await.unwrap();
+        assert_eq!(response.status_code, 401);
+    }
+}
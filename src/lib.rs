@@ -1,26 +1,91 @@
+// Without a transport nothing receives interactions, so the dispatcher has no caller.
+#![cfg_attr(not(any(feature = "verify", feature = "gateway")), allow(dead_code))]
+
+// Lets the derive macros refer to `::slash` from within the crate's own tests.
+#[cfg(all(test, feature = "macros"))]
+extern crate self as slash;
+
 mod filter;
 
 mod filters;
+pub use filters::concurrency::{limit_concurrency, Scope};
+#[cfg(not(target_arch = "wasm32"))]
+pub use filters::cooldown::cooldown;
 pub use filters::*;
 
-mod reject;
+pub mod reject;
 
 pub(crate) mod generic;
 
 pub mod command;
-pub use command::command;
+pub use command::{command, message_command, user_command};
+
+mod errors;
+pub use errors::{DiscordApiError, Error, GatewayError, Result};
 
 pub mod context;
 pub use context::Context;
 
+pub mod options;
+#[cfg(feature = "macros")]
+pub use slash_macros::CommandOptions;
+
+mod collector;
+pub use collector::ComponentCollector;
+
+mod paginator;
+pub use paginator::Paginator;
+
+mod reply;
+pub use reply::{MessageBuilder, MessageError, ModalBuilder, ModalError};
+
+pub mod client;
+pub use client::{Client, ClientBuilder, ComponentPriority};
+
+mod router;
+pub use router::Router;
+
+mod config;
+pub use config::Config;
+
+mod shutdown;
+pub use shutdown::ShutdownHandle;
+
+mod dispatch;
+
 pub mod model;
 
-pub use crate::filter::Filter;
-pub use crate::filters::*;
+#[cfg(not(target_arch = "wasm32"))]
+pub use crate::filter::TimedOut;
+pub use crate::filter::{wrap_fn, BoxedFilter, Check, Filter, FilterMeta};
 
 pub mod rest;
 
+#[cfg(feature = "axum")]
+pub mod axum;
+#[cfg(feature = "gateway")]
+pub mod cache;
 #[cfg(feature = "gateway")]
-pub(crate) mod gateway;
-#[cfg(feature = "http")]
+pub mod gateway;
+#[cfg(feature = "verify")]
 pub(crate) mod http;
+#[cfg(feature = "verify")]
+pub use http::HttpResponse;
+
+#[cfg(feature = "lambda")]
+pub mod lambda;
+mod runtime;
+
+mod backoff;
+pub use backoff::Backoff;
+
+/// The rustls crate, whose `ServerConfig` can be given to the built-in server.
+#[cfg(feature = "tls")]
+pub use tokio_rustls::rustls;
+#[cfg(feature = "tower")]
+pub mod service;
+#[cfg(feature = "warp")]
+pub mod warp;
+
+#[cfg(test)]
+mod test_util;
@@ -0,0 +1,246 @@
+use std::collections::HashMap;
+
+use bitflags::bitflags;
+use serde::{Deserialize, Serialize};
+
+use super::{permissions::Permissions, snowflake::Snowflake, user::User};
+
+bitflags! {
+    /// Flags on an application.
+    ///
+    /// Bits unknown to this version of Slash are retained when deserializing.
+    #[derive(Serialize, Deserialize)]
+    #[serde(transparent)]
+    pub struct ApplicationFlags: u32 {
+        /// Indicates if an app uses the Auto Moderation API.
+        const APPLICATION_AUTO_MODERATION_RULE_CREATE_BADGE = 1 << 6;
+        /// Intent required for bots in 100 or more servers to receive presence updates.
+        const GATEWAY_PRESENCE = 1 << 12;
+        /// Intent required for bots in under 100 servers to receive presence updates.
+        const GATEWAY_PRESENCE_LIMITED = 1 << 13;
+        /// Intent required for bots in 100 or more servers to receive member-related events.
+        const GATEWAY_GUILD_MEMBERS = 1 << 14;
+        /// Intent required for bots in under 100 servers to receive member-related events.
+        const GATEWAY_GUILD_MEMBERS_LIMITED = 1 << 15;
+        /// Indicates unusual growth of an app that prevents verification.
+        const VERIFICATION_PENDING_GUILD_LIMIT = 1 << 16;
+        /// Indicates if an app is embedded within the Discord client.
+        const EMBEDDED = 1 << 17;
+        /// Intent required for bots in 100 or more servers to receive message content.
+        const GATEWAY_MESSAGE_CONTENT = 1 << 18;
+        /// Intent required for bots in under 100 servers to receive message content.
+        const GATEWAY_MESSAGE_CONTENT_LIMITED = 1 << 19;
+        /// Indicates if an app has registered global application commands.
+        const APPLICATION_COMMAND_BADGE = 1 << 23;
+    }
+}
+
+/// An application registered with Discord, as returned by
+/// [`Client::get_current_application`](crate::rest::Client::get_current_application).
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Application {
+    /// ID of the app.
+    pub id: Snowflake,
+    /// Name of the app.
+    pub name: String,
+    /// Icon hash of the app.
+    pub icon: Option<String>,
+    /// Description of the app.
+    pub description: String,
+    /// When `false`, only the app owner can add the app to guilds.
+    #[serde(default)]
+    pub bot_public: bool,
+    /// When `true`, the app's bot will only join upon completion of the full OAuth2 code grant
+    /// flow.
+    #[serde(default)]
+    pub bot_require_code_grant: bool,
+    /// Partial user object for the owner of the app.
+    #[serde(default)]
+    pub owner: Option<User>,
+    /// The team owning the app, if it belongs to one.
+    #[serde(default)]
+    pub team: Option<Team>,
+    /// Hex encoded key for verification in interactions.
+    pub verify_key: String,
+    /// Guild associated with the app, such as its support server.
+    #[serde(default)]
+    pub guild_id: Option<Snowflake>,
+    /// The app's public flags.
+    #[serde(default)]
+    pub flags: Option<ApplicationFlags>,
+    /// Approximate count of guilds the app has been added to.
+    #[serde(default)]
+    pub approximate_guild_count: Option<u64>,
+    /// Interactions endpoint URL for the app, if it receives interactions over HTTP.
+    #[serde(default)]
+    pub interactions_endpoint_url: Option<String>,
+    /// Role connection verification URL for the app.
+    #[serde(default)]
+    pub role_connections_verification_url: Option<String>,
+    /// Settings for the app's default in-app authorization link, if enabled.
+    #[serde(default)]
+    pub install_params: Option<InstallParams>,
+    /// Default custom authorization URL for the app, if enabled.
+    #[serde(default)]
+    pub custom_install_url: Option<String>,
+}
+
+impl Application {
+    /// Returns whether a user owns the app, either directly or as a member of the team owning
+    /// it.
+    pub fn is_owner<S: Into<Snowflake>>(&self, user_id: S) -> bool {
+        let user_id = user_id.into();
+        match &self.team {
+            Some(team) => team.members.iter().any(|member| member.user.id == user_id),
+            None => self.owner.as_ref().map(|owner| owner.id) == Some(user_id),
+        }
+    }
+}
+
+/// The settings of an application's default in-app authorization link.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct InstallParams {
+    /// OAuth2 scopes to add the application to the server with.
+    pub scopes: Vec<String>,
+    /// Permissions to request for the bot role.
+    pub permissions: Permissions,
+}
+
+/// A group of developers owning applications together.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Team {
+    /// Unique ID of the team.
+    pub id: Snowflake,
+    /// Hash of the image of the team's icon.
+    pub icon: Option<String>,
+    /// Name of the team.
+    pub name: String,
+    /// Members of the team.
+    pub members: Vec<TeamMember>,
+    /// User ID of the current team owner.
+    pub owner_user_id: Snowflake,
+}
+
+int_enum! {
+    pub enum MembershipState {
+        /// The user has been invited to the team.
+        Invited = 1,
+        /// The user has accepted the invitation.
+        Accepted = 2,
+    }
+}
+
+/// A developer in a [`Team`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TeamMember {
+    /// User's membership state on the team.
+    pub membership_state: MembershipState,
+    /// ID of the parent team of which they are a member.
+    pub team_id: Snowflake,
+    /// Partial user of the team member.
+    pub user: User,
+    /// Role of the team member: `admin`, `developer` or `read_only`.
+    pub role: String,
+}
+
+int_enum! {
+    pub enum RoleConnectionMetadataType {
+        /// The metadata value (`integer`) is less than or equal to the guild's configured value
+        /// (`integer`).
+        IntegerLessThanOrEqual = 1,
+        /// The metadata value (`integer`) is greater than or equal to the guild's configured
+        /// value (`integer`).
+        IntegerGreaterThanOrEqual = 2,
+        /// The metadata value (`integer`) is equal to the guild's configured value (`integer`).
+        IntegerEqual = 3,
+        /// The metadata value (`integer`) is not equal to the guild's configured value
+        /// (`integer`).
+        IntegerNotEqual = 4,
+        /// The metadata value (`ISO8601 string`) is less than or equal to the guild's
+        /// configured value (`integer`; days before current date).
+        DatetimeLessThanOrEqual = 5,
+        /// The metadata value (`ISO8601 string`) is greater than or equal to the guild's
+        /// configured value (`integer`; days before current date).
+        DatetimeGreaterThanOrEqual = 6,
+        /// The metadata value (`integer`) is equal to the guild's configured value (`integer`;
+        /// 1).
+        BooleanEqual = 7,
+        /// The metadata value (`integer`) is not equal to the guild's configured value
+        /// (`integer`; 1).
+        BooleanNotEqual = 8,
+    }
+}
+
+/// A requirement of the linked roles of an application, which guilds configure a value to
+/// compare the metadata of each user's role connection with.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RoleConnectionMetadata {
+    /// Type of metadata value.
+    #[serde(rename = "type")]
+    pub ty: RoleConnectionMetadataType,
+    /// Dictionary key for the metadata field (must be `a-z`, `0-9`, or `_` characters; 1-50
+    /// characters).
+    pub key: String,
+    /// Name of the metadata field (1-100 characters).
+    pub name: String,
+    /// Translations of the name, keyed by locale.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name_localizations: Option<HashMap<String, String>>,
+    /// Description of the metadata field (1-200 characters).
+    pub description: String,
+    /// Translations of the description, keyed by locale.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description_localizations: Option<HashMap<String, String>>,
+}
+
+impl RoleConnectionMetadata {
+    /// Create a metadata field of the given type, without translations.
+    pub fn new<K, N, D>(ty: RoleConnectionMetadataType, key: K, name: N, description: D) -> Self
+    where
+        K: Into<String>,
+        N: Into<String>,
+        D: Into<String>,
+    {
+        Self {
+            ty,
+            key: key.into(),
+            name: name.into(),
+            name_localizations: None,
+            description: description.into(),
+            description_localizations: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Application;
+
+    #[test]
+    fn test_is_owner() {
+        let user = |id: &str| serde_json::json!({"id": id, "username": "dev", "discriminator": "0", "avatar": null});
+        let mut application: Application = serde_json::from_value(serde_json::json!({
+            "id": "1",
+            "name": "bot",
+            "icon": null,
+            "description": "",
+            "verify_key": "abc",
+            "owner": user("2"),
+            "flags": 1 << 23,
+        }))
+        .unwrap();
+        assert!(application.is_owner(2));
+        assert!(!application.is_owner(3));
+
+        application.team = serde_json::from_value(serde_json::json!({
+            "id": "4",
+            "icon": null,
+            "name": "team",
+            "owner_user_id": "3",
+            "members": [{"membership_state": 2, "team_id": "4", "user": user("3"), "role": "admin"}],
+        }))
+        .unwrap();
+        assert!(application.is_owner(3));
+        assert!(!application.is_owner(2));
+    }
+}
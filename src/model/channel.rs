@@ -0,0 +1,207 @@
+use serde::{Deserialize, Serialize};
+
+use super::{permissions::Permissions, snowflake::Snowflake, user::User};
+
+int_enum! {
+    pub enum ChannelType {
+        /// A text channel within a guild.
+        GuildText = 0,
+        /// A direct message between users.
+        Dm = 1,
+        /// A voice channel within a guild.
+        GuildVoice = 2,
+        /// A direct message between multiple users.
+        GroupDm = 3,
+        /// An organizational category containing up to 50 channels.
+        GuildCategory = 4,
+        /// A channel that users can follow and crosspost into their own guild.
+        GuildAnnouncement = 5,
+        /// A temporary sub-channel within an announcement channel.
+        AnnouncementThread = 10,
+        /// A temporary sub-channel within a text or forum channel.
+        PublicThread = 11,
+        /// A temporary sub-channel within a text channel, only viewable by those invited.
+        PrivateThread = 12,
+        /// A voice channel for hosting events with an audience.
+        GuildStageVoice = 13,
+        /// The channel in a hub containing the listed guilds.
+        GuildDirectory = 14,
+        /// A channel that can only contain threads.
+        GuildForum = 15,
+        /// A channel that can only contain threads, displayed as a grid.
+        GuildMedia = 16,
+    }
+}
+
+impl ChannelType {
+    /// Returns whether the channel is a thread.
+    pub fn is_thread(self) -> bool {
+        matches!(
+            self,
+            ChannelType::AnnouncementThread
+                | ChannelType::PublicThread
+                | ChannelType::PrivateThread
+        )
+    }
+}
+
+/// How long a thread stays active without new messages before Discord archives it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum AutoArchiveDuration {
+    /// One hour.
+    Hour,
+    /// One day.
+    Day,
+    /// Three days.
+    ThreeDays,
+    /// One week.
+    Week,
+    /// A number of minutes unknown to this version of Slash.
+    Unknown(u16),
+}
+
+impl From<u16> for AutoArchiveDuration {
+    fn from(minutes: u16) -> Self {
+        match minutes {
+            60 => AutoArchiveDuration::Hour,
+            1440 => AutoArchiveDuration::Day,
+            4320 => AutoArchiveDuration::ThreeDays,
+            10080 => AutoArchiveDuration::Week,
+            other => AutoArchiveDuration::Unknown(other),
+        }
+    }
+}
+
+impl From<AutoArchiveDuration> for u16 {
+    fn from(duration: AutoArchiveDuration) -> Self {
+        match duration {
+            AutoArchiveDuration::Hour => 60,
+            AutoArchiveDuration::Day => 1440,
+            AutoArchiveDuration::ThreeDays => 4320,
+            AutoArchiveDuration::Week => 10080,
+            AutoArchiveDuration::Unknown(other) => other,
+        }
+    }
+}
+
+impl Serialize for AutoArchiveDuration {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u16((*self).into())
+    }
+}
+
+impl<'de> Deserialize<'de> for AutoArchiveDuration {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        u16::deserialize(deserializer).map(AutoArchiveDuration::from)
+    }
+}
+
+int_enum! {
+    pub enum OverwriteType {
+        /// The overwrite applies to the members of a role.
+        Role = 0,
+        /// The overwrite applies to a single member.
+        Member = 1,
+    }
+}
+
+/// Permissions allowed or denied in a channel to a role or member, on top of those they have
+/// in the guild.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PermissionOverwrite {
+    /// ID of the role or user.
+    pub id: Snowflake,
+    /// Whether the overwrite applies to a role or a member.
+    #[serde(rename = "type")]
+    pub ty: OverwriteType,
+    /// The permissions allowed.
+    pub allow: Permissions,
+    /// The permissions denied.
+    pub deny: Permissions,
+}
+
+/// The state of a thread, which other channels don't have.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThreadMetadata {
+    /// Whether the thread is archived.
+    pub archived: bool,
+    /// How long the thread stays active without new messages before it is archived.
+    pub auto_archive_duration: AutoArchiveDuration,
+    /// When the thread's archive status was last changed, as an ISO8601 timestamp.
+    pub archive_timestamp: String,
+    /// Whether the thread is locked, so only members with the `MANAGE_THREADS` permission can
+    /// unarchive it.
+    #[serde(default)]
+    pub locked: bool,
+    /// Whether non-moderators can add other non-moderators to a private thread.
+    pub invitable: Option<bool>,
+}
+
+/// A guild channel, thread or direct message within Discord.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Channel {
+    /// ID of the channel.
+    pub id: Snowflake,
+    /// Type of channel.
+    #[serde(rename = "type")]
+    pub ty: ChannelType,
+    /// ID of the guild the channel is in, if any.
+    pub guild_id: Option<Snowflake>,
+    /// Sorting position of the channel.
+    pub position: Option<i32>,
+    /// Name of the channel, 1-100 characters.
+    pub name: Option<String>,
+    /// Topic of the channel.
+    pub topic: Option<String>,
+    /// Whether the channel is age-restricted.
+    #[serde(default)]
+    pub nsfw: bool,
+    /// ID of the last message sent in the channel, which may not point to an existing message.
+    pub last_message_id: Option<Snowflake>,
+    /// The recipients of a direct message.
+    #[serde(default)]
+    pub recipients: Vec<User>,
+    /// ID of the parent category of a channel, or the channel a thread was created in.
+    pub parent_id: Option<Snowflake>,
+    /// Permissions allowed or denied to roles and members in the channel. Threads use those
+    /// of their parent channel.
+    #[serde(default)]
+    pub permission_overwrites: Vec<PermissionOverwrite>,
+    /// ID of the creator of a thread.
+    pub owner_id: Option<Snowflake>,
+    /// Approximate number of messages in a thread, not counting its first message.
+    pub message_count: Option<u32>,
+    /// Approximate number of users in a thread, stopping at 50.
+    pub member_count: Option<u32>,
+    /// The state of a thread.
+    pub thread_metadata: Option<ThreadMetadata>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AutoArchiveDuration, Channel, ChannelType};
+
+    #[test]
+    fn test_thread() {
+        let channel: Channel = serde_json::from_value(serde_json::json!({
+            "id": "1",
+            "type": 11,
+            "parent_id": "2",
+            "thread_metadata": {
+                "archived": false,
+                "auto_archive_duration": 1440,
+                "archive_timestamp": "2024-01-01T00:00:00+00:00",
+            },
+        }))
+        .unwrap();
+        assert!(channel.ty.is_thread());
+        assert!(!ChannelType::GuildForum.is_thread());
+        let metadata = channel.thread_metadata.unwrap();
+        assert_eq!(metadata.auto_archive_duration, AutoArchiveDuration::Day);
+        assert_eq!(
+            serde_json::to_string(&AutoArchiveDuration::from(30)).unwrap(),
+            "30"
+        );
+    }
+}
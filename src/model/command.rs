@@ -1,8 +1,8 @@
 use serde::{Deserialize, Serialize};
 
-use super::snowflake::Snowflake;
+use super::{channel::ChannelType, snowflake::Snowflake};
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 #[serde(untagged)]
 pub enum ChoiceValue {
     String(String),
@@ -10,37 +10,30 @@ pub enum ChoiceValue {
     Double(f64),
 }
 
-#[derive(Clone, Serialize, Deserialize, Debug)]
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
 pub struct CommandOptionChoice {
-    name: String,
-    value: ChoiceValue,
+    /// 1-100 character choice name.
+    pub name: String,
+    /// Value for the choice, up to 100 characters if string.
+    pub value: ChoiceValue,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
-pub enum CommandOptionType {
-    #[serde(rename = "SUB_COMMAND")]
-    SubCommand = 1,
-    #[serde(rename = "SUB_COMMAND_GROUP")]
-    SubCommandGroup,
-    #[serde(rename = "STRING")]
-    String,
-    #[serde(rename = "INTEGER")]
-    Integer,
-    #[serde(rename = "BOOLEAN")]
-    Boolean,
-    #[serde(rename = "USER")]
-    User,
-    #[serde(rename = "CHANNEL")]
-    Channel,
-    #[serde(rename = "ROLE")]
-    Role,
-    #[serde(rename = "MENTIONABLE")]
-    Mentionable,
-    #[serde(rename = "NUMBER")]
-    Number,
+int_enum! {
+    pub enum CommandOptionType {
+        SubCommand = 1,
+        SubCommandGroup = 2,
+        String = 3,
+        Integer = 4,
+        Boolean = 5,
+        User = 6,
+        Channel = 7,
+        Role = 8,
+        Mentionable = 9,
+        Number = 10,
+    }
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 pub struct ApplicationCommandOption {
     /// The type of option
     #[serde(rename = "type")]
@@ -53,48 +46,125 @@ pub struct ApplicationCommandOption {
     #[serde(default)]
     pub required: bool,
     /// Choices for `STRING`, `INTEGER`, and `NUMBER` types for the user to pick from, max 25.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub choices: Vec<CommandOptionChoice>,
     /// If the option is a subcommand or subcommand group type, these nested options will be the parameters.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub options: Vec<ApplicationCommandOption>,
+    /// For `CHANNEL` options, the types of channels shown to pick from, or any if empty.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub channel_types: Vec<ChannelType>,
+    /// For `INTEGER` and `NUMBER` options, the minimum value permitted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_value: Option<ChoiceValue>,
+    /// For `INTEGER` and `NUMBER` options, the maximum value permitted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_value: Option<ChoiceValue>,
+}
+
+impl ApplicationCommandOption {
+    /// Create a new option of the given type.
+    pub fn new<S: AsRef<str>>(ty: CommandOptionType, name: S, description: S) -> Self {
+        ApplicationCommandOption {
+            ty,
+            name: name.as_ref().to_string(),
+            description: description.as_ref().to_string(),
+            required: false,
+            choices: vec![],
+            options: vec![],
+            channel_types: vec![],
+            min_value: None,
+            max_value: None,
+        }
+    }
+}
+
+int_enum! {
+    #[derive(Default)]
+    pub enum ApplicationCommandType {
+        /// Slash commands; a text-based command that shows up when a user types `/`.
+        #[default]
+        ChatInput = 1,
+        /// A UI-based command that shows up when you right click or tap on a user.
+        User = 2,
+        /// A UI-based command that shows up when you right click or tap on a message
+        Message = 3,
+    }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
-pub enum ApplicationCommandType {
-    /// Slash commands; a text-based command that shows up when a user types `/`.
-    #[serde(rename = "CHAT_INPUT")]
-    ChatInput = 1,
-    /// A UI-based command that shows up when you right click or tap on a user.
-    #[serde(rename = "USER")]
-    User,
-    /// A UI-based command that shows up when you right click or tap on a message
-    #[serde(rename = "MESSAGE")]
-    Message,
+int_enum! {
+    /// Where an application can be installed, also called its supported installation contexts.
+    pub enum IntegrationType {
+        /// App is installable to servers.
+        GuildInstall = 0,
+        /// App is installable to users.
+        UserInstall = 1,
+    }
 }
 
-impl Default for ApplicationCommandType {
-    fn default() -> Self {
-        ApplicationCommandType::ChatInput
+int_enum! {
+    /// Where an interaction can be used, or where it was triggered from.
+    pub enum InteractionContextType {
+        /// Interaction can be used within servers.
+        Guild = 0,
+        /// Interaction can be used within DMs with the app's bot user.
+        BotDm = 1,
+        /// Interaction can be used within Group DMs and DMs other than the app's bot user.
+        PrivateChannel = 2,
     }
 }
 
 /// Application commands are commands that an application can register to Discord.
 /// They provide users a first-class way of interacting directly with your application
 /// that feels deeply integrated into Discord.
-#[derive(Serialize, Deserialize, Debug)]
+///
+/// The IDs are assigned by Discord, and are left empty when registering a new command.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
 pub struct ApplicationCommand {
     /// Unique id of the command.
-    pub id: Snowflake,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<Snowflake>,
     /// The type of command, defaults 1 if not set.
     #[serde(rename = "type", default)]
     pub ty: ApplicationCommandType,
     /// Unique id of the parent application
-    pub application_id: Snowflake,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub application_id: Option<Snowflake>,
     /// Guild id of the command, if not global
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub guild_id: Option<Snowflake>,
     /// 1-32 character name
     pub name: String,
     /// 1-100 character description for `CHAT_INPUT` commands, empty string for `USER` and `MESSAGE` commands.
     pub description: String,
     /// The parameters for the command, max 25, valid only for `CHAT_INPUT` commands.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub options: Vec<ApplicationCommandOption>,
+    /// Installation contexts where the command is available, only for globally-scoped commands.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub integration_types: Option<Vec<IntegrationType>>,
+    /// Interaction contexts where the command can be used, only for globally-scoped commands.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub contexts: Option<Vec<InteractionContextType>>,
+    /// Autoincrementing version identifier updated during substantial record changes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<Snowflake>,
+}
+
+impl ApplicationCommand {
+    /// Create a new, unregistered command of the given type.
+    pub fn new<S: AsRef<str>>(ty: ApplicationCommandType, name: S) -> Self {
+        ApplicationCommand {
+            id: None,
+            ty,
+            application_id: None,
+            guild_id: None,
+            name: name.as_ref().to_string(),
+            description: "".to_string(),
+            options: vec![],
+            integration_types: None,
+            contexts: None,
+            version: None,
+        }
+    }
 }
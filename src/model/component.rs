@@ -0,0 +1,184 @@
+use serde::{Deserialize, Serialize};
+
+int_enum! {
+    pub enum ComponentType {
+        /// A container for other components.
+        ActionRow = 1,
+        /// A clickable button.
+        Button = 2,
+        /// A select menu for picking from predefined text options.
+        StringSelect = 3,
+        /// A text input, only used in modals.
+        TextInput = 4,
+        /// A select menu for users.
+        UserSelect = 5,
+        /// A select menu for roles.
+        RoleSelect = 6,
+        /// A select menu for users and roles.
+        MentionableSelect = 7,
+        /// A select menu for channels.
+        ChannelSelect = 8,
+    }
+}
+
+int_enum! {
+    pub enum ButtonStyle {
+        /// A blurple button.
+        Primary = 1,
+        /// A grey button.
+        Secondary = 2,
+        /// A green button.
+        Success = 3,
+        /// A red button.
+        Danger = 4,
+        /// A grey button navigating to a URL.
+        Link = 5,
+        /// A button prompting the user to purchase a SKU.
+        Premium = 6,
+    }
+}
+
+/// An interactive element attached to a message, such as a button or select menu.
+///
+/// Messages hold up to 5 action rows at the top level, each containing up to 5 buttons or a
+/// single select menu.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Component {
+    /// Type of component.
+    #[serde(rename = "type")]
+    pub ty: ComponentType,
+    /// Developer-defined identifier sent back when the component is used, up to 100
+    /// characters.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub custom_id: Option<String>,
+    /// Style of a button.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub style: Option<ButtonStyle>,
+    /// Text shown on a button, up to 80 characters.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// URL a link button navigates to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Whether the component is disabled.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub disabled: bool,
+    /// Options of a string select menu, max 25.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub options: Vec<SelectOption>,
+    /// Placeholder shown by a select menu when nothing is selected.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub placeholder: Option<String>,
+    /// Minimum number of items to select in a select menu.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_values: Option<u8>,
+    /// Maximum number of items to select in a select menu.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_values: Option<u8>,
+    /// Components contained in an action row.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub components: Vec<Component>,
+}
+
+impl Component {
+    /// Create a component of the given type, with every other field unset.
+    pub fn new(ty: ComponentType) -> Self {
+        Component {
+            ty,
+            custom_id: None,
+            style: None,
+            label: None,
+            url: None,
+            disabled: false,
+            options: vec![],
+            placeholder: None,
+            min_values: None,
+            max_values: None,
+            components: vec![],
+        }
+    }
+
+    /// Create an action row containing the given components.
+    pub fn action_row(components: Vec<Component>) -> Self {
+        Component {
+            components,
+            ..Self::new(ComponentType::ActionRow)
+        }
+    }
+
+    /// Create a button sending the given custom ID when clicked.
+    pub fn button<S: Into<String>>(style: ButtonStyle, custom_id: S, label: S) -> Self {
+        Component {
+            style: Some(style),
+            custom_id: Some(custom_id.into()),
+            label: Some(label.into()),
+            ..Self::new(ComponentType::Button)
+        }
+    }
+
+    /// Create a button navigating to the given URL when clicked.
+    pub fn link_button<S: Into<String>>(url: S, label: S) -> Self {
+        Component {
+            style: Some(ButtonStyle::Link),
+            url: Some(url.into()),
+            label: Some(label.into()),
+            ..Self::new(ComponentType::Button)
+        }
+    }
+
+    /// Disable this component and every component it contains, leaving link buttons enabled
+    /// since they don't send interactions.
+    pub fn disable(&mut self) {
+        if self.ty != ComponentType::ActionRow && self.style != Some(ButtonStyle::Link) {
+            self.disabled = true;
+        }
+        for component in &mut self.components {
+            component.disable();
+        }
+    }
+}
+
+/// An option of a string select menu.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SelectOption {
+    /// Text shown to the user, up to 100 characters.
+    pub label: String,
+    /// Value sent back when the option is selected, up to 100 characters.
+    pub value: String,
+    /// Additional description of the option, up to 100 characters.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Whether the option is selected by default.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub default: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ButtonStyle, Component};
+
+    #[test]
+    fn test_action_row_serialize() {
+        let row = Component::action_row(vec![Component::button(
+            ButtonStyle::Primary,
+            "next",
+            "Next",
+        )]);
+        assert_eq!(
+            serde_json::to_string(&row).unwrap(),
+            r#"{"type":1,"components":[{"type":2,"custom_id":"next","style":1,"label":"Next"}]}"#
+        );
+    }
+
+    #[test]
+    fn test_disable_skips_link_buttons() {
+        let mut row = Component::action_row(vec![
+            Component::button(ButtonStyle::Primary, "next", "Next"),
+            Component::link_button("https://discord.com", "Docs"),
+        ]);
+        row.disable();
+        assert!(!row.disabled);
+        assert!(row.components[0].disabled);
+        assert!(!row.components[1].disabled);
+    }
+}
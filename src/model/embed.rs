@@ -0,0 +1,84 @@
+use serde::{Deserialize, Serialize};
+
+/// Rich content attached to a message.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Embed {
+    /// Title of the embed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// Description of the embed, up to 4096 characters.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// URL the title links to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// ISO 8601 timestamp shown in the footer.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<String>,
+    /// Color of the embed's left border, as an RGB integer.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<u32>,
+    /// Footer of the embed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub footer: Option<EmbedFooter>,
+    /// Large image shown below the description.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image: Option<EmbedMedia>,
+    /// Small image shown next to the description.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thumbnail: Option<EmbedMedia>,
+    /// Author shown above the title.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<EmbedAuthor>,
+    /// Fields of the embed, max 25.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<EmbedField>,
+}
+
+/// The footer of an [`Embed`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmbedFooter {
+    /// Text of the footer, up to 2048 characters.
+    pub text: String,
+    /// URL of the footer icon.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon_url: Option<String>,
+}
+
+/// An image or thumbnail in an [`Embed`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmbedMedia {
+    /// Source URL of the image, or `attachment://<filename>` for an uploaded file.
+    pub url: String,
+    /// Height of the image, set by Discord.
+    #[serde(skip_serializing)]
+    pub height: Option<u32>,
+    /// Width of the image, set by Discord.
+    #[serde(skip_serializing)]
+    pub width: Option<u32>,
+}
+
+/// The author of an [`Embed`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmbedAuthor {
+    /// Name of the author, up to 256 characters.
+    pub name: String,
+    /// URL the author's name links to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// URL of the author's icon.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon_url: Option<String>,
+}
+
+/// A field of an [`Embed`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmbedField {
+    /// Name of the field, up to 256 characters.
+    pub name: String,
+    /// Value of the field, up to 1024 characters.
+    pub value: String,
+    /// Whether the field is shown side by side with other inline fields.
+    #[serde(default)]
+    pub inline: bool,
+}
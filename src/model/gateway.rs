@@ -0,0 +1,307 @@
+use std::time::Duration;
+
+use bitflags::bitflags;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::{snowflake::Snowflake, user::User};
+
+bitflags! {
+    /// The gateway intents requested when identifying, controlling which events are received.
+    ///
+    /// Interactions are always received, so bots only handling commands need no intents.
+    #[derive(Default, Serialize, Deserialize)]
+    #[serde(transparent)]
+    pub struct GatewayIntents: u64 {
+        const GUILDS = 1 << 0;
+        /// Privileged, and must be enabled in the developer portal.
+        const GUILD_MEMBERS = 1 << 1;
+        const GUILD_MODERATION = 1 << 2;
+        const GUILD_EXPRESSIONS = 1 << 3;
+        const GUILD_INTEGRATIONS = 1 << 4;
+        const GUILD_WEBHOOKS = 1 << 5;
+        const GUILD_INVITES = 1 << 6;
+        const GUILD_VOICE_STATES = 1 << 7;
+        /// Privileged, and must be enabled in the developer portal.
+        const GUILD_PRESENCES = 1 << 8;
+        const GUILD_MESSAGES = 1 << 9;
+        const GUILD_MESSAGE_REACTIONS = 1 << 10;
+        const GUILD_MESSAGE_TYPING = 1 << 11;
+        const DIRECT_MESSAGES = 1 << 12;
+        const DIRECT_MESSAGE_REACTIONS = 1 << 13;
+        const DIRECT_MESSAGE_TYPING = 1 << 14;
+        /// Privileged, and must be enabled in the developer portal.
+        const MESSAGE_CONTENT = 1 << 15;
+        const GUILD_SCHEDULED_EVENTS = 1 << 16;
+        const AUTO_MODERATION_CONFIGURATION = 1 << 20;
+        const AUTO_MODERATION_EXECUTION = 1 << 21;
+        const GUILD_MESSAGE_POLLS = 1 << 24;
+        const DIRECT_MESSAGE_POLLS = 1 << 25;
+    }
+}
+
+int_enum! {
+    pub enum Opcode {
+        /// An event was dispatched.
+        Dispatch = 0,
+        /// Fired periodically by the client to keep the connection alive.
+        Heartbeat = 1,
+        /// Starts a new session during the initial handshake.
+        Identify = 2,
+        /// Update the client's presence.
+        PresenceUpdate = 3,
+        /// Used to join/leave or move between voice channels.
+        VoiceStateUpdate = 4,
+        /// Resume a previous session that was disconnected.
+        Resume = 6,
+        /// The client should reconnect and resume immediately.
+        Reconnect = 7,
+        /// Request information about offline guild members in a large guild.
+        RequestGuildMembers = 8,
+        /// The session has been invalidated.
+        InvalidSession = 9,
+        /// Sent immediately after connecting, contains the `heartbeat_interval` to use.
+        Hello = 10,
+        /// Sent in response to receiving a heartbeat to acknowledge that it has been received.
+        HeartbeatAck = 11,
+    }
+}
+
+/// A payload sent or received over the gateway.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GatewayPayload {
+    /// The opcode of the payload.
+    pub op: Opcode,
+    /// The event data.
+    #[serde(default)]
+    pub d: serde_json::Value,
+    /// The sequence number of a dispatched event, used for resuming sessions and heartbeating.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub s: Option<u64>,
+    /// The name of a dispatched event.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub t: Option<String>,
+}
+
+/// The data of a `Hello` payload.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Hello {
+    /// Interval (in milliseconds) the client should heartbeat with.
+    pub heartbeat_interval: u64,
+}
+
+/// Connection properties sent when identifying.
+#[derive(Debug, Clone, Serialize)]
+pub struct IdentifyProperties {
+    /// The operating system.
+    pub os: String,
+    /// The library name.
+    pub browser: String,
+    /// The library name.
+    pub device: String,
+}
+
+impl Default for IdentifyProperties {
+    fn default() -> Self {
+        IdentifyProperties {
+            os: std::env::consts::OS.to_string(),
+            browser: "slash".to_string(),
+            device: "slash".to_string(),
+        }
+    }
+}
+
+/// The data of an `Identify` payload, starting a new session.
+#[derive(Debug, Clone, Serialize)]
+pub struct Identify {
+    /// The authentication token.
+    pub token: String,
+    /// Connection properties.
+    pub properties: IdentifyProperties,
+    /// Value between 50 and 250, total number of members where the gateway will stop sending
+    /// offline members in the guild member list.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub large_threshold: Option<u8>,
+    /// The shard ID and total number of shards, used for guild sharding.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shard: Option<[u32; 2]>,
+    /// The presence the bot starts with.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub presence: Option<UpdatePresence>,
+    /// The gateway intents to receive events for.
+    pub intents: GatewayIntents,
+}
+
+/// An event dispatched by the gateway.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum GatewayEvent {
+    /// The session was identified.
+    Ready(Box<Ready>),
+    /// An event without a model, with its name, such as `GUILD_CREATE`, and its raw data.
+    Raw { name: String, data: Value },
+}
+
+/// The gateway URL and recommended sharding of a bot, fetched with
+/// [`Client::get_gateway_bot`](crate::rest::Client::get_gateway_bot).
+#[derive(Debug, Clone, Deserialize)]
+pub struct GatewayBot {
+    /// The WSS URL that can be used for connecting to the gateway.
+    pub url: String,
+    /// The recommended number of shards to use when connecting.
+    pub shards: u32,
+    /// Information on the current session start limit.
+    pub session_start_limit: SessionStartLimit,
+}
+
+/// The number of sessions a bot may start, which is reset daily.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SessionStartLimit {
+    /// Total number of session starts the current user is allowed.
+    pub total: u32,
+    /// Remaining number of session starts the current user is allowed.
+    pub remaining: u32,
+    /// Number of milliseconds after which the limit resets.
+    pub reset_after: u64,
+    /// Number of identify requests allowed per 5 seconds.
+    pub max_concurrency: u32,
+}
+
+impl SessionStartLimit {
+    /// Returns how long until the limit resets.
+    pub fn reset_after(&self) -> Duration {
+        Duration::from_millis(self.reset_after)
+    }
+}
+
+/// The data of a `READY` event, dispatched once a new session is identified.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Ready {
+    /// The gateway API version.
+    #[serde(rename = "v")]
+    pub version: u8,
+    /// The bot's user.
+    pub user: User,
+    /// The guilds the bot is in, which are sent in later `GUILD_CREATE` events.
+    pub guilds: Vec<UnavailableGuild>,
+    /// The ID of the session, used for resuming.
+    pub session_id: String,
+    /// The URL to use when resuming the session.
+    pub resume_gateway_url: String,
+    /// The shard ID and total number of shards of the session.
+    pub shard: Option<[u32; 2]>,
+    /// The bot's application.
+    pub application: PartialApplication,
+}
+
+/// A guild that is either offline, or not yet sent by the gateway.
+#[derive(Debug, Clone, Deserialize)]
+pub struct UnavailableGuild {
+    pub id: Snowflake,
+    /// Whether the guild is offline due to an outage.
+    #[serde(default)]
+    pub unavailable: bool,
+}
+
+/// The application a `READY` event was sent to.
+#[derive(Debug, Clone, Deserialize)]
+pub struct PartialApplication {
+    pub id: Snowflake,
+    /// The application's public flags.
+    pub flags: u64,
+}
+
+/// The data of a `Resume` payload, replaying events missed since a disconnect.
+#[derive(Debug, Clone, Serialize)]
+pub struct Resume {
+    /// The authentication token.
+    pub token: String,
+    /// The ID of the session to resume.
+    pub session_id: String,
+    /// The sequence number of the last event received.
+    pub seq: Option<u64>,
+}
+
+/// The status of a user.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+    Online,
+    /// Do not disturb.
+    Dnd,
+    Idle,
+    /// Shown as offline.
+    Invisible,
+    Offline,
+}
+
+int_enum! {
+    pub enum ActivityType {
+        /// Playing {name}
+        Playing = 0,
+        /// Streaming {details}
+        Streaming = 1,
+        /// Listening to {name}
+        Listening = 2,
+        /// Watching {name}
+        Watching = 3,
+        /// {emoji} {state}
+        Custom = 4,
+        /// Competing in {name}
+        Competing = 5,
+    }
+}
+
+/// An activity shown in a bot's presence.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Activity {
+    /// The activity's name.
+    pub name: String,
+    /// The activity type.
+    #[serde(rename = "type")]
+    pub ty: ActivityType,
+    /// Stream URL, validated when the type is `Streaming`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// The user's current party status, or text used for a custom status.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+}
+
+impl Activity {
+    /// Create a new activity of the given type.
+    pub fn new<S: AsRef<str>>(ty: ActivityType, name: S) -> Self {
+        Activity {
+            name: name.as_ref().to_string(),
+            ty,
+            url: None,
+            state: None,
+        }
+    }
+}
+
+/// The data of a `PresenceUpdate` payload, also sent when identifying.
+#[derive(Debug, Clone, Serialize)]
+pub struct UpdatePresence {
+    /// Unix time (in milliseconds) of when the client went idle, or null if the client is not
+    /// idle.
+    pub since: Option<u64>,
+    /// The user's activities.
+    pub activities: Vec<Activity>,
+    /// The user's new status.
+    pub status: Status,
+    /// Whether or not the client is afk.
+    pub afk: bool,
+}
+
+impl UpdatePresence {
+    /// Create a presence with the given status and, optionally, an activity.
+    pub fn new(status: Status, activity: Option<Activity>) -> Self {
+        UpdatePresence {
+            since: None,
+            activities: activity.into_iter().collect(),
+            status,
+            afk: false,
+        }
+    }
+}
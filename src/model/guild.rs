@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+
+use super::{
+    channel::Channel,
+    member::Member,
+    permissions::{self, Permissions},
+    role::Role,
+    snowflake::Snowflake,
+};
+
+/// A guild, or server, within Discord.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Guild {
+    /// ID of the guild.
+    pub id: Snowflake,
+    /// Name of the guild, 2-100 characters.
+    pub name: String,
+    /// The guild's icon hash.
+    pub icon: Option<String>,
+    /// ID of the guild's owner.
+    pub owner_id: Snowflake,
+    /// Roles in the guild.
+    #[serde(default)]
+    pub roles: Vec<Role>,
+    /// The preferred locale of a community guild, defaulting to `en-US`.
+    pub preferred_locale: Option<String>,
+}
+
+impl Guild {
+    /// Returns the permissions of a member in a channel of the guild, as computed by
+    /// [`permissions::compute`], with every permission for the guild's owner. Threads should
+    /// be given their parent channel.
+    pub fn permissions_in(&self, member: &Member, channel: &Channel) -> Permissions {
+        if member.user_id() == Some(self.owner_id) {
+            return Permissions::all();
+        }
+        permissions::compute(self.id, member, &self.roles, &channel.permission_overwrites)
+    }
+}
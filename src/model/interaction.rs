@@ -1,40 +1,519 @@
 use std::collections::HashMap;
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-use super::{command::ApplicationCommandType, member::Member, snowflake::Snowflake, user::User};
+use super::{
+    channel::Channel,
+    command::{ApplicationCommandType, CommandOptionType, IntegrationType, InteractionContextType},
+    component::{Component, ComponentType},
+    embed::Embed,
+    member::{Member, PartialMember},
+    message::{
+        AllowedMentions, CreateMessage, FileUpload, Message, MessageFlags, PartialAttachment,
+    },
+    modal::{self, SubmittedInput},
+    monetization::Entitlement,
+    role::Role,
+    snowflake::Snowflake,
+    user::User,
+};
 
-#[derive(Debug, Clone, Deserialize, PartialEq)]
-pub(crate) enum InteractionType {
-    #[serde(rename = "PING")]
-    Ping = 1,
-    #[serde(rename = "APPLICATION_COMMAND")]
-    ApplicationCommand,
-    #[serde(rename = "MESSAGE_COMPONENT")]
-    MessageComponent,
-    #[serde(rename = "APPLICATION_COMMAND_AUTOCOMPLETE")]
-    ApplicationCommandAutocomplete,
+int_enum! {
+    /// The kind of an interaction.
+    pub enum InteractionType {
+        /// Sent by Discord to check that the bot is up.
+        Ping = 1,
+        /// A slash or context-menu command.
+        ApplicationCommand = 2,
+        /// A click on a button, or a selection in a select menu.
+        MessageComponent = 3,
+        /// A request for autocomplete suggestions while typing a command option.
+        ApplicationCommandAutocomplete = 4,
+        /// The submission of a modal.
+        ModalSubmit = 5,
+    }
 }
 
-#[derive(Debug, Clone, Deserialize)]
-struct ResolvedData {
+/// Users, members, roles, channels and messages referenced by an interaction, keyed by their
+/// IDs.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ResolvedData {
+    /// The referenced users.
+    #[serde(default)]
     pub users: HashMap<Snowflake, User>,
-    pub members: HashMap<Snowflake, Member>,
+    /// The guild members of the referenced users, whose users are in `users`.
+    #[serde(default)]
+    pub members: HashMap<Snowflake, PartialMember>,
+    /// The referenced roles.
+    #[serde(default)]
+    pub roles: HashMap<Snowflake, Role>,
+    /// The referenced channels, with only their ID, name, type and parent.
+    #[serde(default)]
+    pub channels: HashMap<Snowflake, Channel>,
+    /// The referenced messages.
+    #[serde(default)]
+    pub messages: HashMap<Snowflake, Message>,
+}
+
+/// The value given to an option of an application command.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(untagged)]
+pub enum OptionValue {
+    /// The value of a `STRING` option, or the ID of a user, channel, role or mentionable.
+    String(String),
+    /// The value of an `INTEGER` option, or a whole `NUMBER`.
+    Integer(i64),
+    /// The value of a `NUMBER` option.
+    Number(f64),
+    /// The value of a `BOOLEAN` option.
+    Boolean(bool),
+}
+
+/// An option given to an application command, or a subcommand and its options.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CommandDataOption {
+    /// Name of the option.
+    pub name: String,
+    /// Type of the option.
+    #[serde(rename = "type")]
+    pub ty: CommandOptionType,
+    /// The value of the option, absent for subcommands and groups.
+    pub value: Option<OptionValue>,
+    /// The options of a subcommand, or the subcommand of a group.
+    #[serde(default)]
+    pub options: Vec<CommandDataOption>,
+    /// Whether the user is typing this option, in autocomplete interactions.
+    #[serde(default)]
+    pub focused: bool,
+}
+
+/// The data of an application command interaction.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ApplicationCommandData {
+    /// ID of the invoked command.
+    pub id: Snowflake,
+    /// Name of the invoked command.
+    pub name: String,
+    /// Type of the invoked command.
+    #[serde(rename = "type")]
+    pub ty: ApplicationCommandType,
+    /// The options given to the command, or the subcommand that was used.
+    #[serde(default)]
+    pub options: Vec<CommandDataOption>,
+    /// The users, members, roles, channels and messages referenced by the command.
+    #[serde(default)]
+    pub resolved: ResolvedData,
+    /// ID of the guild the command is registered to, if it isn't global.
+    pub guild_id: Option<Snowflake>,
+    /// The user or message the command was run on, for `USER` and `MESSAGE` commands.
+    pub target_id: Option<Snowflake>,
+}
+
+impl ApplicationCommandData {
+    /// Returns the options given to the subcommand that was used, or to the command itself if
+    /// it has no subcommands.
+    pub fn leaf_options(&self) -> &[CommandDataOption] {
+        let mut options = self.options.as_slice();
+        while let [option] = options {
+            match option.ty {
+                CommandOptionType::SubCommand | CommandOptionType::SubCommandGroup => {
+                    options = &option.options;
+                }
+                _ => break,
+            }
+        }
+        options
+    }
+
+    /// Returns the option of the given name, among those of the subcommand that was used.
+    pub fn option(&self, name: &str) -> Option<&CommandDataOption> {
+        self.leaf_options()
+            .iter()
+            .find(|option| option.name == name)
+    }
 }
 
+/// The data of a message component interaction.
+#[derive(Debug, Clone, Deserialize)]
+pub struct MessageComponentData {
+    /// The custom ID of the component.
+    pub custom_id: String,
+    /// Type of the component.
+    pub component_type: ComponentType,
+    /// The values selected in a select menu.
+    #[serde(default)]
+    pub values: Vec<String>,
+}
+
+/// The data of a modal submit interaction.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ModalSubmitData {
+    /// The custom ID of the modal.
+    pub custom_id: String,
+    /// The values of the modal's text inputs.
+    #[serde(rename = "components", deserialize_with = "modal::deserialize_rows")]
+    pub inputs: Vec<SubmittedInput>,
+}
+
+impl ModalSubmitData {
+    /// Returns the value entered in the text input of the given custom ID.
+    pub fn value(&self, custom_id: &str) -> Option<&str> {
+        self.inputs
+            .iter()
+            .find(|input| input.custom_id == custom_id)
+            .map(|input| input.value.as_str())
+    }
+}
+
+/// The data payload of an interaction, depending on its type.
 #[derive(Debug, Clone, Deserialize)]
 #[serde(untagged)]
-pub(crate) enum InteractionData {
-    ApplicationCommand {
-        id: Snowflake,
-        name: String,
-        ty: ApplicationCommandType,
-    },
+pub enum InteractionData {
+    /// The data of an application command.
+    ApplicationCommand(Box<ApplicationCommandData>),
+    /// The data of a message component.
+    MessageComponent(MessageComponentData),
+    /// The data of a submitted modal.
+    ModalSubmit(ModalSubmitData),
+    /// Data for an interaction type this version of Slash doesn't model.
+    Other(serde_json::Value),
 }
 
+/// An interaction received from Discord, as sent over HTTP or the gateway.
 #[derive(Debug, Clone, Deserialize)]
-pub(crate) struct Interaction {
+pub struct Interaction {
+    /// ID of the interaction.
     pub id: Snowflake,
+    /// ID of the application the interaction is for.
     pub application_id: Snowflake,
+    /// Type of the interaction.
+    #[serde(rename = "type")]
     pub ty: InteractionType,
+    /// The interaction's data payload, absent for `PING` interactions.
+    pub data: Option<InteractionData>,
+    /// Continuation token for responding to the interaction.
+    pub token: String,
+    /// The guild the interaction was invoked in.
+    pub guild_id: Option<Snowflake>,
+    /// The channel the interaction was invoked in.
+    pub channel_id: Option<Snowflake>,
+    /// For component interactions, the message the component is attached to.
+    pub message: Option<Message>,
+    /// The member who invoked the interaction, when invoked in a guild.
+    pub member: Option<Member>,
+    /// The user who invoked the interaction, when invoked in a direct message.
+    pub user: Option<User>,
+    /// For monetized apps, any entitlements for the invoking user, representing access to premium
+    /// SKUs.
+    #[serde(default)]
+    pub entitlements: Vec<Entitlement>,
+    /// The selected language of the invoking user, absent for `PING` interactions.
+    pub locale: Option<String>,
+    /// The preferred locale of the guild the interaction was invoked in, if any.
+    pub guild_locale: Option<String>,
+    /// Context where the interaction was triggered from.
+    pub context: Option<InteractionContextType>,
+    /// Mapping of installation contexts that the interaction was authorized for to related user
+    /// or guild IDs.
+    #[serde(default)]
+    pub authorizing_integration_owners: HashMap<IntegrationType, Snowflake>,
+}
+
+impl Interaction {
+    /// Returns the command data of this interaction, if it is an application command.
+    pub fn command_data(&self) -> Option<&ApplicationCommandData> {
+        match self.data {
+            Some(InteractionData::ApplicationCommand(ref data)) => Some(&**data),
+            _ => None,
+        }
+    }
+
+    /// Returns the user who invoked the interaction, whether in a guild or a direct message.
+    pub fn user(&self) -> Option<&User> {
+        match self.member {
+            Some(Member {
+                user: Some(ref user),
+                ..
+            }) => Some(user),
+            _ => self.user.as_ref(),
+        }
+    }
+
+    /// Returns the ID of the user who invoked the interaction.
+    pub fn user_id(&self) -> Option<Snowflake> {
+        Some(self.user()?.id)
+    }
+
+    /// Returns the component data of this interaction, if it is a message component.
+    pub fn component_data(&self) -> Option<&MessageComponentData> {
+        match self.data {
+            Some(InteractionData::MessageComponent(ref data)) => Some(data),
+            _ => None,
+        }
+    }
+
+    /// Returns the data of the submitted modal, if this is a modal submit interaction.
+    pub fn modal_data(&self) -> Option<&ModalSubmitData> {
+        match self.data {
+            Some(InteractionData::ModalSubmit(ref data)) => Some(data),
+            _ => None,
+        }
+    }
+}
+
+int_enum! {
+    pub enum InteractionCallbackType {
+        /// ACK a `Ping`.
+        Pong = 1,
+        /// Respond to an interaction with a message.
+        ChannelMessageWithSource = 4,
+        /// ACK an interaction and edit a response later, the user sees a loading state.
+        DeferredChannelMessageWithSource = 5,
+        /// For components, ACK an interaction and edit the original message later.
+        DeferredUpdateMessage = 6,
+        /// For components, edit the message the component was attached to.
+        UpdateMessage = 7,
+        /// Respond to an autocomplete interaction with suggested choices.
+        ApplicationCommandAutocompleteResult = 8,
+        /// Respond to an interaction with a popup modal.
+        Modal = 9,
+        /// Respond to an interaction with an upgrade button, only available for apps with
+        /// monetization enabled.
+        PremiumRequired = 10,
+    }
+}
+
+/// Data attached to an interaction response.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct InteractionCallbackData {
+    /// The message contents, up to 2000 characters.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    /// Rich content to attach, max 10.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub embeds: Option<Vec<Embed>>,
+    /// Interactive components to attach.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub components: Option<Vec<Component>>,
+    /// Attachments to include.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attachments: Option<Vec<PartialAttachment>>,
+    /// Files to upload, sent alongside the response rather than in it.
+    #[serde(skip)]
+    pub files: Vec<FileUpload>,
+    /// Which mentions notify their targets, or Discord's default of all of them.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allowed_mentions: Option<AllowedMentions>,
+    /// Whether the message is read aloud with text-to-speech.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub tts: bool,
+    /// Flags of the message, such as `EPHEMERAL` or `SUPPRESS_EMBEDS`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub flags: Option<MessageFlags>,
+}
+
+impl From<CreateMessage> for InteractionCallbackData {
+    fn from(message: CreateMessage) -> Self {
+        Self {
+            content: message.content,
+            embeds: Some(message.embeds).filter(|embeds| !embeds.is_empty()),
+            components: Some(message.components).filter(|components| !components.is_empty()),
+            attachments: Some(message.attachments).filter(|attachments| !attachments.is_empty()),
+            files: message.files,
+            allowed_mentions: message.allowed_mentions,
+            tts: message.tts,
+            flags: message.flags,
+        }
+    }
+}
+
+/// The response sent back to Discord when an interaction is received.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InteractionResponse {
+    /// The type of response.
+    #[serde(rename = "type")]
+    pub ty: InteractionCallbackType,
+    /// An optional response message.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<InteractionCallbackData>,
+}
+
+impl InteractionResponse {
+    /// The response acknowledging a `PING`, sent by Discord to check that the bot is up.
+    pub(crate) fn pong() -> Self {
+        Self {
+            ty: InteractionCallbackType::Pong,
+            data: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{InteractionCallbackData, InteractionResponse, InteractionType};
+    use crate::{
+        model::{
+            command::{ApplicationCommandType, InteractionContextType},
+            message::{CreateMessage, MessageFlags},
+        },
+        test_util,
+    };
+
+    #[test]
+    fn test_message_command_deserialize() {
+        let interaction = test_util::interaction(json!({
+            "version": 1,
+            "data": {
+                "id": "940565712559304736",
+                "name": "Quote",
+                "type": 3,
+                "target_id": "940565712559304737",
+                "resolved": {
+                    "messages": {
+                        "940565712559304737": {
+                            "id": "940565712559304737",
+                            "channel_id": "940565712559304738",
+                            "author": {
+                                "id": "940565712559304739",
+                                "username": "slash",
+                                "discriminator": "0001",
+                                "avatar": null,
+                                "public_flags": 0
+                            },
+                            "content": "hello",
+                            "timestamp": "2022-02-08T11:12:20.740000+00:00",
+                            "edited_timestamp": null,
+                            "type": 0
+                        }
+                    }
+                }
+            }
+        }));
+        assert_eq!(interaction.ty, InteractionType::ApplicationCommand);
+
+        let data = interaction.command_data().unwrap();
+        assert_eq!(data.ty, ApplicationCommandType::Message);
+        let message = &data.resolved.messages[&data.target_id.unwrap()];
+        assert_eq!(message.content, "hello");
+    }
+
+    #[test]
+    fn test_unknown_values_deserialize() {
+        let interaction = test_util::interaction(json!({
+            "type": 42,
+            "context": 7,
+            "data": { "something": "new" }
+        }));
+        assert_eq!(interaction.ty, InteractionType::Unknown(42));
+        assert_eq!(
+            interaction.context,
+            Some(InteractionContextType::Unknown(7))
+        );
+        assert!(interaction.command_data().is_none());
+    }
+
+    #[test]
+    fn test_invoking_user() {
+        let user = json!({
+            "id": "940565712559304739",
+            "username": "slash",
+            "discriminator": "0001",
+            "avatar": null
+        });
+        let dm = json!({
+            "channel_id": "940565712559304738",
+            "user": user
+        });
+        let guild = json!({
+            "guild_id": "940565712559304737",
+            "channel_id": "940565712559304738",
+            "member": {
+                "user": user,
+                "roles": [],
+                "joined_at": "2022-02-08T11:12:20.740000+00:00",
+                "deaf": false,
+                "mute": false
+            }
+        });
+        for fields in [dm, guild] {
+            let interaction = test_util::interaction(fields);
+            assert_eq!(interaction.user().unwrap().username, "slash");
+            assert_eq!(interaction.channel_id, Some(940565712559304738.into()));
+        }
+    }
+
+    #[test]
+    fn test_resolved_partial_member() {
+        let interaction = test_util::interaction(json!({
+            "data": {
+                "id": "940565712559304736",
+                "name": "Warn",
+                "type": 2,
+                "target_id": "940565712559304739",
+                "resolved": {
+                    "users": {
+                        "940565712559304739": { "id": "940565712559304739", "username": "slash", "discriminator": "0" }
+                    },
+                    "members": {
+                        "940565712559304739": { "roles": [], "joined_at": null, "permissions": "1024" }
+                    }
+                }
+            }
+        }));
+        let resolved = &interaction.command_data().unwrap().resolved;
+        let id = 940565712559304739.into();
+        let member = resolved.members[&id].clone();
+        assert_eq!(member.joined_at, None);
+        let member = member.into_member(resolved.users[&id].clone());
+        assert_eq!(member.user_id(), Some(id));
+    }
+
+    #[test]
+    fn test_modal_submit_deserialize() {
+        let interaction = test_util::interaction(json!({
+            "type": 5,
+            "data": {
+                "custom_id": "report",
+                "components": [
+                    {
+                        "type": 1,
+                        "components": [{ "type": 4, "custom_id": "reason", "value": "spam" }]
+                    },
+                    {
+                        "type": 1,
+                        "components": [{ "type": 4, "custom_id": "details", "value": "" }]
+                    }
+                ]
+            }
+        }));
+        assert_eq!(interaction.ty, InteractionType::ModalSubmit);
+        let data = interaction.modal_data().unwrap();
+        assert_eq!(data.custom_id, "report");
+        assert_eq!(data.value("reason"), Some("spam"));
+        assert_eq!(data.value("details"), Some(""));
+        assert_eq!(data.value("missing"), None);
+        assert!(interaction.component_data().is_none());
+    }
+
+    #[test]
+    fn test_pong_serialize() {
+        let pong = serde_json::to_string(&InteractionResponse::pong()).unwrap();
+        assert_eq!(pong, r#"{"type":1}"#);
+    }
+
+    #[test]
+    fn test_reply_flags_serialize() {
+        let data = InteractionCallbackData::from(CreateMessage {
+            content: Some("https://discord.com".to_string()),
+            tts: true,
+            flags: Some(MessageFlags::SUPPRESS_EMBEDS),
+            ..Default::default()
+        });
+        assert_eq!(
+            serde_json::to_string(&data).unwrap(),
+            r#"{"content":"https://discord.com","tts":true,"flags":4}"#
+        );
+    }
 }
@@ -1,31 +1,196 @@
-use serde::Deserialize;
+use std::time::Duration;
 
-use super::{snowflake::Snowflake, user::User};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Deserialize)]
+use super::{permissions::Permissions, snowflake::Snowflake, user::User};
+
+/// A member of a guild.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Member {
-    /// The user this guild member represents.
-    pub user: User,
+    /// The user this guild member represents. Missing from the members sent with messages,
+    /// whose author is sent separately.
+    #[serde(default)]
+    pub user: Option<User>,
     #[serde(rename = "nick")]
-    /// This user's guild nickname.
-    pub nickname: String,
+    /// This user's guild nickname, if they have one.
+    pub nickname: Option<String>,
     /// The member's guild avatar hash.
     pub avatar: Option<String>,
     /// Array of role object ids.
     pub roles: Vec<Snowflake>,
-    /// When the user joined the guild.
-    pub joined_at: String,
-    /// When the user started boosting the guild
-    pub premium_since: String,
+    /// When the user joined the guild, or `None` for guests of a voice channel.
+    pub joined_at: Option<String>,
+    /// When the user started boosting the guild, if they are boosting it.
+    pub premium_since: Option<String>,
     /// Whether the user is deafened in voice channels.
+    #[serde(default)]
     pub deaf: bool,
     /// Whether the user is muted in voice channels.
+    #[serde(default)]
     pub mute: bool,
     /// Whether the user has not yet passed the guild's Membership Screening requirements.
     pub pending: Option<bool>,
-    /// Total permissions of the member in the channel, including overwrites, returned when in the interaction object
-    pub permissions: String,
+    /// Total permissions of the member in the channel, including overwrites, only returned
+    /// in the interaction object.
+    pub permissions: Option<Permissions>,
     /// When the user's timeout will expire and the user will be able to communicate in the guild again,
     /// null or a time in the past if the user is not timed out
-    pub communication_disabled_until: Option<String>,
+    pub communication_disabled_until: Option<DateTime<Utc>>,
+}
+
+impl Member {
+    /// Returns the ID of the member's user, unless it is missing.
+    pub fn user_id(&self) -> Option<Snowflake> {
+        Some(self.user.as_ref()?.id)
+    }
+
+    /// Returns whether the member is timed out, unable to communicate in the guild.
+    pub fn is_timed_out(&self) -> bool {
+        self.timeout_remaining().is_some()
+    }
+
+    /// Returns how long until the member's timeout expires, or `None` if they aren't timed
+    /// out.
+    pub fn timeout_remaining(&self) -> Option<Duration> {
+        timeout_remaining(self.communication_disabled_until)
+    }
+}
+
+/// A guild member referenced by an interaction, whose user is in the resolved users, and which
+/// lacks the `deaf` and `mute` fields.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PartialMember {
+    /// This user's guild nickname, if they have one.
+    #[serde(rename = "nick")]
+    pub nickname: Option<String>,
+    /// The member's guild avatar hash.
+    pub avatar: Option<String>,
+    /// Array of role object ids.
+    pub roles: Vec<Snowflake>,
+    /// When the user joined the guild, or `None` for guests of a voice channel.
+    pub joined_at: Option<String>,
+    /// When the user started boosting the guild, if they are boosting it.
+    pub premium_since: Option<String>,
+    /// Whether the user has not yet passed the guild's Membership Screening requirements.
+    pub pending: Option<bool>,
+    /// Total permissions of the member in the channel of the interaction, including
+    /// overwrites.
+    pub permissions: Option<Permissions>,
+    /// When the user's timeout will expire, null or a time in the past if the user is not timed
+    /// out.
+    pub communication_disabled_until: Option<DateTime<Utc>>,
+}
+
+impl PartialMember {
+    /// Returns whether the member is timed out, unable to communicate in the guild.
+    pub fn is_timed_out(&self) -> bool {
+        self.timeout_remaining().is_some()
+    }
+
+    /// Returns how long until the member's timeout expires, or `None` if they aren't timed
+    /// out.
+    pub fn timeout_remaining(&self) -> Option<Duration> {
+        timeout_remaining(self.communication_disabled_until)
+    }
+
+    /// Returns the member with the given user, such as the resolved user of the same ID.
+    pub fn into_member(self, user: User) -> Member {
+        Member {
+            user: Some(user),
+            nickname: self.nickname,
+            avatar: self.avatar,
+            roles: self.roles,
+            joined_at: self.joined_at,
+            premium_since: self.premium_since,
+            deaf: false,
+            mute: false,
+            pending: self.pending,
+            permissions: self.permissions,
+            communication_disabled_until: self.communication_disabled_until,
+        }
+    }
+}
+
+/// Returns how long until a timeout ending at the given time expires, if it hasn't yet.
+fn timeout_remaining(until: Option<DateTime<Utc>>) -> Option<Duration> {
+    (until? - Utc::now())
+        .to_std()
+        .ok()
+        .filter(|remaining| !remaining.is_zero())
+}
+
+/// Changes to make to a guild member. Fields left as `None` are unchanged, while fields set
+/// to `Some(None)` are cleared.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ModifyMember {
+    /// The member's guild nickname.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nick: Option<Option<String>>,
+    /// The IDs of every role the member should have.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub roles: Option<Vec<Snowflake>>,
+    /// Whether the member is muted in voice channels.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mute: Option<bool>,
+    /// Whether the member is deafened in voice channels.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deaf: Option<bool>,
+    /// The voice channel to move the member to, or `Some(None)` to disconnect them.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub channel_id: Option<Option<Snowflake>>,
+    /// When to time the member out until, up to 28 days in the future, or `Some(None)` to
+    /// lift their timeout.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub communication_disabled_until: Option<Option<DateTime<Utc>>>,
+}
+
+/// Options for banning a user from a guild.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CreateBan {
+    /// How many seconds of the user's most recent messages to delete, up to 7 days.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delete_message_seconds: Option<u32>,
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::{Member, ModifyMember};
+
+    #[test]
+    fn test_modify_member_serialize() {
+        let changes = ModifyMember {
+            nick: Some(None),
+            communication_disabled_until: Some(Some(
+                Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap(),
+            )),
+            ..Default::default()
+        };
+        assert_eq!(
+            serde_json::to_string(&changes).unwrap(),
+            r#"{"nick":null,"communication_disabled_until":"2024-01-01T00:00:00Z"}"#
+        );
+    }
+
+    #[test]
+    fn test_timeout() {
+        let member = |until: Option<String>| -> Member {
+            serde_json::from_value(serde_json::json!({
+                "roles": [],
+                "joined_at": "2024-01-01T00:00:00+00:00",
+                "communication_disabled_until": until,
+            }))
+            .unwrap()
+        };
+        let in_an_hour = (Utc::now() + chrono::Duration::hours(1)).to_rfc3339();
+        let timed_out = member(Some(in_an_hour));
+        assert!(timed_out.is_timed_out());
+        let remaining = timed_out.timeout_remaining().unwrap();
+        assert!(remaining.as_secs() > 3500 && remaining.as_secs() <= 3600);
+
+        assert!(!member(Some("2024-01-01T00:00:00+00:00".to_string())).is_timed_out());
+        assert!(!member(None).is_timed_out());
+    }
 }
@@ -0,0 +1,401 @@
+use std::fmt;
+
+use bitflags::bitflags;
+use serde::{Deserialize, Serialize};
+
+use super::{component::Component, embed::Embed, snowflake::Snowflake, user::User};
+
+int_enum! {
+    pub enum MessageType {
+        Default = 0,
+        RecipientAdd = 1,
+        RecipientRemove = 2,
+        Call = 3,
+        ChannelNameChange = 4,
+        ChannelIconChange = 5,
+        ChannelPinnedMessage = 6,
+        UserJoin = 7,
+        GuildBoost = 8,
+        GuildBoostTier1 = 9,
+        GuildBoostTier2 = 10,
+        GuildBoostTier3 = 11,
+        ChannelFollowAdd = 12,
+        GuildDiscoveryDisqualified = 14,
+        GuildDiscoveryRequalified = 15,
+        GuildDiscoveryGracePeriodInitialWarning = 16,
+        GuildDiscoveryGracePeriodFinalWarning = 17,
+        ThreadCreated = 18,
+        Reply = 19,
+        ChatInputCommand = 20,
+        ThreadStarterMessage = 21,
+        GuildInviteReminder = 22,
+        ContextMenuCommand = 23,
+        AutoModerationAction = 24,
+        RoleSubscriptionPurchase = 25,
+        InteractionPremiumUpsell = 26,
+        StageStart = 27,
+        StageEnd = 28,
+        StageSpeaker = 29,
+        StageTopic = 31,
+        GuildApplicationPremiumSubscription = 32,
+    }
+}
+
+bitflags! {
+    /// Flags on a message.
+    ///
+    /// Bits unknown to this version of Slash are retained when deserializing.
+    #[derive(Default, Serialize, Deserialize)]
+    #[serde(transparent)]
+    pub struct MessageFlags: u32 {
+        /// The message has been published to following channels.
+        const CROSSPOSTED = 1 << 0;
+        /// The message originated from a followed announcement channel.
+        const IS_CROSSPOST = 1 << 1;
+        /// Links in the message aren't previewed with embeds.
+        const SUPPRESS_EMBEDS = 1 << 2;
+        /// The source message of this crosspost has been deleted.
+        const SOURCE_MESSAGE_DELETED = 1 << 3;
+        /// The message came from the urgent message system.
+        const URGENT = 1 << 4;
+        /// The message has an associated thread, with the same ID as the message.
+        const HAS_THREAD = 1 << 5;
+        /// The message is only visible to the user who invoked the interaction.
+        const EPHEMERAL = 1 << 6;
+        /// The message is an interaction response, and the bot is "thinking".
+        const LOADING = 1 << 7;
+        /// The message failed to mention some roles and add their members to the thread.
+        const FAILED_TO_MENTION_SOME_ROLES_IN_THREAD = 1 << 8;
+        /// The message doesn't trigger push and desktop notifications.
+        const SUPPRESS_NOTIFICATIONS = 1 << 12;
+        /// The message is a voice message.
+        const IS_VOICE_MESSAGE = 1 << 13;
+    }
+}
+
+/// A message sent in a channel within Discord.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Message {
+    /// ID of the message.
+    pub id: Snowflake,
+    /// ID of the channel the message was sent in.
+    pub channel_id: Snowflake,
+    /// ID of the guild the message was sent in, if any.
+    pub guild_id: Option<Snowflake>,
+    /// The author of this message.
+    pub author: User,
+    /// Contents of the message.
+    pub content: String,
+    /// When this message was sent.
+    pub timestamp: String,
+    /// When this message was edited, or null if never.
+    pub edited_timestamp: Option<String>,
+    /// Whether this was a TTS message.
+    #[serde(default)]
+    pub tts: bool,
+    /// Whether this message mentions everyone.
+    #[serde(default)]
+    pub mention_everyone: bool,
+    /// Users specifically mentioned in the message.
+    #[serde(default)]
+    pub mentions: Vec<User>,
+    /// Roles specifically mentioned in this message.
+    #[serde(default)]
+    pub mention_roles: Vec<Snowflake>,
+    /// Whether this message is pinned.
+    #[serde(default)]
+    pub pinned: bool,
+    /// If the message is generated by a webhook, this is the webhook's ID.
+    pub webhook_id: Option<Snowflake>,
+    /// Type of message.
+    #[serde(rename = "type")]
+    pub ty: MessageType,
+    /// Rich content attached to the message.
+    #[serde(default)]
+    pub embeds: Vec<Embed>,
+    /// Interactive components attached to the message.
+    #[serde(default)]
+    pub components: Vec<Component>,
+    /// Files attached to the message.
+    #[serde(default)]
+    pub attachments: Vec<Attachment>,
+    /// Flags on the message.
+    #[serde(default)]
+    pub flags: MessageFlags,
+    /// The message this one replies to, crossposts or forwards.
+    pub message_reference: Option<MessageReference>,
+    /// The message this one replies to. Missing if Discord didn't include it, and `null` if
+    /// it was deleted.
+    pub referenced_message: Option<Box<Message>>,
+}
+
+int_enum! {
+    pub enum MessageReferenceType {
+        /// A standard reference used by replies.
+        Default = 0,
+        /// A reference used to point to a message at a point in time.
+        Forward = 1,
+    }
+}
+
+/// A reference from a message to another, such as the message it replies to.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MessageReference {
+    /// Type of reference, defaulting to a reply.
+    #[serde(rename = "type", default = "default_reference_type")]
+    pub ty: MessageReferenceType,
+    /// ID of the originating message.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message_id: Option<Snowflake>,
+    /// ID of the originating message's channel.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel_id: Option<Snowflake>,
+    /// ID of the originating message's guild.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub guild_id: Option<Snowflake>,
+}
+
+fn default_reference_type() -> MessageReferenceType {
+    MessageReferenceType::Default
+}
+
+/// A file attached to a message.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Attachment {
+    /// ID of the attachment.
+    pub id: Snowflake,
+    /// Name of the attached file.
+    pub filename: String,
+    /// Description of the file, used as alt text.
+    pub description: Option<String>,
+    /// The file's media type.
+    pub content_type: Option<String>,
+    /// Size of the file in bytes.
+    pub size: u64,
+    /// Source URL of the file.
+    pub url: String,
+    /// Proxied URL of the file.
+    pub proxy_url: String,
+    /// Height of an image.
+    pub height: Option<u32>,
+    /// Width of an image.
+    pub width: Option<u32>,
+}
+
+/// A reference to an attachment when sending or editing a message, such as an existing
+/// attachment to keep.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PartialAttachment {
+    /// ID of the attachment.
+    pub id: Snowflake,
+    /// Name of the attached file.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filename: Option<String>,
+    /// Description of the file, used as alt text.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+impl From<&Attachment> for PartialAttachment {
+    fn from(attachment: &Attachment) -> Self {
+        PartialAttachment {
+            id: attachment.id,
+            filename: None,
+            description: None,
+        }
+    }
+}
+
+/// A file to upload with a message. The message's attachments must reference it by its index
+/// among the message's files, which [`MessageBuilder::file`](crate::MessageBuilder::file)
+/// does.
+#[derive(Clone, PartialEq, Eq)]
+pub struct FileUpload {
+    /// Name of the file, which embeds can refer to with `attachment://filename`.
+    pub filename: String,
+    /// Contents of the file.
+    pub data: Vec<u8>,
+}
+
+impl fmt::Debug for FileUpload {
+    // Leaves out the contents, which can be large.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FileUpload")
+            .field("filename", &self.filename)
+            .field("size", &self.data.len())
+            .finish()
+    }
+}
+
+/// A kind of mention that may notify its target.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MentionType {
+    /// Role mentions.
+    Roles,
+    /// User mentions.
+    Users,
+    /// `@everyone` and `@here` mentions.
+    Everyone,
+}
+
+/// Which mentions in a message notify their targets. Defaults to none.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AllowedMentions {
+    /// Kinds of mentions that notify every target of that kind.
+    pub parse: Vec<MentionType>,
+    /// Roles that may be notified, max 100.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub roles: Vec<Snowflake>,
+    /// Users that may be notified, max 100.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub users: Vec<Snowflake>,
+    /// Whether the author of the message being replied to is notified.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub replied_user: bool,
+}
+
+/// A message to send in a channel.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CreateMessage {
+    /// The message contents, up to 2000 characters.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    /// Rich content to attach, max 10.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub embeds: Vec<Embed>,
+    /// Interactive components to attach.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub components: Vec<Component>,
+    /// Attachments to include.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub attachments: Vec<PartialAttachment>,
+    /// Files to upload, sent alongside the message rather than in it.
+    #[serde(skip)]
+    pub files: Vec<FileUpload>,
+    /// Which mentions notify their targets, or Discord's default of all of them.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allowed_mentions: Option<AllowedMentions>,
+    /// Whether the message is read aloud with text-to-speech.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub tts: bool,
+    /// Flags of the message. Only `SUPPRESS_EMBEDS` and `SUPPRESS_NOTIFICATIONS` can be set,
+    /// along with `EPHEMERAL` for interaction responses.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub flags: Option<MessageFlags>,
+}
+
+/// Changes to make to a message. Fields left as `None` are unchanged.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct EditMessage {
+    /// The message contents, up to 2000 characters.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    /// Rich content replacing the message's embeds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub embeds: Option<Vec<Embed>>,
+    /// Components replacing the message's components.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub components: Option<Vec<Component>>,
+    /// Attachments to keep, removing any others, and those of the uploaded files.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attachments: Option<Vec<PartialAttachment>>,
+    /// Files to upload, sent alongside the changes rather than in them.
+    #[serde(skip)]
+    pub files: Vec<FileUpload>,
+    /// Which mentions notify their targets.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allowed_mentions: Option<AllowedMentions>,
+    /// Flags replacing the message's flags. Only `SUPPRESS_EMBEDS` can be changed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub flags: Option<MessageFlags>,
+}
+
+impl From<CreateMessage> for EditMessage {
+    /// Replace the content, embeds, components and attachments of a message with those of
+    /// another.
+    fn from(message: CreateMessage) -> Self {
+        Self {
+            content: Some(message.content.unwrap_or_default()),
+            embeds: Some(message.embeds),
+            components: Some(message.components),
+            attachments: Some(message.attachments),
+            files: message.files,
+            allowed_mentions: message.allowed_mentions,
+            flags: message.flags,
+        }
+    }
+}
+
+impl<S: Into<String>> From<S> for CreateMessage {
+    fn from(content: S) -> Self {
+        Self {
+            content: Some(content.into()),
+            ..Default::default()
+        }
+    }
+}
+
+/// An emoji to react to a message with.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ReactionEmoji {
+    /// A Unicode emoji, such as `"👍"`.
+    Unicode(String),
+    /// A custom emoji of a guild.
+    Custom {
+        /// Name of the emoji.
+        name: String,
+        /// ID of the emoji.
+        id: Snowflake,
+    },
+}
+
+impl ReactionEmoji {
+    /// Returns a custom emoji of a guild.
+    pub fn custom<N: Into<String>, S: Into<Snowflake>>(name: N, id: S) -> Self {
+        ReactionEmoji::Custom {
+            name: name.into(),
+            id: id.into(),
+        }
+    }
+}
+
+/// Formats the emoji as Discord expects it in URLs, before it is percent-encoded: as itself for
+/// Unicode emojis, and as `name:id` for custom emojis.
+impl fmt::Display for ReactionEmoji {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReactionEmoji::Unicode(emoji) => f.write_str(emoji),
+            ReactionEmoji::Custom { name, id } => write!(f, "{}:{}", name, id),
+        }
+    }
+}
+
+impl From<char> for ReactionEmoji {
+    fn from(emoji: char) -> Self {
+        ReactionEmoji::Unicode(emoji.to_string())
+    }
+}
+
+impl From<&str> for ReactionEmoji {
+    fn from(emoji: &str) -> Self {
+        ReactionEmoji::Unicode(emoji.to_owned())
+    }
+}
+
+impl From<String> for ReactionEmoji {
+    fn from(emoji: String) -> Self {
+        ReactionEmoji::Unicode(emoji)
+    }
+}
+
+/// Pages through the users who reacted to a message with an emoji.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct ReactionQuery {
+    /// Retrieve users after this user ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<Snowflake>,
+    /// Number of users to return, 1-100, default 25.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u8>,
+}
@@ -1,5 +1,143 @@
+/// Declares an integer-backed enum sent by Discord, with an `Unknown` variant catching any value
+/// added to the API after this version of Slash, so new Discord features don't break
+/// deserialization.
+macro_rules! int_enum {
+    (
+        $(#[$meta:meta])*
+        $vis:vis enum $name:ident {
+            $(
+                $(#[$variant_meta:meta])*
+                $variant:ident = $value:literal,
+            )*
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+        #[non_exhaustive]
+        $vis enum $name {
+            $(
+                $(#[$variant_meta])*
+                $variant,
+            )*
+            /// A value unknown to this version of Slash.
+            Unknown(u8),
+        }
+
+        impl From<u8> for $name {
+            fn from(value: u8) -> Self {
+                match value {
+                    $($value => $name::$variant,)*
+                    other => $name::Unknown(other),
+                }
+            }
+        }
+
+        impl From<$name> for u8 {
+            fn from(value: $name) -> Self {
+                match value {
+                    $($name::$variant => $value,)*
+                    $name::Unknown(other) => other,
+                }
+            }
+        }
+
+        impl serde::Serialize for $name {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_u8((*self).into())
+            }
+        }
+
+        impl<'de> serde::Deserialize<'de> for $name {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                <u8 as serde::Deserialize>::deserialize(deserializer).map($name::from)
+            }
+        }
+    };
+}
+
+pub mod application;
+pub mod channel;
 pub mod command;
+pub mod component;
+pub mod embed;
+pub mod gateway;
+pub mod guild;
 pub mod interaction;
 pub mod member;
+pub mod message;
+pub mod modal;
+pub mod monetization;
+pub mod oauth2;
+pub mod permissions;
+pub mod role;
 pub mod snowflake;
 pub mod user;
+
+/// Deserialize a payload received from Discord.
+///
+/// With the `simd-json` feature enabled, payloads are parsed with simd-json instead of
+/// serde_json, on a copy of the bytes as it parses in place. This covers both transports:
+/// gateway events are parsed with it too, before their data is read with `from_value`.
+///
+/// With the `strict-model` feature enabled, any fields the models don't capture are logged, so
+/// changes to the API can be noticed without breaking deserialization.
+pub(crate) fn from_slice<T: serde::de::DeserializeOwned>(bytes: &[u8]) -> serde_json::Result<T> {
+    #[cfg(feature = "simd-json")]
+    {
+        use serde::de::Error;
+
+        let mut bytes = bytes.to_vec();
+        let deserializer = &mut simd_json::Deserializer::from_slice(&mut bytes)
+            .map_err(serde_json::Error::custom)?;
+        deserialize(deserializer).map_err(serde_json::Error::custom)
+    }
+    #[cfg(not(feature = "simd-json"))]
+    {
+        deserialize(&mut serde_json::Deserializer::from_slice(bytes))
+    }
+}
+
+/// Deserialize a payload received from Discord that was already parsed, such as the data of a
+/// gateway event. Like [`from_slice`], logs the fields the model doesn't capture with the
+/// `strict-model` feature enabled.
+#[cfg(feature = "gateway")]
+pub(crate) fn from_value<T: serde::de::DeserializeOwned>(
+    value: serde_json::Value,
+) -> serde_json::Result<T> {
+    deserialize(value)
+}
+
+/// Deserialize a payload, logging the fields the model doesn't capture in `strict-model`.
+fn deserialize<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: serde::Deserialize<'de>,
+{
+    #[cfg(feature = "strict-model")]
+    {
+        serde_ignored::deserialize(deserializer, |path| {
+            tracing::warn!(
+                model = std::any::type_name::<T>(),
+                field = %path,
+                "ignoring field not captured by the model"
+            );
+        })
+    }
+    #[cfg(not(feature = "strict-model"))]
+    {
+        T::deserialize(deserializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{from_slice, user::User};
+
+    #[test]
+    fn test_from_slice() {
+        let json = br#"{"id": "940565712559304739", "username": "slash", "discriminator": "0001", "avatar": null}"#;
+        let user: User = from_slice(json).unwrap();
+        assert_eq!(user.username, "slash");
+        assert!(from_slice::<User>(b"{\"id\": ").is_err());
+    }
+}
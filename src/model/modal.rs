@@ -0,0 +1,183 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use super::component::ComponentType;
+
+int_enum! {
+    pub enum TextInputStyle {
+        /// A single-line input.
+        Short = 1,
+        /// A multi-line input.
+        Paragraph = 2,
+    }
+}
+
+/// A popup form shown in response to an interaction, made of up to 5 text inputs.
+///
+/// Modals are checked against Discord's limits when built with a
+/// [`ModalBuilder`](crate::ModalBuilder), or when shown.
+#[derive(Debug, Clone, Serialize)]
+pub struct Modal {
+    /// Developer-defined identifier sent back when the modal is submitted, up to 100
+    /// characters.
+    pub custom_id: String,
+    /// Title of the popup, up to 45 characters.
+    pub title: String,
+    /// The text inputs of the modal, each shown on its own row.
+    #[serde(rename = "components", serialize_with = "serialize_rows")]
+    pub inputs: Vec<TextInput>,
+}
+
+/// A text input of a [`Modal`].
+#[derive(Debug, Clone, Serialize)]
+pub struct TextInput {
+    /// Developer-defined identifier of the input, sent back with its value, up to 100
+    /// characters.
+    pub custom_id: String,
+    /// Whether the input is a single line or a paragraph.
+    pub style: TextInputStyle,
+    /// Label shown above the input, up to 45 characters.
+    pub label: String,
+    /// Minimum length of the input's value, up to 4000.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_length: Option<u16>,
+    /// Maximum length of the input's value, from 1 to 4000.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_length: Option<u16>,
+    /// Whether the input must be filled in. Defaults to `true`.
+    pub required: bool,
+    /// Pre-filled value of the input, up to 4000 characters.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    /// Placeholder shown while the input is empty, up to 100 characters.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub placeholder: Option<String>,
+}
+
+impl TextInput {
+    /// Create a required text input of the given style, without any limits.
+    pub fn new<S: Into<String>>(style: TextInputStyle, custom_id: S, label: S) -> Self {
+        TextInput {
+            custom_id: custom_id.into(),
+            style,
+            label: label.into(),
+            min_length: None,
+            max_length: None,
+            required: true,
+            value: None,
+            placeholder: None,
+        }
+    }
+
+    /// Create a required single-line text input.
+    pub fn short<S: Into<String>>(custom_id: S, label: S) -> Self {
+        Self::new(TextInputStyle::Short, custom_id, label)
+    }
+
+    /// Create a required multi-line text input.
+    pub fn paragraph<S: Into<String>>(custom_id: S, label: S) -> Self {
+        Self::new(TextInputStyle::Paragraph, custom_id, label)
+    }
+
+    /// Set the minimum length of the value, up to 4000.
+    pub fn min_length(mut self, min_length: u16) -> Self {
+        self.min_length = Some(min_length);
+        self
+    }
+
+    /// Set the maximum length of the value, from 1 to 4000.
+    pub fn max_length(mut self, max_length: u16) -> Self {
+        self.max_length = Some(max_length);
+        self
+    }
+
+    /// Set whether the input must be filled in. Defaults to `true`.
+    pub fn required(mut self, required: bool) -> Self {
+        self.required = required;
+        self
+    }
+
+    /// Pre-fill the input with a value.
+    pub fn value<S: Into<String>>(mut self, value: S) -> Self {
+        self.value = Some(value.into());
+        self
+    }
+
+    /// Set the placeholder shown while the input is empty.
+    pub fn placeholder<S: Into<String>>(mut self, placeholder: S) -> Self {
+        self.placeholder = Some(placeholder.into());
+        self
+    }
+}
+
+/// Serialize each text input in its own action row, as Discord expects.
+fn serialize_rows<S: Serializer>(inputs: &[TextInput], serializer: S) -> Result<S::Ok, S::Error> {
+    #[derive(Serialize)]
+    struct Input<'a> {
+        #[serde(rename = "type")]
+        ty: ComponentType,
+        #[serde(flatten)]
+        input: &'a TextInput,
+    }
+
+    #[derive(Serialize)]
+    struct Row<'a> {
+        #[serde(rename = "type")]
+        ty: ComponentType,
+        components: [Input<'a>; 1],
+    }
+
+    serializer.collect_seq(inputs.iter().map(|input| Row {
+        ty: ComponentType::ActionRow,
+        components: [Input {
+            ty: ComponentType::TextInput,
+            input,
+        }],
+    }))
+}
+
+/// The value of a text input in a submitted [`Modal`].
+#[derive(Debug, Clone, Deserialize)]
+pub struct SubmittedInput {
+    /// The custom ID of the text input.
+    pub custom_id: String,
+    /// The value the user entered, which is empty if the input was left blank.
+    #[serde(default)]
+    pub value: String,
+}
+
+/// Deserialize the text inputs of a submitted modal, each in their own action row.
+pub(crate) fn deserialize_rows<'de, D>(deserializer: D) -> Result<Vec<SubmittedInput>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    struct Row {
+        #[serde(default)]
+        components: Vec<SubmittedInput>,
+    }
+
+    let rows = Vec::<Row>::deserialize(deserializer)?;
+    Ok(rows.into_iter().flat_map(|row| row.components).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Modal, TextInput};
+
+    #[test]
+    fn test_modal_serialize() {
+        let modal = Modal {
+            custom_id: "feedback".to_string(),
+            title: "Feedback".to_string(),
+            inputs: vec![TextInput::short("name", "Name").max_length(32)],
+        };
+        assert_eq!(
+            serde_json::to_string(&modal).unwrap(),
+            concat!(
+                r#"{"custom_id":"feedback","title":"Feedback","components":[{"type":1,"#,
+                r#""components":[{"type":4,"custom_id":"name","style":1,"label":"Name","#,
+                r#""max_length":32,"required":true}]}]}"#
+            )
+        );
+    }
+}
@@ -0,0 +1,163 @@
+use bitflags::bitflags;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::snowflake::Snowflake;
+
+int_enum! {
+    pub enum SkuType {
+        /// Durable one-time purchase.
+        Durable = 2,
+        /// Consumable one-time purchase.
+        Consumable = 3,
+        /// Represents a recurring subscription.
+        Subscription = 5,
+        /// System-generated group for each `Subscription` SKU created.
+        SubscriptionGroup = 6,
+    }
+}
+
+bitflags! {
+    /// Flags on a SKU.
+    ///
+    /// Bits unknown to this version of Slash are retained when deserializing.
+    #[derive(Serialize, Deserialize)]
+    #[serde(transparent)]
+    pub struct SkuFlags: u32 {
+        /// SKU is available for purchase.
+        const AVAILABLE = 1 << 2;
+        /// Recurring SKU that can be purchased by a user and applied to a single server.
+        const GUILD_SUBSCRIPTION = 1 << 7;
+        /// Recurring SKU purchased by a user for themselves.
+        const USER_SUBSCRIPTION = 1 << 8;
+    }
+}
+
+/// SKUs (stock-keeping units) represent premium offerings that can be made available to an
+/// application's users or guilds.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Sku {
+    /// ID of the SKU.
+    pub id: Snowflake,
+    /// Type of SKU.
+    #[serde(rename = "type")]
+    pub ty: SkuType,
+    /// ID of the parent application.
+    pub application_id: Snowflake,
+    /// Customer-facing name of the premium offering.
+    pub name: String,
+    /// System-generated URL slug based on the SKU's name.
+    pub slug: String,
+    /// SKU flags combined as a bitfield.
+    pub flags: SkuFlags,
+}
+
+int_enum! {
+    pub enum EntitlementType {
+        /// Entitlement was purchased by user.
+        Purchase = 1,
+        /// Entitlement for Discord Nitro subscription.
+        PremiumSubscription = 2,
+        /// Entitlement was gifted by developer.
+        DeveloperGift = 3,
+        /// Entitlement was purchased by a dev in application test mode.
+        TestModePurchase = 4,
+        /// Entitlement was granted when the SKU was free.
+        FreePurchase = 5,
+        /// Entitlement was gifted by another user.
+        UserGift = 6,
+        /// Entitlement was claimed by user for free as a Nitro Subscriber.
+        PremiumPurchase = 7,
+        /// Entitlement was purchased as an app subscription.
+        ApplicationSubscription = 8,
+    }
+}
+
+/// Entitlements represent that a user or guild has access to a premium offering in an
+/// application.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Entitlement {
+    /// ID of the entitlement.
+    pub id: Snowflake,
+    /// ID of the SKU.
+    pub sku_id: Snowflake,
+    /// ID of the parent application.
+    pub application_id: Snowflake,
+    /// ID of the user that is granted access to the entitlement's SKU.
+    pub user_id: Option<Snowflake>,
+    /// Type of entitlement.
+    #[serde(rename = "type")]
+    pub ty: EntitlementType,
+    /// Entitlement was deleted.
+    #[serde(default)]
+    pub deleted: bool,
+    /// Start date at which the entitlement is valid.
+    pub starts_at: Option<DateTime<Utc>>,
+    /// Date at which the entitlement is no longer valid.
+    pub ends_at: Option<DateTime<Utc>>,
+    /// ID of the guild that is granted access to the entitlement's SKU.
+    pub guild_id: Option<Snowflake>,
+    /// For consumable items, whether or not the entitlement has been consumed.
+    pub consumed: Option<bool>,
+}
+
+/// Filters applied when listing an application's entitlements.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct EntitlementQuery {
+    /// User ID to look up entitlements for.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<Snowflake>,
+    /// Optional list of SKU IDs to check entitlements for.
+    #[serde(
+        skip_serializing_if = "Vec::is_empty",
+        serialize_with = "serialize_comma_separated"
+    )]
+    pub sku_ids: Vec<Snowflake>,
+    /// Retrieve entitlements before this entitlement ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before: Option<Snowflake>,
+    /// Retrieve entitlements after this entitlement ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<Snowflake>,
+    /// Number of entitlements to return, 1-100, default 100.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u8>,
+    /// Guild ID to look up entitlements for.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub guild_id: Option<Snowflake>,
+    /// Whether or not ended entitlements should be omitted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exclude_ended: Option<bool>,
+}
+
+fn serialize_comma_separated<S>(ids: &[Snowflake], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    let joined = ids
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(",");
+    serializer.serialize_str(&joined)
+}
+
+int_enum! {
+    pub enum EntitlementOwnerType {
+        /// The entitlement is granted to a guild.
+        Guild = 1,
+        /// The entitlement is granted to a user.
+        User = 2,
+    }
+}
+
+/// A test entitlement, granting a user or guild access to a SKU without a purchase.
+#[derive(Clone, Debug, Serialize)]
+pub struct CreateTestEntitlement {
+    /// ID of the SKU to grant the entitlement to.
+    pub sku_id: Snowflake,
+    /// ID of the guild or user to grant the entitlement to.
+    pub owner_id: Snowflake,
+    /// Whether the owner is a guild or a user.
+    pub owner_type: EntitlementOwnerType,
+}
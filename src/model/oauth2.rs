@@ -0,0 +1,30 @@
+use std::fmt;
+
+use serde::Deserialize;
+
+/// The scope allowing an application to update its own commands, without a bot token.
+pub const APPLICATIONS_COMMANDS_UPDATE: &str = "applications.commands.update";
+
+/// An OAuth2 access token granted to an application.
+#[derive(Clone, Deserialize)]
+pub struct AccessToken {
+    /// The token to authenticate with, as a bearer token.
+    pub access_token: String,
+    /// The type of token, always `Bearer`.
+    pub token_type: String,
+    /// How many seconds the token is valid for.
+    pub expires_in: u64,
+    /// Space-separated scopes the token was granted.
+    pub scope: String,
+}
+
+impl fmt::Debug for AccessToken {
+    // Leaves out the token, so that it isn't logged by accident.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AccessToken")
+            .field("token_type", &self.token_type)
+            .field("expires_in", &self.expires_in)
+            .field("scope", &self.scope)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,300 @@
+use bitflags::bitflags;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use super::{
+    channel::{OverwriteType, PermissionOverwrite},
+    member::Member,
+    role::Role,
+    snowflake::Snowflake,
+};
+
+bitflags! {
+    /// A set of permissions in a guild or channel.
+    ///
+    /// Discord sends permissions as a string holding the bit set, as it doesn't fit in the
+    /// integers of every language. Bits unknown to this version of Slash are retained when
+    /// deserializing.
+    #[derive(Default)]
+    pub struct Permissions: u64 {
+        /// Allows creation of instant invites.
+        const CREATE_INSTANT_INVITE = 1 << 0;
+        /// Allows kicking members.
+        const KICK_MEMBERS = 1 << 1;
+        /// Allows banning members.
+        const BAN_MEMBERS = 1 << 2;
+        /// Allows all permissions and bypasses channel permission overwrites.
+        const ADMINISTRATOR = 1 << 3;
+        /// Allows management and editing of channels.
+        const MANAGE_CHANNELS = 1 << 4;
+        /// Allows management and editing of the guild.
+        const MANAGE_GUILD = 1 << 5;
+        /// Allows for the addition of reactions to messages.
+        const ADD_REACTIONS = 1 << 6;
+        /// Allows for viewing of audit logs.
+        const VIEW_AUDIT_LOG = 1 << 7;
+        /// Allows for using priority speaker in a voice channel.
+        const PRIORITY_SPEAKER = 1 << 8;
+        /// Allows the user to go live.
+        const STREAM = 1 << 9;
+        /// Allows guild members to view a channel, including reading its messages.
+        const VIEW_CHANNEL = 1 << 10;
+        /// Allows for sending messages in a channel and creating threads in a forum.
+        const SEND_MESSAGES = 1 << 11;
+        /// Allows for sending of `/tts` messages.
+        const SEND_TTS_MESSAGES = 1 << 12;
+        /// Allows for deletion of other users' messages.
+        const MANAGE_MESSAGES = 1 << 13;
+        /// Links sent by users with this permission will be auto-embedded.
+        const EMBED_LINKS = 1 << 14;
+        /// Allows for uploading images and files.
+        const ATTACH_FILES = 1 << 15;
+        /// Allows for reading of message history.
+        const READ_MESSAGE_HISTORY = 1 << 16;
+        /// Allows for using the `@everyone` tag to notify all users in a channel.
+        const MENTION_EVERYONE = 1 << 17;
+        /// Allows the usage of custom emojis from other servers.
+        const USE_EXTERNAL_EMOJIS = 1 << 18;
+        /// Allows for viewing guild insights.
+        const VIEW_GUILD_INSIGHTS = 1 << 19;
+        /// Allows for joining of a voice channel.
+        const CONNECT = 1 << 20;
+        /// Allows for speaking in a voice channel.
+        const SPEAK = 1 << 21;
+        /// Allows for muting members in a voice channel.
+        const MUTE_MEMBERS = 1 << 22;
+        /// Allows for deafening of members in a voice channel.
+        const DEAFEN_MEMBERS = 1 << 23;
+        /// Allows for moving of members between voice channels.
+        const MOVE_MEMBERS = 1 << 24;
+        /// Allows for using voice-activity-detection in a voice channel.
+        const USE_VAD = 1 << 25;
+        /// Allows for modification of own nickname.
+        const CHANGE_NICKNAME = 1 << 26;
+        /// Allows for modification of other users' nicknames.
+        const MANAGE_NICKNAMES = 1 << 27;
+        /// Allows management and editing of roles.
+        const MANAGE_ROLES = 1 << 28;
+        /// Allows management and editing of webhooks.
+        const MANAGE_WEBHOOKS = 1 << 29;
+        /// Allows for editing and deleting emojis, stickers, and soundboard sounds.
+        const MANAGE_GUILD_EXPRESSIONS = 1 << 30;
+        /// Allows members to use application commands in text channels.
+        const USE_APPLICATION_COMMANDS = 1 << 31;
+        /// Allows for requesting to speak in stage channels.
+        const REQUEST_TO_SPEAK = 1 << 32;
+        /// Allows for editing and deleting scheduled events.
+        const MANAGE_EVENTS = 1 << 33;
+        /// Allows for deleting and archiving threads, and viewing all private threads.
+        const MANAGE_THREADS = 1 << 34;
+        /// Allows for creating public and announcement threads.
+        const CREATE_PUBLIC_THREADS = 1 << 35;
+        /// Allows for creating private threads.
+        const CREATE_PRIVATE_THREADS = 1 << 36;
+        /// Allows the usage of custom stickers from other servers.
+        const USE_EXTERNAL_STICKERS = 1 << 37;
+        /// Allows for sending messages in threads.
+        const SEND_MESSAGES_IN_THREADS = 1 << 38;
+        /// Allows for using activities in a voice channel.
+        const USE_EMBEDDED_ACTIVITIES = 1 << 39;
+        /// Allows for timing out users to prevent them from sending or reacting to messages.
+        const MODERATE_MEMBERS = 1 << 40;
+        /// Allows for viewing role subscription insights.
+        const VIEW_CREATOR_MONETIZATION_ANALYTICS = 1 << 41;
+        /// Allows for using soundboard in a voice channel.
+        const USE_SOUNDBOARD = 1 << 42;
+        /// Allows for creating emojis, stickers, and soundboard sounds.
+        const CREATE_GUILD_EXPRESSIONS = 1 << 43;
+        /// Allows for creating scheduled events.
+        const CREATE_EVENTS = 1 << 44;
+        /// Allows the usage of custom soundboard sounds from other servers.
+        const USE_EXTERNAL_SOUNDS = 1 << 45;
+        /// Allows sending voice messages.
+        const SEND_VOICE_MESSAGES = 1 << 46;
+        /// Allows sending polls.
+        const SEND_POLLS = 1 << 49;
+        /// Allows user-installed apps to send public responses.
+        const USE_EXTERNAL_APPS = 1 << 50;
+    }
+}
+
+impl Permissions {
+    /// Returns whether these permissions include every given permission, which is always the
+    /// case with `ADMINISTRATOR`.
+    pub fn has(self, permissions: Permissions) -> bool {
+        self.contains(Permissions::ADMINISTRATOR) || self.contains(permissions)
+    }
+}
+
+/// Compute the permissions of a member in a channel of a guild, from the guild's roles and the
+/// channel's overwrites, following Discord's documented algorithm. This lets bots caching
+/// guilds and channels check permissions in any channel, rather than only trusting those sent
+/// with an interaction for its own channel.
+///
+/// `roles` are the guild's roles, including `@everyone`, whose ID is the guild's. The guild's
+/// owner has every permission, which isn't checked here: see
+/// [`Guild::permissions_in`](super::guild::Guild::permissions_in). Permissions implied by
+/// others, such as losing every permission in a channel without `VIEW_CHANNEL`, aren't
+/// applied either.
+pub fn compute(
+    guild_id: Snowflake,
+    member: &Member,
+    roles: &[Role],
+    channel_overwrites: &[PermissionOverwrite],
+) -> Permissions {
+    let mut permissions = roles
+        .iter()
+        .filter(|role| role.id == guild_id || member.roles.contains(&role.id))
+        .fold(Permissions::empty(), |permissions, role| {
+            permissions | role.permissions
+        });
+    if permissions.contains(Permissions::ADMINISTRATOR) {
+        return Permissions::all();
+    }
+
+    let mut apply = |allow: Permissions, deny: Permissions| {
+        permissions.remove(deny);
+        permissions.insert(allow);
+    };
+    let overwrite = |ty: OverwriteType, id: Snowflake| {
+        channel_overwrites
+            .iter()
+            .find(|overwrite| overwrite.ty == ty && overwrite.id == id)
+    };
+    if let Some(everyone) = overwrite(OverwriteType::Role, guild_id) {
+        apply(everyone.allow, everyone.deny);
+    }
+    let (allow, deny) = channel_overwrites
+        .iter()
+        .filter(|overwrite| {
+            overwrite.ty == OverwriteType::Role
+                && overwrite.id != guild_id
+                && member.roles.contains(&overwrite.id)
+        })
+        .fold(
+            (Permissions::empty(), Permissions::empty()),
+            |(allow, deny), overwrite| (allow | overwrite.allow, deny | overwrite.deny),
+        );
+    apply(allow, deny);
+    if let Some(own) = member
+        .user_id()
+        .and_then(|user_id| overwrite(OverwriteType::Member, user_id))
+    {
+        apply(own.allow, own.deny);
+    }
+    permissions
+}
+
+impl Serialize for Permissions {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&self.bits())
+    }
+}
+
+impl<'de> Deserialize<'de> for Permissions {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let bits = String::deserialize(deserializer)?
+            .parse()
+            .map_err(de::Error::custom)?;
+        // Keeps the bits unknown to this version.
+        Ok(Permissions { bits })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{compute, Permissions};
+    use crate::model::{
+        channel::{OverwriteType, PermissionOverwrite},
+        member::Member,
+        role::Role,
+    };
+
+    #[test]
+    fn test_permissions_serde() {
+        let permissions: Permissions = serde_json::from_str(r#""2251799813685256""#).unwrap();
+        assert!(permissions.contains(Permissions::ADMINISTRATOR));
+        assert_eq!(permissions.bits(), 1 << 51 | 1 << 3);
+        assert_eq!(
+            serde_json::to_string(&permissions).unwrap(),
+            r#""2251799813685256""#
+        );
+    }
+
+    #[test]
+    fn test_administrator_has_every_permission() {
+        let moderator = Permissions::KICK_MEMBERS | Permissions::BAN_MEMBERS;
+        assert!(moderator.has(Permissions::KICK_MEMBERS));
+        assert!(!moderator.has(Permissions::KICK_MEMBERS | Permissions::MANAGE_ROLES));
+        assert!(Permissions::ADMINISTRATOR.has(Permissions::MANAGE_ROLES));
+    }
+
+    #[test]
+    fn test_compute() {
+        let role = |id: u64, permissions: Permissions| -> Role {
+            serde_json::from_value(serde_json::json!({
+                "id": id.to_string(), "name": "role", "color": 0, "hoist": false,
+                "position": 0, "permissions": permissions, "managed": false,
+                "mentionable": false,
+            }))
+            .unwrap()
+        };
+        let overwrite = |id: u64, ty, allow, deny| PermissionOverwrite {
+            id: id.into(),
+            ty,
+            allow,
+            deny,
+        };
+        let member: Member = serde_json::from_value(serde_json::json!({
+            "user": { "id": "10", "username": "slash", "discriminator": "0" },
+            "roles": ["2"],
+            "joined_at": "2024-01-01T00:00:00+00:00",
+            "deaf": false,
+            "mute": false,
+        }))
+        .unwrap();
+        let roles = [
+            role(1, Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES),
+            role(2, Permissions::KICK_MEMBERS),
+            role(3, Permissions::ADMINISTRATOR),
+        ];
+
+        let base = compute(1.into(), &member, &roles, &[]);
+        assert_eq!(
+            base,
+            Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES | Permissions::KICK_MEMBERS
+        );
+
+        let read_only = [
+            overwrite(
+                1,
+                OverwriteType::Role,
+                Permissions::empty(),
+                Permissions::SEND_MESSAGES,
+            ),
+            overwrite(
+                2,
+                OverwriteType::Role,
+                Permissions::ATTACH_FILES,
+                Permissions::empty(),
+            ),
+            overwrite(
+                3,
+                OverwriteType::Role,
+                Permissions::SEND_MESSAGES,
+                Permissions::empty(),
+            ),
+        ];
+        let permissions = compute(1.into(), &member, &roles, &read_only);
+        assert!(!permissions.contains(Permissions::SEND_MESSAGES));
+        assert!(permissions.contains(Permissions::ATTACH_FILES));
+
+        let muted = [overwrite(
+            10,
+            OverwriteType::Member,
+            Permissions::empty(),
+            Permissions::VIEW_CHANNEL,
+        )];
+        let permissions = compute(1.into(), &member, &roles, &muted);
+        assert!(!permissions.contains(Permissions::VIEW_CHANNEL));
+    }
+}
@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+
+use super::{permissions::Permissions, snowflake::Snowflake};
+
+/// A set of permissions that can be given to members of a guild.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Role {
+    /// ID of the role.
+    pub id: Snowflake,
+    /// Name of the role.
+    pub name: String,
+    /// Color of the role, as an RGB integer, or 0 for no color.
+    pub color: u32,
+    /// Whether members with the role are listed separately in the member list.
+    pub hoist: bool,
+    /// The role's icon hash.
+    pub icon: Option<String>,
+    /// The role's unicode emoji.
+    pub unicode_emoji: Option<String>,
+    /// Position of the role in the role hierarchy.
+    pub position: i32,
+    /// The permissions the role gives.
+    pub permissions: Permissions,
+    /// Whether the role is managed by an integration.
+    pub managed: bool,
+    /// Whether the role can be mentioned.
+    pub mentionable: bool,
+}
@@ -1,13 +1,13 @@
 use std::fmt::Display;
 
-use chrono::{DateTime, NaiveDateTime, Utc};
+use chrono::{DateTime, Utc};
 use serde::{
     de::{Deserializer, Visitor},
     Deserialize, Serialize,
 };
 
-/// The snowflake struct.
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+/// The snowflake struct. Snowflakes are ordered by creation time.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Snowflake {
     inner: u64,
 }
@@ -24,6 +24,12 @@ impl From<u64> for Snowflake {
     }
 }
 
+impl From<Snowflake> for u64 {
+    fn from(id: Snowflake) -> Self {
+        id.inner
+    }
+}
+
 impl From<&str> for Snowflake {
     fn from(id: &str) -> Self {
         Snowflake {
@@ -96,13 +102,13 @@ impl Snowflake {
         let millis = (self.inner >> 22) + DISCORD_EPOCH;
         let secs = (millis / 1000) as i64;
         let nanos = ((millis % 1000) * 1_000_000) as u32;
-        DateTime::<Utc>::from_utc(NaiveDateTime::from_timestamp(secs, nanos), Utc)
+        DateTime::<Utc>::from_timestamp(secs, nanos).expect("snowflake timestamp out of range")
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use chrono::{TimeZone, Utc};
+    use chrono::{DateTime, NaiveDate, Utc};
     use serde::Deserialize;
 
     use super::Snowflake;
@@ -110,7 +116,10 @@ mod tests {
     #[test]
     fn test_snowflake_decode() {
         let snowflake: Snowflake = "940565712559304734".into();
-        let timestamp = Utc.ymd(2022, 02, 8).and_hms_milli(11, 12, 20, 740);
+        let timestamp = NaiveDate::from_ymd_opt(2022, 2, 8)
+            .and_then(|date| date.and_hms_milli_opt(11, 12, 20, 740))
+            .map(|dt| DateTime::<Utc>::from_naive_utc_and_offset(dt, Utc))
+            .unwrap();
         assert_eq!(snowflake.timestamp(), timestamp);
     }
 
@@ -124,13 +133,19 @@ mod tests {
         let data: MyData = serde_json::from_str(json).unwrap();
         assert_eq!(
             data.timestamp.timestamp(),
-            Utc.ymd(2022, 02, 8).and_hms_milli(11, 42, 11, 922)
+            NaiveDate::from_ymd_opt(2022, 2, 8)
+                .and_then(|date| date.and_hms_milli_opt(11, 42, 11, 922))
+                .map(|dt| DateTime::<Utc>::from_naive_utc_and_offset(dt, Utc))
+                .unwrap()
         );
     }
 
     #[test]
     fn test_snowflake_new() {
-        let timestamp = Utc.ymd(2022, 02, 8).and_hms_milli(11, 42, 11, 922);
+        let timestamp = NaiveDate::from_ymd_opt(2022, 2, 8)
+            .and_then(|date| date.and_hms_milli_opt(11, 42, 11, 922))
+            .map(|dt| DateTime::<Utc>::from_naive_utc_and_offset(dt, Utc))
+            .unwrap();
         let snowflake = Snowflake::new(timestamp);
         assert_eq!(snowflake.timestamp(), timestamp);
     }
@@ -1,10 +1,14 @@
 use bitflags::bitflags;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use super::snowflake::Snowflake;
 
 bitflags! {
-    #[derive(Deserialize)]
+    /// Flags on a user's account.
+    ///
+    /// Bits unknown to this version of Slash are retained when deserializing.
+    #[derive(Serialize, Deserialize)]
+    #[serde(transparent)]
     pub struct UserFlags: u32 {
         const NONE = 0;
         /// Discord Employee
@@ -38,14 +42,16 @@ bitflags! {
     }
 }
 
-#[derive(Debug, Clone, Deserialize, PartialEq)]
-pub enum PremiumType {
-    None = 1,
-    NitroClassic,
-    Nitro,
+int_enum! {
+    pub enum PremiumType {
+        None = 0,
+        NitroClassic = 1,
+        Nitro = 2,
+        NitroBasic = 3,
+    }
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct User {
     /// The user's ID.
     pub id: Snowflake,
@@ -56,27 +62,42 @@ pub struct User {
     /// The user's avatar hash.
     pub avatar: Option<String>,
     /// Whether this user belongs to an OAuth-2 application.
+    #[serde(default)]
     pub bot: bool,
     /// Whether the user is an Official Discord System user (part of the urgent message system)
+    #[serde(default)]
     pub system: bool,
     /// Whether the user has 2FA enabled on their account.
+    #[serde(default)]
     pub mfa_enabled: bool,
     /// The user's banner hash.
     pub banner: Option<String>,
     /// The user's banner color encoded as an integer representation of hexadecimal color code.
     pub accent_color: Option<u32>,
     /// The user's chosen language option.
-    pub locale: String,
+    pub locale: Option<String>,
     /// The flags on a user's account.
-    pub flags: UserFlags,
+    pub flags: Option<UserFlags>,
     /// The user's public flags.
-    pub public_flags: UserFlags,
+    pub public_flags: Option<UserFlags>,
     /// The type of Nitro subscription on a user's account.
-    pub premium_type: PremiumType,
+    pub premium_type: Option<PremiumType>,
 }
 
 impl User {
     pub fn tag(&self) -> String {
-        return format!("{}#{}", self.username, self.discriminator);
+        format!("{}#{}", self.username, self.discriminator)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::UserFlags;
+
+    #[test]
+    fn test_flags_retain_unknown_bits() {
+        let flags: UserFlags = serde_json::from_str(&((1u32 << 30) | 1).to_string()).unwrap();
+        assert!(flags.contains(UserFlags::STAFF));
+        assert_eq!(flags.bits(), (1 << 30) | 1);
     }
 }
@@ -0,0 +1,409 @@
+//! Reads the options given to an application command, with [`Context::option`].
+use std::fmt;
+
+use crate::{
+    context::Context,
+    model::{
+        channel::Channel,
+        command::{ApplicationCommandOption, CommandOptionType},
+        interaction::{CommandDataOption, OptionValue, ResolvedData},
+        role::Role,
+        snowflake::Snowflake,
+        user::User,
+    },
+    reject::{self, Reject, Rejection},
+};
+
+/// The rejection of an option read with a type it wasn't given as.
+#[derive(Debug, Clone)]
+pub struct WrongOptionType {
+    /// Name of the option.
+    pub name: String,
+    /// The type the option was given as.
+    pub found: CommandOptionType,
+}
+
+impl Reject for WrongOptionType {}
+
+impl fmt::Display for WrongOptionType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "option `{}` was given as {:?}", self.name, self.found)
+    }
+}
+
+/// The rejection of a required option that wasn't given, or an interaction that isn't an
+/// application command.
+#[derive(Debug, Clone)]
+pub struct MissingOption {
+    /// Name of the option.
+    pub name: String,
+}
+
+impl Reject for MissingOption {}
+
+impl fmt::Display for MissingOption {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "missing required option `{}`", self.name)
+    }
+}
+
+/// The rejection of an [argument](crate::arg) whose option wasn't given, was given as another
+/// type, or is out of the argument's range.
+#[derive(Debug, Clone)]
+pub struct InvalidOption {
+    /// Name of the option.
+    pub name: String,
+    /// The type the argument reads the option as.
+    pub expected: CommandOptionType,
+    /// The value given to the option, if any.
+    pub got: Option<OptionValue>,
+}
+
+impl Reject for InvalidOption {}
+
+impl fmt::Display for InvalidOption {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.got {
+            Some(ref got) => write!(
+                f,
+                "invalid value for option `{}`, expected {:?}: {:?}",
+                self.name, self.expected, got
+            ),
+            None => write!(f, "missing required option `{}`", self.name),
+        }
+    }
+}
+
+/// A type that can be read from an option of an application command.
+///
+/// Users, roles and channels are read from the data Discord resolves alongside the options,
+/// while `Option<T>` reads optional options, being `None` when they weren't given.
+pub trait FromOption: Sized {
+    /// Read the value of an option, returning `None` if it is of another type.
+    fn from_option(option: &CommandDataOption, resolved: &ResolvedData) -> Option<Self>;
+
+    /// Returns the value of an option that wasn't given, if it is optional.
+    fn missing() -> Option<Self> {
+        None
+    }
+}
+
+impl FromOption for String {
+    fn from_option(option: &CommandDataOption, _: &ResolvedData) -> Option<Self> {
+        match (option.ty, &option.value) {
+            (CommandOptionType::String, Some(OptionValue::String(value))) => Some(value.clone()),
+            _ => None,
+        }
+    }
+}
+
+impl FromOption for i64 {
+    fn from_option(option: &CommandDataOption, _: &ResolvedData) -> Option<Self> {
+        match (option.ty, &option.value) {
+            (CommandOptionType::Integer, Some(OptionValue::Integer(value))) => Some(*value),
+            _ => None,
+        }
+    }
+}
+
+impl FromOption for f64 {
+    fn from_option(option: &CommandDataOption, _: &ResolvedData) -> Option<Self> {
+        match (option.ty, &option.value) {
+            (CommandOptionType::Number, Some(OptionValue::Number(value))) => Some(*value),
+            // Whole numbers are sent without a fractional part.
+            (CommandOptionType::Number, Some(OptionValue::Integer(value))) => Some(*value as f64),
+            _ => None,
+        }
+    }
+}
+
+impl FromOption for bool {
+    fn from_option(option: &CommandDataOption, _: &ResolvedData) -> Option<Self> {
+        match (option.ty, &option.value) {
+            (CommandOptionType::Boolean, Some(OptionValue::Boolean(value))) => Some(*value),
+            _ => None,
+        }
+    }
+}
+
+/// Reads the ID of a user, channel, role or mentionable.
+impl FromOption for Snowflake {
+    fn from_option(option: &CommandDataOption, _: &ResolvedData) -> Option<Self> {
+        match (option.ty, &option.value) {
+            (
+                CommandOptionType::User
+                | CommandOptionType::Channel
+                | CommandOptionType::Role
+                | CommandOptionType::Mentionable,
+                Some(OptionValue::String(id)),
+            ) => id.parse::<u64>().ok().map(Snowflake::from),
+            _ => None,
+        }
+    }
+}
+
+impl FromOption for User {
+    fn from_option(option: &CommandDataOption, resolved: &ResolvedData) -> Option<Self> {
+        match option.ty {
+            CommandOptionType::User | CommandOptionType::Mentionable => {
+                let id = Snowflake::from_option(option, resolved)?;
+                resolved.users.get(&id).cloned()
+            }
+            _ => None,
+        }
+    }
+}
+
+impl FromOption for Role {
+    fn from_option(option: &CommandDataOption, resolved: &ResolvedData) -> Option<Self> {
+        match option.ty {
+            CommandOptionType::Role | CommandOptionType::Mentionable => {
+                let id = Snowflake::from_option(option, resolved)?;
+                resolved.roles.get(&id).cloned()
+            }
+            _ => None,
+        }
+    }
+}
+
+impl FromOption for Channel {
+    fn from_option(option: &CommandDataOption, resolved: &ResolvedData) -> Option<Self> {
+        match option.ty {
+            CommandOptionType::Channel => {
+                let id = Snowflake::from_option(option, resolved)?;
+                resolved.channels.get(&id).cloned()
+            }
+            _ => None,
+        }
+    }
+}
+
+impl<T: FromOption> FromOption for Option<T> {
+    fn from_option(option: &CommandDataOption, resolved: &ResolvedData) -> Option<Self> {
+        T::from_option(option, resolved).map(Some)
+    }
+
+    fn missing() -> Option<Self> {
+        Some(None)
+    }
+}
+
+/// A [`FromOption`] type that declares the type of its option, so that options can be
+/// declared from the fields of a [`CommandOptions`] struct.
+pub trait OptionType: FromOption {
+    /// The type of option the value is read from.
+    const TYPE: CommandOptionType;
+    /// Whether the option must be given.
+    const REQUIRED: bool = true;
+}
+
+impl OptionType for String {
+    const TYPE: CommandOptionType = CommandOptionType::String;
+}
+
+impl OptionType for i64 {
+    const TYPE: CommandOptionType = CommandOptionType::Integer;
+}
+
+impl OptionType for f64 {
+    const TYPE: CommandOptionType = CommandOptionType::Number;
+}
+
+impl OptionType for bool {
+    const TYPE: CommandOptionType = CommandOptionType::Boolean;
+}
+
+impl OptionType for User {
+    const TYPE: CommandOptionType = CommandOptionType::User;
+}
+
+impl OptionType for Role {
+    const TYPE: CommandOptionType = CommandOptionType::Role;
+}
+
+impl OptionType for Channel {
+    const TYPE: CommandOptionType = CommandOptionType::Channel;
+}
+
+impl<T: OptionType> OptionType for Option<T> {
+    const TYPE: CommandOptionType = T::TYPE;
+    const REQUIRED: bool = false;
+}
+
+/// A struct of the options of a command, declared and read all at once with the
+/// [`options`](crate::arg::options) filter.
+///
+/// With the `macros` feature, this can be derived for structs of [`OptionType`] fields:
+///
+/// ```ignore
+/// #[derive(slash::CommandOptions)]
+/// struct Ban {
+///     /// The user to ban
+///     target: User,
+///     /// Days of messages to delete
+///     #[option(min = 0, max = 7)]
+///     days: Option<i64>,
+/// }
+/// ```
+pub trait CommandOptions: Sized {
+    /// Returns the options to declare, in order.
+    fn options() -> Vec<ApplicationCommandOption>;
+
+    /// Read the options given to the command.
+    fn from_context(context: &Context) -> Result<Self, Rejection>;
+}
+
+impl Context {
+    /// Read an option given to the command, or to the subcommand that was used, as the given
+    /// type.
+    ///
+    /// Options that weren't given are rejected with [`MissingOption`], unless read as an
+    /// `Option`, and options of another type are rejected with [`WrongOptionType`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use slash::{model::user::User, Context};
+    /// # fn run(context: Context) {
+    /// let count: i64 = context.option("count").unwrap_or(1);
+    /// let target: Option<User> = context.option("target").ok().flatten();
+    /// # }
+    /// ```
+    pub fn option<T: FromOption>(&self, name: &str) -> Result<T, Rejection> {
+        let missing = || {
+            reject::custom(MissingOption {
+                name: name.to_string(),
+            })
+        };
+        let data = self.command_data().ok_or_else(missing)?;
+        match data.option(name) {
+            Some(option) => T::from_option(option, &data.resolved).ok_or_else(|| {
+                reject::custom(WrongOptionType {
+                    name: name.to_string(),
+                    found: option.ty,
+                })
+            }),
+            None => T::missing().ok_or_else(missing),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{MissingOption, WrongOptionType};
+    use crate::{context::Context, model::user::User, test_util};
+
+    /// A context of `/mod warn` given a count, a weight and a target.
+    fn context() -> Context {
+        test_util::context(json!({
+            "data": {
+                "id": "940565712559304736",
+                "name": "mod",
+                "type": 1,
+                "options": [{
+                    "name": "warn",
+                    "type": 1,
+                    "options": [
+                        { "name": "count", "type": 4, "value": 3 },
+                        { "name": "weight", "type": 10, "value": 2 },
+                        { "name": "target", "type": 6, "value": "940565712559304739" }
+                    ]
+                }],
+                "resolved": {
+                    "users": {
+                        "940565712559304739": {
+                            "id": "940565712559304739",
+                            "username": "slash",
+                            "discriminator": "0001",
+                            "avatar": null
+                        }
+                    }
+                }
+            }
+        }))
+    }
+
+    #[test]
+    fn test_typed_options() {
+        let context = context();
+        assert_eq!(context.option::<i64>("count").unwrap(), 3);
+        assert_eq!(context.option::<f64>("weight").unwrap(), 2.0);
+        let target: User = context.option("target").unwrap();
+        assert_eq!(target.username, "slash");
+        assert_eq!(context.option::<Option<bool>>("silent").unwrap(), None);
+
+        let wrong = context.option::<String>("count").unwrap_err();
+        assert_eq!(wrong.find::<WrongOptionType>().unwrap().name, "count");
+        let missing = context.option::<bool>("silent").unwrap_err();
+        assert!(missing.find::<MissingOption>().is_some());
+    }
+
+    #[cfg(feature = "macros")]
+    #[test]
+    fn test_derive_command_options() {
+        use super::CommandOptions;
+
+        #[derive(crate::CommandOptions)]
+        struct Warn {
+            /// How many warnings to give
+            #[option(min = 1, max = 5)]
+            count: i64,
+            /// The user to warn
+            target: User,
+            #[option(
+                rename = "reason",
+                description = "Why they are warned",
+                choices("spam", "abuse")
+            )]
+            why: Option<String>,
+            /// Where to log the warning
+            #[option(channel_types(GuildText, GuildForum))]
+            log: Option<crate::model::channel::Channel>,
+        }
+
+        assert_eq!(
+            serde_json::to_value(Warn::options()).unwrap(),
+            json!([
+                {
+                    "type": 4,
+                    "name": "count",
+                    "description": "How many warnings to give",
+                    "required": true,
+                    "min_value": 1,
+                    "max_value": 5,
+                },
+                {
+                    "type": 6,
+                    "name": "target",
+                    "description": "The user to warn",
+                    "required": true,
+                },
+                {
+                    "type": 3,
+                    "name": "reason",
+                    "description": "Why they are warned",
+                    "required": false,
+                    "choices": [
+                        { "name": "spam", "value": "spam" },
+                        { "name": "abuse", "value": "abuse" },
+                    ],
+                },
+                {
+                    "type": 7,
+                    "name": "log",
+                    "description": "Where to log the warning",
+                    "required": false,
+                    "channel_types": [0, 15],
+                },
+            ])
+        );
+
+        let warn = Warn::from_context(&context()).unwrap();
+        assert_eq!(warn.count, 3);
+        assert_eq!(warn.target.username, "slash");
+        assert_eq!(warn.why, None);
+        assert!(warn.log.is_none());
+    }
+}
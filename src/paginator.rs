@@ -0,0 +1,183 @@
+//! Defines the `Paginator`, which replies with pages of embeds browsed with buttons.
+use std::time::Duration;
+
+use crate::{
+    context::Context,
+    errors::Result,
+    model::{
+        component::{ButtonStyle, Component},
+        embed::Embed,
+        interaction::{InteractionCallbackData, InteractionCallbackType, InteractionResponse},
+        message::EditMessage,
+    },
+};
+
+/// How long a paginator responds to its buttons, by default.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(120);
+
+const FIRST: &str = "slash:paginator:first";
+const PREVIOUS: &str = "slash:paginator:previous";
+const NEXT: &str = "slash:paginator:next";
+const LAST: &str = "slash:paginator:last";
+
+/// Replies to an interaction with pages of embeds, browsed with buttons to jump to the first,
+/// previous, next and last page.
+///
+/// Once the timeout elapses, the buttons are disabled.
+///
+/// # Example
+///
+/// ```no_run
+/// # use slash::{model::embed::Embed, Context, Paginator};
+/// # async fn run(context: Context) -> slash::Result<()> {
+/// let pages = (1..=3)
+///     .map(|page| Embed {
+///         title: Some(format!("Page {}", page)),
+///         ..Default::default()
+///     })
+///     .collect();
+/// Paginator::new(pages).run(&context).await
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Paginator {
+    pages: Vec<Embed>,
+    timeout: Duration,
+    invoker_only: bool,
+}
+
+impl Paginator {
+    /// Create a paginator showing the given pages, one embed each.
+    pub fn new(pages: Vec<Embed>) -> Self {
+        Self {
+            pages,
+            timeout: DEFAULT_TIMEOUT,
+            invoker_only: true,
+        }
+    }
+
+    /// Set how long the buttons keep working, counting from the reply. Defaults to 2 minutes,
+    /// and must stay below the 15 minutes an interaction can be responded to.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Set whether only the user who invoked the interaction can use the buttons, with clicks
+    /// from anyone else being ignored. Defaults to `true`.
+    pub fn invoker_only(mut self, invoker_only: bool) -> Self {
+        self.invoker_only = invoker_only;
+        self
+    }
+
+    /// Reply to the interaction with the first page, then switch pages as the buttons are
+    /// clicked. Only returns once the timeout elapses, and the buttons are disabled.
+    pub async fn run(self, context: &Context) -> Result<()> {
+        let mut page = 0;
+        context
+            .respond(self.response(InteractionCallbackType::ChannelMessageWithSource, page))
+            .await?;
+        if self.pages.len() <= 1 {
+            return Ok(());
+        }
+
+        let message = context.original_response().await?;
+        let invoker = context.user_id();
+        let invoker_only = self.invoker_only;
+        let mut clicks = context.await_component(
+            message.id,
+            |click| {
+                click
+                    .custom_id()
+                    .is_some_and(|id| id.starts_with("slash:paginator:"))
+            },
+            self.timeout,
+        );
+        while let Some(click) = clicks.next().await {
+            if invoker_only && click.user_id() != invoker {
+                click.defer_update().await?;
+                continue;
+            }
+            let last = self.pages.len() - 1;
+            page = match click.custom_id() {
+                Some(FIRST) => 0,
+                Some(PREVIOUS) => page.saturating_sub(1),
+                Some(NEXT) => (page + 1).min(last),
+                Some(LAST) => last,
+                _ => page,
+            };
+            click
+                .respond(self.response(InteractionCallbackType::UpdateMessage, page))
+                .await?;
+        }
+
+        context
+            .edit_original_response(&EditMessage {
+                components: Some(self.buttons(page, true)),
+                ..Default::default()
+            })
+            .await?;
+        Ok(())
+    }
+
+    fn response(&self, ty: InteractionCallbackType, page: usize) -> InteractionResponse {
+        InteractionResponse {
+            ty,
+            data: Some(InteractionCallbackData {
+                embeds: Some(self.pages.get(page).cloned().into_iter().collect()),
+                components: Some(if self.pages.len() > 1 {
+                    self.buttons(page, false)
+                } else {
+                    vec![]
+                }),
+                ..Default::default()
+            }),
+        }
+    }
+
+    /// Create the row of buttons shown on the given page.
+    fn buttons(&self, page: usize, disabled: bool) -> Vec<Component> {
+        let last = self.pages.len().saturating_sub(1);
+        let button = |custom_id: &str, label: &str, enabled: bool| Component {
+            disabled: disabled || !enabled,
+            ..Component::button(ButtonStyle::Secondary, custom_id, label)
+        };
+        let indicator = Component {
+            disabled: true,
+            ..Component::button(
+                ButtonStyle::Secondary,
+                "slash:paginator:page".to_string(),
+                format!("{}/{}", page + 1, self.pages.len()),
+            )
+        };
+        vec![Component::action_row(vec![
+            button(FIRST, "«", page > 0),
+            button(PREVIOUS, "‹", page > 0),
+            indicator,
+            button(NEXT, "›", page < last),
+            button(LAST, "»", page < last),
+        ])]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Paginator;
+    use crate::model::embed::Embed;
+
+    #[test]
+    fn test_buttons_disable_at_the_ends() {
+        let paginator = Paginator::new(vec![Embed::default(); 3]);
+        let enabled = |page, disabled| {
+            paginator.buttons(page, disabled)[0]
+                .components
+                .iter()
+                .map(|button| !button.disabled)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(enabled(0, false), [false, false, false, true, true]);
+        assert_eq!(enabled(1, false), [true, true, false, true, true]);
+        assert_eq!(enabled(2, false), [true, true, false, false, false]);
+        assert_eq!(enabled(1, true), [false; 5]);
+    }
+}
@@ -58,9 +58,21 @@
 
 use std::any::Any;
 use std::convert::Infallible;
+use std::error::Error as StdError;
 use std::fmt;
+use std::time::Duration;
 
 pub(crate) use self::sealed::{CombineRejection, IsReject};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::cooldown::OnCooldown;
+#[cfg(feature = "custom-id")]
+use crate::custom_id::CustomIdError;
+use crate::errors::{DiscordApiError, Error};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::filter::TimedOut;
+use crate::filters::concurrency::ConcurrencyLimited;
+use crate::options::{InvalidOption, MissingOption, WrongOptionType};
+use crate::permission::{GuildOnly, MissingPermissions, MissingRole};
 
 /// Rejects a request with a custom cause.
 ///
@@ -122,6 +134,13 @@ impl dyn Cause {
     }
 }
 
+/// Rejects a request because no filter matched it.
+pub(crate) fn not_found() -> Rejection {
+    Rejection {
+        reason: Reason::NotFound,
+    }
+}
+
 pub(crate) fn known<T: Into<Known>>(err: T) -> Rejection {
     Rejection::known(err.into())
 }
@@ -147,18 +166,49 @@ enum Rejections {
 #[derive(Debug)]
 pub(crate) enum Known {
     DiscordApiError(DiscordApiError),
+    RateLimited(RateLimited),
 }
 
 impl Known {
     fn inner_as_any(&self) -> &dyn Any {
         match *self {
             Known::DiscordApiError(ref e) => e,
+            Known::RateLimited(ref e) => e,
         }
     }
 }
 
-#[derive(Debug)]
-pub(crate) struct DiscordApiError {}
+impl From<DiscordApiError> for Known {
+    fn from(err: DiscordApiError) -> Self {
+        Known::DiscordApiError(err)
+    }
+}
+
+impl From<RateLimited> for Known {
+    fn from(err: RateLimited) -> Self {
+        Known::RateLimited(err)
+    }
+}
+
+/// The rejection of a request to Discord's API that was still rate limited once the REST
+/// client ran out of retries.
+#[derive(Debug, Clone, Copy)]
+pub struct RateLimited {
+    /// How long until the request can be made again.
+    pub retry_after: Duration,
+    /// Whether the limit applies to every request, rather than to the endpoint.
+    pub global: bool,
+}
+
+impl fmt::Display for RateLimited {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "rate limited by Discord, retry after {:?}",
+            self.retry_after
+        )
+    }
+}
 
 impl Rejection {
     fn known(known: Known) -> Self {
@@ -211,6 +261,30 @@ impl Rejection {
     pub fn is_not_found(&self) -> bool {
         matches!(self.reason, Reason::NotFound)
     }
+
+    /// Returns true if the invoking member lacks permissions required with
+    /// [`permission::require`](crate::permission::require).
+    pub fn is_missing_permissions(&self) -> bool {
+        self.find::<MissingPermissions>().is_some()
+    }
+
+    /// Returns true if the command was used again before the end of its
+    /// [`cooldown`](fn@crate::cooldown).
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn is_cooldown(&self) -> bool {
+        self.find::<OnCooldown>().is_some()
+    }
+
+    /// Returns true if a request made to Discord's API failed.
+    pub fn is_api_error(&self) -> bool {
+        self.find::<DiscordApiError>().is_some()
+    }
+
+    /// Returns true if a request made to Discord's API was rate limited, in which case
+    /// [`RateLimited`] tells how long until it can be made again.
+    pub fn is_rate_limited(&self) -> bool {
+        self.find::<RateLimited>().is_some()
+    }
 }
 
 impl<T: Reject> From<T> for Rejection {
@@ -220,6 +294,24 @@ impl<T: Reject> From<T> for Rejection {
     }
 }
 
+/// Rejects with the errors raised while handling an interaction, such as those of REST
+/// requests, so they can be returned from [`and_then`](crate::Filter::and_then) with `?`.
+impl From<Error> for Rejection {
+    fn from(err: Error) -> Rejection {
+        match err {
+            Error::Api(err) => known(err),
+            Error::RateLimited {
+                retry_after,
+                global,
+            } => known(RateLimited {
+                retry_after,
+                global,
+            }),
+            err => Rejection::custom(Box::new(err)),
+        }
+    }
+}
+
 impl From<Infallible> for Rejection {
     #[inline]
     fn from(infallible: Infallible) -> Rejection {
@@ -237,6 +329,17 @@ impl fmt::Debug for Rejection {
     }
 }
 
+impl fmt::Display for Rejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.reason {
+            Reason::NotFound => f.write_str("no filter matched the interaction"),
+            Reason::Other(ref other) => fmt::Display::fmt(other, f),
+        }
+    }
+}
+
+impl StdError for Rejection {}
+
 impl fmt::Debug for Reason {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match *self {
@@ -282,6 +385,50 @@ impl Rejections {
     }
 }
 
+impl fmt::Display for Rejections {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Rejections::Known(Known::DiscordApiError(ref e)) => fmt::Display::fmt(e, f),
+            Rejections::Known(Known::RateLimited(ref e)) => fmt::Display::fmt(e, f),
+            Rejections::Custom(ref e) => fmt_custom(&**e, f),
+            Rejections::Combined(ref a, ref b) => write!(f, "{}; {}", a, b),
+        }
+    }
+}
+
+/// Displays the custom rejections of this crate, and the errors it raises, with their
+/// `Display` implementation.
+fn fmt_custom(cause: &dyn Cause, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    macro_rules! display {
+        ($($(#[$attr:meta])* $ty:ty,)*) => {
+            $(
+                $(#[$attr])*
+                if let Some(e) = cause.downcast_ref::<$ty>() {
+                    return fmt::Display::fmt(e, f);
+                }
+            )*
+        };
+    }
+    display!(
+        Error,
+        MissingOption,
+        WrongOptionType,
+        InvalidOption,
+        MissingPermissions,
+        MissingRole,
+        GuildOnly,
+        ConcurrencyLimited,
+        #[cfg(not(target_arch = "wasm32"))]
+        TimedOut,
+        #[cfg(not(target_arch = "wasm32"))]
+        OnCooldown,
+        #[cfg(feature = "custom-id")]
+        CustomIdError,
+    );
+    // Other custom rejections are only required to be `Debug`.
+    fmt::Debug::fmt(cause, f)
+}
+
 mod sealed {
     use super::{Reason, Rejection, Rejections};
     use std::convert::Infallible;
@@ -375,3 +522,51 @@ mod sealed {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{CombineRejection, RateLimited, Reject, Rejection};
+    use crate::{errors::Error, options::MissingOption};
+
+    #[test]
+    fn test_rejection_display() {
+        assert_eq!(
+            super::not_found().to_string(),
+            "no filter matched the interaction"
+        );
+        let missing = super::custom(MissingOption {
+            name: "text".to_string(),
+        });
+        assert_eq!(missing.to_string(), "missing required option `text`");
+        assert!(!missing.is_api_error());
+
+        #[derive(Debug)]
+        struct Unknown;
+        impl Reject for Unknown {}
+        let combined = super::custom(Unknown).combine(super::custom(MissingOption {
+            name: "text".to_string(),
+        }));
+        assert_eq!(
+            combined.to_string(),
+            "Unknown; missing required option `text`"
+        );
+    }
+
+    #[test]
+    fn test_rejection_from_error() {
+        let rejection = Rejection::from(Error::RateLimited {
+            retry_after: Duration::from_secs(2),
+            global: true,
+        });
+        assert!(rejection.is_rate_limited());
+        let limit = rejection.find::<RateLimited>().unwrap();
+        assert_eq!(limit.retry_after, Duration::from_secs(2));
+        assert!(limit.global);
+
+        let rejection = Rejection::from(Error::Timeout);
+        assert!(matches!(rejection.find::<Error>(), Some(Error::Timeout)));
+        assert_eq!(rejection.to_string(), "HTTP request timed out");
+    }
+}
@@ -0,0 +1,447 @@
+//! Defines the `MessageBuilder` and `ModalBuilder`, which create the messages and modals sent
+//! in response to interactions.
+use thiserror::Error;
+
+use crate::model::{
+    component::Component,
+    embed::Embed,
+    message::{AllowedMentions, CreateMessage, FileUpload, MessageFlags, PartialAttachment},
+    modal::{Modal, TextInput},
+};
+
+/// The longest content a message can have, in characters.
+const MAX_CONTENT_LENGTH: usize = 2000;
+/// The most embeds a message can have.
+const MAX_EMBEDS: usize = 10;
+/// The most characters the embeds of a message can have altogether.
+const MAX_EMBED_LENGTH: usize = 6000;
+/// The most action rows a message can have, and the most text inputs of a modal.
+const MAX_ROWS: usize = 5;
+/// The most files a message can upload.
+const MAX_FILES: usize = 10;
+/// The longest title of a modal, or label of a text input, in characters.
+const MAX_LABEL_LENGTH: usize = 45;
+/// The longest placeholder of a text input, in characters.
+const MAX_PLACEHOLDER_LENGTH: usize = 100;
+/// The longest value of a text input, in characters.
+const MAX_INPUT_LENGTH: u16 = 4000;
+
+/// The ways a message can exceed Discord's limits.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum MessageError {
+    /// The content is longer than 2000 characters.
+    #[error("the content is {0} characters long, over the limit of 2000")]
+    ContentTooLong(usize),
+    /// The message has more than 10 embeds.
+    #[error("the message has {0} embeds, but at most 10 are allowed")]
+    TooManyEmbeds(usize),
+    /// The embeds have more than 6000 characters altogether.
+    #[error("the embeds have {0} characters, over the limit of 6000")]
+    EmbedsTooLong(usize),
+    /// The message has more than 5 action rows.
+    #[error("the message has {0} action rows, but at most 5 are allowed")]
+    TooManyRows(usize),
+    /// The message uploads more than 10 files.
+    #[error("the message has {0} files, but at most 10 are allowed")]
+    TooManyFiles(usize),
+}
+
+impl CreateMessage {
+    /// Check this message against Discord's limits on the content, embeds and components of
+    /// a message, which Discord otherwise only reports as an opaque form body error.
+    pub fn validate(&self) -> Result<(), MessageError> {
+        let content = self.content.as_deref().map_or(0, |c| c.chars().count());
+        if content > MAX_CONTENT_LENGTH {
+            return Err(MessageError::ContentTooLong(content));
+        }
+        if self.embeds.len() > MAX_EMBEDS {
+            return Err(MessageError::TooManyEmbeds(self.embeds.len()));
+        }
+        let embeds = self.embeds.iter().map(embed_length).sum();
+        if embeds > MAX_EMBED_LENGTH {
+            return Err(MessageError::EmbedsTooLong(embeds));
+        }
+        if self.components.len() > MAX_ROWS {
+            return Err(MessageError::TooManyRows(self.components.len()));
+        }
+        if self.files.len() > MAX_FILES {
+            return Err(MessageError::TooManyFiles(self.files.len()));
+        }
+        Ok(())
+    }
+}
+
+/// Returns the number of characters of an embed counting towards the limit of a message.
+fn embed_length(embed: &Embed) -> usize {
+    let len = |text: Option<&str>| text.map_or(0, |text| text.chars().count());
+    len(embed.title.as_deref())
+        + len(embed.description.as_deref())
+        + len(embed.footer.as_ref().map(|footer| footer.text.as_str()))
+        + len(embed.author.as_ref().map(|author| author.name.as_str()))
+        + embed
+            .fields
+            .iter()
+            .map(|field| len(Some(&field.name)) + len(Some(&field.value)))
+            .sum::<usize>()
+}
+
+/// Builds a message to reply, follow up or edit a reply with, combining its content, embeds,
+/// components, attachments and flags.
+///
+/// The message is checked against Discord's limits when it's built or sent.
+///
+/// # Example
+///
+/// ```no_run
+/// # use slash::{model::embed::Embed, MessageBuilder};
+/// # async fn run(context: slash::Context) -> slash::Result<()> {
+/// let message = MessageBuilder::new()
+///     .content("Here are the results:")
+///     .embed(Embed {
+///         title: Some("Results".to_string()),
+///         ..Default::default()
+///     })
+///     .ephemeral();
+/// context.reply(message).await
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MessageBuilder {
+    message: CreateMessage,
+}
+
+impl MessageBuilder {
+    /// Create an empty message.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the content of the message, up to 2000 characters.
+    pub fn content<S: Into<String>>(mut self, content: S) -> Self {
+        self.message.content = Some(content.into());
+        self
+    }
+
+    /// Add an embed to the message, up to 10.
+    pub fn embed(mut self, embed: Embed) -> Self {
+        self.message.embeds.push(embed);
+        self
+    }
+
+    /// Add several embeds to the message, up to 10 in total.
+    pub fn embeds<I: IntoIterator<Item = Embed>>(mut self, embeds: I) -> Self {
+        self.message.embeds.extend(embeds);
+        self
+    }
+
+    /// Add an action row with the given components to the message, up to 5 rows.
+    pub fn row(mut self, components: Vec<Component>) -> Self {
+        self.message
+            .components
+            .push(Component::action_row(components));
+        self
+    }
+
+    /// Add top-level components, such as action rows, to the message.
+    pub fn components<I: IntoIterator<Item = Component>>(mut self, components: I) -> Self {
+        self.message.components.extend(components);
+        self
+    }
+
+    /// Keep an existing attachment, such as one of the reply being edited. Files are uploaded
+    /// with [`file`](MessageBuilder::file) instead.
+    pub fn attachment(mut self, attachment: PartialAttachment) -> Self {
+        self.message.attachments.push(attachment);
+        self
+    }
+
+    /// Upload a file with the message, up to 10. Embeds can show an uploaded image with the
+    /// URL `attachment://filename`.
+    ///
+    /// ```no_run
+    /// # use slash::MessageBuilder;
+    /// # async fn run(context: slash::Context) -> slash::Result<()> {
+    /// let report = MessageBuilder::new()
+    ///     .content("Here is your report.")
+    ///     .file("report.csv", "name,score\nslash,100\n");
+    /// context.reply(report).await
+    /// # }
+    /// ```
+    pub fn file<S: Into<String>, B: Into<Vec<u8>>>(mut self, filename: S, data: B) -> Self {
+        let filename = filename.into();
+        self.message.attachments.push(PartialAttachment {
+            // Files are referred to by their index among the message's files.
+            id: (self.message.files.len() as u64).into(),
+            filename: Some(filename.clone()),
+            description: None,
+        });
+        self.message.files.push(FileUpload {
+            filename,
+            data: data.into(),
+        });
+        self
+    }
+
+    /// Set which mentions in the message notify their targets. Defaults to Discord's default
+    /// of all of them.
+    pub fn allowed_mentions(mut self, allowed_mentions: AllowedMentions) -> Self {
+        self.message.allowed_mentions = Some(allowed_mentions);
+        self
+    }
+
+    /// Set whether the message is read aloud with text-to-speech. Defaults to `false`.
+    pub fn tts(mut self, tts: bool) -> Self {
+        self.message.tts = tts;
+        self
+    }
+
+    /// Add flags to the message.
+    pub fn flags(mut self, flags: MessageFlags) -> Self {
+        self.message.flags = Some(self.message.flags.unwrap_or_default() | flags);
+        self
+    }
+
+    /// Only show the message to the user who invoked the interaction.
+    ///
+    /// Only interaction responses and followups can be ephemeral.
+    pub fn ephemeral(self) -> Self {
+        self.flags(MessageFlags::EPHEMERAL)
+    }
+
+    /// Don't preview the links in the message with embeds.
+    pub fn suppress_embeds(self) -> Self {
+        self.flags(MessageFlags::SUPPRESS_EMBEDS)
+    }
+
+    /// Build the message, checking it against Discord's limits.
+    pub fn build(self) -> Result<CreateMessage, MessageError> {
+        self.message.validate()?;
+        Ok(self.message)
+    }
+}
+
+impl From<MessageBuilder> for CreateMessage {
+    fn from(builder: MessageBuilder) -> Self {
+        builder.message
+    }
+}
+
+/// The ways a modal can exceed Discord's limits.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ModalError {
+    /// The modal has no text inputs, or more than 5.
+    #[error("the modal has {0} text inputs, but 1-5 are allowed")]
+    InputCount(usize),
+    /// The title of the modal is empty or longer than 45 characters.
+    #[error("the title of the modal must be 1-45 characters")]
+    InvalidTitle,
+    /// The label of a text input is empty or longer than 45 characters.
+    #[error("the label of `{0}` must be 1-45 characters")]
+    InvalidLabel(String),
+    /// The placeholder of a text input is longer than 100 characters.
+    #[error("the placeholder of `{0}` is over the limit of 100 characters")]
+    PlaceholderTooLong(String),
+    /// The length limits of a text input are out of range, or the minimum is above the
+    /// maximum.
+    #[error(
+        "the length limits of `{0}` must be within 0-4000, with the minimum below the maximum"
+    )]
+    InvalidLength(String),
+    /// The pre-filled value of a text input is longer than its maximum length.
+    #[error("the value of `{0}` is longer than its maximum length")]
+    ValueTooLong(String),
+}
+
+impl Modal {
+    /// Check this modal against Discord's limits on its title and text inputs, which Discord
+    /// otherwise only reports when the modal is shown.
+    pub fn validate(&self) -> Result<(), ModalError> {
+        if !(1..=MAX_ROWS).contains(&self.inputs.len()) {
+            return Err(ModalError::InputCount(self.inputs.len()));
+        }
+        if !(1..=MAX_LABEL_LENGTH).contains(&self.title.chars().count()) {
+            return Err(ModalError::InvalidTitle);
+        }
+        self.inputs.iter().try_for_each(validate_input)
+    }
+}
+
+fn validate_input(input: &TextInput) -> Result<(), ModalError> {
+    let id = || input.custom_id.clone();
+    if !(1..=MAX_LABEL_LENGTH).contains(&input.label.chars().count()) {
+        return Err(ModalError::InvalidLabel(id()));
+    }
+    let placeholder = input
+        .placeholder
+        .as_deref()
+        .map_or(0, |p| p.chars().count());
+    if placeholder > MAX_PLACEHOLDER_LENGTH {
+        return Err(ModalError::PlaceholderTooLong(id()));
+    }
+    let min = input.min_length.unwrap_or(0);
+    let max = input.max_length.unwrap_or(MAX_INPUT_LENGTH);
+    if min > MAX_INPUT_LENGTH || !(1..=MAX_INPUT_LENGTH).contains(&max) || min > max {
+        return Err(ModalError::InvalidLength(id()));
+    }
+    let value = input.value.as_deref().map_or(0, |v| v.chars().count());
+    if value > usize::from(max) {
+        return Err(ModalError::ValueTooLong(id()));
+    }
+    Ok(())
+}
+
+/// Builds a modal to show in response to an interaction, with each text input on its own
+/// row.
+///
+/// # Example
+///
+/// ```no_run
+/// # use slash::{model::modal::TextInput, ModalBuilder};
+/// # async fn run(context: slash::Context) -> slash::Result<()> {
+/// let modal = ModalBuilder::new("feedback", "Send feedback")
+///     .input(TextInput::short("subject", "Subject").max_length(100))
+///     .input(TextInput::paragraph("details", "Details").required(false))
+///     .build()?;
+/// context.show_modal(modal).await
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ModalBuilder {
+    modal: Modal,
+}
+
+impl ModalBuilder {
+    /// Create a modal without any text inputs.
+    pub fn new<S: Into<String>>(custom_id: S, title: S) -> Self {
+        Self {
+            modal: Modal {
+                custom_id: custom_id.into(),
+                title: title.into(),
+                inputs: vec![],
+            },
+        }
+    }
+
+    /// Add a text input to the modal, on its own row, up to 5.
+    pub fn input(mut self, input: TextInput) -> Self {
+        self.modal.inputs.push(input);
+        self
+    }
+
+    /// Build the modal, checking it against Discord's limits.
+    pub fn build(self) -> Result<Modal, ModalError> {
+        self.modal.validate()?;
+        Ok(self.modal)
+    }
+}
+
+impl From<ModalBuilder> for Modal {
+    fn from(builder: ModalBuilder) -> Self {
+        builder.modal
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{MessageBuilder, MessageError, ModalBuilder, ModalError};
+    use crate::model::{
+        component::{ButtonStyle, Component},
+        embed::Embed,
+        message::MessageFlags,
+        modal::TextInput,
+    };
+
+    #[test]
+    fn test_flags_combine() {
+        let message = MessageBuilder::new()
+            .content("https://discord.com")
+            .ephemeral()
+            .suppress_embeds()
+            .build()
+            .unwrap();
+        assert_eq!(
+            message.flags,
+            Some(MessageFlags::EPHEMERAL | MessageFlags::SUPPRESS_EMBEDS)
+        );
+    }
+
+    #[test]
+    fn test_limits() {
+        let embed = |description: String| Embed {
+            description: Some(description),
+            ..Default::default()
+        };
+        let too_many = MessageBuilder::new().embeds(vec![Embed::default(); 11]);
+        assert_eq!(
+            too_many.build().unwrap_err(),
+            MessageError::TooManyEmbeds(11)
+        );
+
+        let too_long = MessageBuilder::new()
+            .embed(embed("a".repeat(4000)))
+            .embed(embed("a".repeat(2001)));
+        assert_eq!(
+            too_long.build().unwrap_err(),
+            MessageError::EmbedsTooLong(6001)
+        );
+
+        let button = || Component::button(ButtonStyle::Primary, "ok", "OK");
+        let rows = (0..6).fold(MessageBuilder::new(), |builder, _| {
+            builder.row(vec![button()])
+        });
+        assert_eq!(rows.build().unwrap_err(), MessageError::TooManyRows(6));
+    }
+
+    #[test]
+    fn test_files_are_attached() {
+        let message = MessageBuilder::new()
+            .file("a.txt", "a")
+            .file("b.png", vec![0, 1])
+            .build()
+            .unwrap();
+        assert_eq!(message.files.len(), 2);
+        assert_eq!(
+            serde_json::to_value(&message.attachments).unwrap(),
+            json!([
+                { "id": 0, "filename": "a.txt" },
+                { "id": 1, "filename": "b.png" }
+            ])
+        );
+
+        let too_many = (0..11).fold(MessageBuilder::new(), |builder, _| {
+            builder.file("a.txt", "a")
+        });
+        assert_eq!(
+            too_many.build().unwrap_err(),
+            MessageError::TooManyFiles(11)
+        );
+    }
+
+    #[test]
+    fn test_modal_limits() {
+        let input = || TextInput::short("name", "Name");
+        let modal = |input: TextInput| ModalBuilder::new("form", "Form").input(input).build();
+        assert!(modal(input().min_length(4000)).is_ok());
+        assert_eq!(
+            modal(input().min_length(10).max_length(5)).unwrap_err(),
+            ModalError::InvalidLength("name".to_string())
+        );
+        assert_eq!(
+            modal(input().max_length(4001)).unwrap_err(),
+            ModalError::InvalidLength("name".to_string())
+        );
+        assert_eq!(
+            modal(input().max_length(3).value("four")).unwrap_err(),
+            ModalError::ValueTooLong("name".to_string())
+        );
+
+        let empty = ModalBuilder::new("form", "Form");
+        assert_eq!(empty.build().unwrap_err(), ModalError::InputCount(0));
+        let full = (0..6).fold(ModalBuilder::new("form", "Form"), |modal, _| {
+            modal.input(input())
+        });
+        assert_eq!(full.build().unwrap_err(), ModalError::InputCount(6));
+    }
+}
@@ -1,9 +1,23 @@
 use const_format::formatcp;
+use reqwest::Method;
 
-use crate::{model::snowflake::Snowflake, rest::API_ENDPOINT};
+use crate::{
+    errors::Result,
+    model::{
+        application::{Application, RoleConnectionMetadata},
+        command::ApplicationCommand,
+        snowflake::Snowflake,
+    },
+    rest::{Client, API_ENDPOINT},
+};
 
 const APPLICATION_API_ENDPOINT: &str = formatcp!("{}/applications", API_ENDPOINT);
 
+/// Create a URL to look up the application of the current bot.
+pub fn me() -> String {
+    format!("{}/@me", APPLICATION_API_ENDPOINT)
+}
+
 /// Create a URL to look up an application by its ID.
 pub fn id<S: Into<Snowflake>>(application_id: S) -> String {
     format!("{}/{}", APPLICATION_API_ENDPOINT, application_id.into())
@@ -32,3 +46,84 @@ pub fn guild_commands<S: Into<Snowflake>>(application_id: S, guild_id: S) -> Str
         guild_id.into()
     )
 }
+
+/// Create a URL to look up the role connection metadata of an application's linked roles.
+pub fn role_connection_metadata<S: Into<Snowflake>>(application_id: S) -> String {
+    format!("{}/role-connections/metadata", id(application_id))
+}
+
+impl Client {
+    /// Fetch the application of the current bot.
+    pub async fn get_current_application(&self) -> Result<Application> {
+        self.send(self.request(Method::GET, self::me())).await
+    }
+
+    /// Fetch all of an application's global commands.
+    pub async fn get_global_commands<S: Into<Snowflake>>(
+        &self,
+        application_id: S,
+    ) -> Result<Vec<ApplicationCommand>> {
+        self.send(self.request(Method::GET, self::commands(application_id)))
+            .await
+    }
+
+    /// Fetch all of an application's commands in a guild.
+    pub async fn get_guild_commands<S: Into<Snowflake>>(
+        &self,
+        application_id: S,
+        guild_id: S,
+    ) -> Result<Vec<ApplicationCommand>> {
+        self.send(self.request(Method::GET, self::guild_commands(application_id, guild_id)))
+            .await
+    }
+
+    /// Replace all of an application's global commands, returning the registered commands.
+    pub async fn bulk_overwrite_global_commands<S: Into<Snowflake>>(
+        &self,
+        application_id: S,
+        commands: &[ApplicationCommand],
+    ) -> Result<Vec<ApplicationCommand>> {
+        self.send(
+            self.request(Method::PUT, self::commands(application_id))
+                .json(commands),
+        )
+        .await
+    }
+
+    /// Replace all of an application's commands in a guild, returning the registered commands.
+    pub async fn bulk_overwrite_guild_commands<S: Into<Snowflake>>(
+        &self,
+        application_id: S,
+        guild_id: S,
+        commands: &[ApplicationCommand],
+    ) -> Result<Vec<ApplicationCommand>> {
+        self.send(
+            self.request(Method::PUT, self::guild_commands(application_id, guild_id))
+                .json(commands),
+        )
+        .await
+    }
+
+    /// Fetch the role connection metadata of an application's linked roles.
+    pub async fn get_role_connection_metadata<S: Into<Snowflake>>(
+        &self,
+        application_id: S,
+    ) -> Result<Vec<RoleConnectionMetadata>> {
+        self.send(self.request(Method::GET, self::role_connection_metadata(application_id)))
+            .await
+    }
+
+    /// Replace the role connection metadata of an application's linked roles, returning the
+    /// updated metadata. An application can have at most 5 metadata fields.
+    pub async fn update_role_connection_metadata<S: Into<Snowflake>>(
+        &self,
+        application_id: S,
+        metadata: &[RoleConnectionMetadata],
+    ) -> Result<Vec<RoleConnectionMetadata>> {
+        self.send(
+            self.request(Method::PUT, self::role_connection_metadata(application_id))
+                .json(metadata),
+        )
+        .await
+    }
+}
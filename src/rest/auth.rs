@@ -0,0 +1,47 @@
+use std::fmt;
+
+/// A token authenticating requests to the REST API, applying the matching `Authorization`
+/// prefix.
+#[derive(Clone, PartialEq, Eq)]
+pub enum AuthToken {
+    /// A bot token, which can use every endpoint available to the bot.
+    Bot(String),
+    /// An OAuth2 bearer token, which can only use the endpoints its scopes allow, such as one
+    /// from [`Client::client_credentials_grant`](super::Client::client_credentials_grant).
+    ///
+    /// Endpoints that are only available to bots fail with
+    /// [`Error::MissingConfig`](crate::Error::MissingConfig) before sending any request.
+    Bearer(String),
+}
+
+impl AuthToken {
+    /// Returns the token, without its prefix.
+    pub fn token(&self) -> &str {
+        match self {
+            AuthToken::Bot(token) | AuthToken::Bearer(token) => token,
+        }
+    }
+
+    /// Whether this is a bot token.
+    pub fn is_bot(&self) -> bool {
+        matches!(self, AuthToken::Bot(_))
+    }
+
+    /// Returns the value of the `Authorization` header authenticating with this token.
+    pub(crate) fn header(&self) -> String {
+        match self {
+            AuthToken::Bot(token) => format!("Bot {}", token),
+            AuthToken::Bearer(token) => format!("Bearer {}", token),
+        }
+    }
+}
+
+impl fmt::Debug for AuthToken {
+    // Leaves out the token, so that it isn't logged by accident.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AuthToken::Bot(_) => f.write_str("Bot(..)"),
+            AuthToken::Bearer(_) => f.write_str("Bearer(..)"),
+        }
+    }
+}
@@ -0,0 +1,199 @@
+use const_format::formatcp;
+use reqwest::{Method, Url};
+use serde::Serialize;
+
+use crate::{
+    errors::Result,
+    model::{
+        channel::{AutoArchiveDuration, Channel},
+        message::{CreateMessage, EditMessage, Message, ReactionEmoji, ReactionQuery},
+        snowflake::Snowflake,
+        user::User,
+    },
+    rest::{multipart, Client, API_ENDPOINT},
+};
+
+const CHANNEL_API_ENDPOINT: &str = formatcp!("{}/channels", API_ENDPOINT);
+
+/// Create a URL to look up a channel by its ID.
+pub fn id<S: Into<Snowflake>>(channel_id: S) -> String {
+    format!("{}/{}", CHANNEL_API_ENDPOINT, channel_id.into())
+}
+
+/// Create a URL to look up a channel's messages.
+pub fn messages<S: Into<Snowflake>>(channel_id: S) -> String {
+    format!("{}/messages", id(channel_id))
+}
+
+/// Create a URL to look up a message in a channel.
+pub fn message<S: Into<Snowflake>>(channel_id: S, message_id: S) -> String {
+    format!("{}/{}", messages(channel_id), message_id.into())
+}
+
+/// Create a URL to look up the reactions to a message with an emoji, which is percent-encoded.
+pub fn reactions<S: Into<Snowflake>>(
+    channel_id: S,
+    message_id: S,
+    emoji: &ReactionEmoji,
+) -> String {
+    let mut url = Url::parse(&format!("{}/reactions", message(channel_id, message_id)))
+        .expect("the API endpoint is a valid URL");
+    url.path_segments_mut()
+        .expect("the API endpoint has a path")
+        .push(&emoji.to_string());
+    url.into()
+}
+
+/// Create a URL to look up the bot's own reaction to a message with an emoji.
+pub fn own_reaction<S: Into<Snowflake>>(
+    channel_id: S,
+    message_id: S,
+    emoji: &ReactionEmoji,
+) -> String {
+    format!("{}/@me", reactions(channel_id, message_id, emoji))
+}
+
+/// Create a URL to start a thread from a message.
+pub fn message_threads<S: Into<Snowflake>>(channel_id: S, message_id: S) -> String {
+    format!("{}/threads", message(channel_id, message_id))
+}
+
+impl Client {
+    /// Send a message in a channel, returning the sent message.
+    pub async fn create_message<S: Into<Snowflake>>(
+        &self,
+        channel_id: S,
+        message: &CreateMessage,
+    ) -> Result<Message> {
+        self.require_bot()?;
+        let request = self.request(Method::POST, messages(channel_id));
+        self.send(multipart::body(request, message, &message.files)?)
+            .await
+    }
+
+    /// Fetch a message in a channel.
+    pub async fn get_message<S: Into<Snowflake>>(
+        &self,
+        channel_id: S,
+        message_id: S,
+    ) -> Result<Message> {
+        self.require_bot()?;
+        self.send(self.request(Method::GET, message(channel_id, message_id)))
+            .await
+    }
+
+    /// Edit a message sent by the bot, returning the edited message.
+    pub async fn edit_message<S: Into<Snowflake>>(
+        &self,
+        channel_id: S,
+        message_id: S,
+        changes: &EditMessage,
+    ) -> Result<Message> {
+        self.require_bot()?;
+        let request = self.request(Method::PATCH, message(channel_id, message_id));
+        self.send(multipart::body(request, changes, &changes.files)?)
+            .await
+    }
+
+    /// Delete a message in a channel.
+    pub async fn delete_message<S: Into<Snowflake>>(
+        &self,
+        channel_id: S,
+        message_id: S,
+    ) -> Result<()> {
+        self.require_bot()?;
+        self.send_empty(self.request(Method::DELETE, message(channel_id, message_id)))
+            .await?;
+        Ok(())
+    }
+
+    /// Start a public thread from a message, returning the thread. Threads started from a
+    /// message in an announcement channel are announcement threads.
+    ///
+    /// Without an `auto_archive` duration, the thread uses the default of the channel.
+    pub async fn start_thread_from_message<S: Into<Snowflake>>(
+        &self,
+        channel_id: S,
+        message_id: S,
+        name: &str,
+        auto_archive: Option<AutoArchiveDuration>,
+    ) -> Result<Channel> {
+        self.require_bot()?;
+
+        #[derive(Serialize)]
+        struct StartThread<'a> {
+            name: &'a str,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            auto_archive_duration: Option<AutoArchiveDuration>,
+        }
+
+        self.send(
+            self.request(Method::POST, message_threads(channel_id, message_id))
+                .json(&StartThread {
+                    name,
+                    auto_archive_duration: auto_archive,
+                }),
+        )
+        .await
+    }
+
+    /// React to a message with an emoji, such as `'👍'` or a custom emoji.
+    pub async fn create_reaction<S, E>(&self, channel_id: S, message_id: S, emoji: E) -> Result<()>
+    where
+        S: Into<Snowflake>,
+        E: Into<ReactionEmoji>,
+    {
+        self.require_bot()?;
+        let url = own_reaction(channel_id, message_id, &emoji.into());
+        self.send_empty(self.request(Method::PUT, url)).await?;
+        Ok(())
+    }
+
+    /// Remove a reaction the bot made to a message.
+    pub async fn delete_own_reaction<S, E>(
+        &self,
+        channel_id: S,
+        message_id: S,
+        emoji: E,
+    ) -> Result<()>
+    where
+        S: Into<Snowflake>,
+        E: Into<ReactionEmoji>,
+    {
+        self.require_bot()?;
+        let url = own_reaction(channel_id, message_id, &emoji.into());
+        self.send_empty(self.request(Method::DELETE, url)).await?;
+        Ok(())
+    }
+
+    /// Fetch a page of the users who reacted to a message with an emoji.
+    pub async fn get_reactions<S, E>(
+        &self,
+        channel_id: S,
+        message_id: S,
+        emoji: E,
+        query: &ReactionQuery,
+    ) -> Result<Vec<User>>
+    where
+        S: Into<Snowflake>,
+        E: Into<ReactionEmoji>,
+    {
+        self.require_bot()?;
+        let url = reactions(channel_id, message_id, &emoji.into());
+        self.send(self.request(Method::GET, url).query(query)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{own_reaction, reactions};
+    use crate::model::message::ReactionEmoji;
+
+    #[test]
+    fn test_reaction_urls() {
+        let url = own_reaction(1, 2, &ReactionEmoji::from('👍'));
+        assert!(url.ends_with("/channels/1/messages/2/reactions/%F0%9F%91%8D/@me"));
+        let url = reactions(1, 2, &ReactionEmoji::custom("party/parrot", 3));
+        assert!(url.ends_with("/reactions/party%2Fparrot:3"));
+    }
+}
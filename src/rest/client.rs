@@ -0,0 +1,525 @@
+use std::{fmt, sync::Arc, time::Duration};
+
+use reqwest::{
+    header::{self, HeaderMap, HeaderValue},
+    Method, Request, RequestBuilder, Response, StatusCode,
+};
+use serde::{de::DeserializeOwned, Deserialize};
+
+use crate::{
+    backoff::Backoff,
+    errors::{DiscordApiError, Error, Result},
+    model,
+    rest::{
+        queue::{Priority, RequestQueue},
+        AuthToken, API_ENDPOINT,
+    },
+    runtime,
+};
+
+/// The number of times a failed request is retried, by default.
+const DEFAULT_MAX_RETRIES: u32 = 3;
+
+/// The longest a rate limited request waits to be retried, by default.
+const DEFAULT_MAX_RETRY_AFTER: Duration = Duration::from_secs(10);
+
+/// The number of requests in flight at once, by default: as many as Discord's global rate
+/// limit allows per second.
+const DEFAULT_MAX_CONCURRENT_REQUESTS: usize = 50;
+
+/// The `User-Agent` sent by default, in the format Discord requires.
+const DEFAULT_USER_AGENT: &str = concat!(
+    "DiscordBot (https://github.com/dysnomiarpg/slash, ",
+    env!("CARGO_PKG_VERSION"),
+    ")"
+);
+
+/// A callback deciding whether a failed request should be retried.
+type RetryClassifier = Arc<dyn Fn(&Error) -> bool + Send + Sync>;
+
+/// Whether a request failed for a reason that may not happen again: Discord responded with a
+/// server error or rate limited it, the connection failed, or the request timed out. Other
+/// client errors, such as invalid requests or missing permissions, are never retried.
+///
+/// This is the default retry classifier. Whatever the classifier, requests that aren't
+/// idempotent, such as those creating messages, are only retried if they can't have reached
+/// Discord, unless [`ClientBuilder::retry_non_idempotent`] is set.
+pub fn is_transient(error: &Error) -> bool {
+    match error {
+        Error::Api(err) => err.status >= 500,
+        Error::RateLimited { .. } => true,
+        #[cfg(not(target_arch = "wasm32"))]
+        Error::Http(err) => err.is_connect() || err.is_timeout(),
+        #[cfg(target_arch = "wasm32")]
+        Error::Http(err) => err.is_request() || err.is_timeout(),
+        Error::Timeout => true,
+        _ => false,
+    }
+}
+
+/// Whether a failed request can't have been handled by Discord, so can be sent again without
+/// risking doing twice what it does: it was rate limited, or the connection failed.
+pub(crate) fn unsent(error: &Error) -> bool {
+    match error {
+        Error::RateLimited { .. } => true,
+        #[cfg(not(target_arch = "wasm32"))]
+        Error::Http(err) => err.is_connect(),
+        _ => false,
+    }
+}
+
+/// Whether sending a request more than once does the same as sending it once.
+fn is_idempotent(method: &Method) -> bool {
+    !matches!(*method, Method::POST)
+}
+
+/// Configures a REST [`Client`].
+pub struct ClientBuilder {
+    auth: Option<AuthToken>,
+    timeout: Option<Duration>,
+    max_retries: u32,
+    retry_if: RetryClassifier,
+    retry_non_idempotent: bool,
+    max_retry_after: Duration,
+    backoff: Backoff,
+    api_base: Option<String>,
+    max_concurrent_requests: usize,
+    #[cfg(not(target_arch = "wasm32"))]
+    proxies: Vec<reqwest::Proxy>,
+    user_agent: HeaderValue,
+    headers: HeaderMap,
+}
+
+impl ClientBuilder {
+    /// Set the bot token to authenticate with. Without one, only the endpoints that don't need
+    /// it can be used, such as responding to interactions.
+    pub fn token<S: AsRef<str>>(self, token: S) -> Self {
+        self.auth(AuthToken::Bot(token.as_ref().to_string()))
+    }
+    /// Set an OAuth2 bearer token to authenticate with instead of a bot token, such as one
+    /// from [`Client::client_credentials_grant`].
+    pub fn bearer_token<S: AsRef<str>>(self, token: S) -> Self {
+        self.auth(AuthToken::Bearer(token.as_ref().to_string()))
+    }
+    /// Set the token to authenticate with.
+    pub fn auth(mut self, auth: AuthToken) -> Self {
+        self.auth = Some(auth);
+        self
+    }
+    /// Set how long each attempt at a request may take before it fails with
+    /// [`Error::Timeout`]. Defaults to no timeout.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+    /// Set how many times a failed request is retried. Defaults to 3.
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+    /// Set which failed requests are retried. Defaults to [`is_transient`].
+    pub fn retry_if<F>(mut self, classifier: F) -> Self
+    where
+        F: Fn(&Error) -> bool + Send + Sync + 'static,
+    {
+        self.retry_if = Arc::new(classifier);
+        self
+    }
+    /// Retry `POST` requests that may have reached Discord, such as those that timed out or
+    /// failed with a server error. Defaults to false, since retrying them may create a
+    /// message twice or respond to an interaction that was already responded to.
+    pub fn retry_non_idempotent(mut self, retry: bool) -> Self {
+        self.retry_non_idempotent = retry;
+        self
+    }
+    /// Set the longest a rate limited request waits to be retried. Requests Discord asks to
+    /// wait longer for fail with [`Error::RateLimited`] instead. Defaults to 10 seconds.
+    pub fn max_retry_after(mut self, max_retry_after: Duration) -> Self {
+        self.max_retry_after = max_retry_after;
+        self
+    }
+    /// Set how long to wait before retrying a failed request. Defaults to
+    /// [`Backoff::default`].
+    pub fn backoff(mut self, backoff: Backoff) -> Self {
+        self.backoff = backoff;
+        self
+    }
+    /// Send requests to the given base URL instead of `https://discordapp.com/api/v9`, such
+    /// as a rate limiting proxy shared by several processes. The URL should include the API
+    /// version, like `http://localhost:3000/api/v9`.
+    ///
+    /// The client doesn't rate limit requests itself, so it leaves rate limiting entirely to
+    /// the proxy. Consider lowering [`ClientBuilder::max_retries`] if the proxy already
+    /// retries failed requests.
+    pub fn api_base<S: Into<String>>(mut self, url: S) -> Self {
+        self.api_base = Some(url.into().trim_end_matches('/').to_string());
+        self
+    }
+    /// Limit how many requests are in flight at once. Once the limit is reached, requests wait
+    /// in a queue where responses and followups to interactions, which must be sent within 3
+    /// seconds, go before background requests such as registering commands. Defaults to 50,
+    /// the requests Discord's global rate limit allows per second.
+    ///
+    /// This only orders requests: rate limits are still left to Discord, and requests it rate
+    /// limits are retried as set with [`ClientBuilder::max_retry_after`].
+    pub fn max_concurrent_requests(mut self, limit: usize) -> Self {
+        self.max_concurrent_requests = limit;
+        self
+    }
+    /// Route requests through an HTTP(S) or SOCKS proxy. Can be called more than once, in
+    /// which case the first proxy matching a request is used.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn proxy(mut self, proxy: reqwest::Proxy) -> Self {
+        self.proxies.push(proxy);
+        self
+    }
+    /// Set the `User-Agent` sent with every request. Discord requires it to look like
+    /// `DiscordBot ($url, $version)`, and defaults to this library's URL and version.
+    pub fn user_agent(mut self, user_agent: HeaderValue) -> Self {
+        self.user_agent = user_agent;
+        self
+    }
+    /// Send the given headers with every request, such as the credentials of an API proxy.
+    /// Can be called more than once, in which case later headers replace earlier ones with
+    /// the same name.
+    pub fn default_headers(mut self, headers: HeaderMap) -> Self {
+        self.headers.extend(headers);
+        self
+    }
+    /// Create the client.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the TLS backend can't be initialized, like [`reqwest::Client::new`].
+    pub fn build(self) -> Client {
+        #[allow(unused_mut)]
+        let mut http = reqwest::Client::builder()
+            .user_agent(self.user_agent)
+            .default_headers(self.headers);
+        #[cfg(not(target_arch = "wasm32"))]
+        for proxy in self.proxies {
+            http = http.proxy(proxy);
+        }
+        Client {
+            http: http.build().expect("failed to initialize the HTTP client"),
+            auth: self.auth,
+            timeout: self.timeout,
+            max_retries: self.max_retries,
+            retry_if: self.retry_if,
+            retry_non_idempotent: self.retry_non_idempotent,
+            max_retry_after: self.max_retry_after,
+            backoff: self.backoff,
+            api_base: self.api_base.map(Arc::from),
+            queue: Arc::new(RequestQueue::new(self.max_concurrent_requests)),
+        }
+    }
+}
+
+impl fmt::Debug for ClientBuilder {
+    // Leaves out the default headers, so that credentials aren't logged by accident.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ClientBuilder")
+            .field("auth", &self.auth)
+            .field("timeout", &self.timeout)
+            .field("max_retries", &self.max_retries)
+            .field("retry_non_idempotent", &self.retry_non_idempotent)
+            .field("max_retry_after", &self.max_retry_after)
+            .field("backoff", &self.backoff)
+            .field("api_base", &self.api_base)
+            .field("max_concurrent_requests", &self.max_concurrent_requests)
+            .field("user_agent", &self.user_agent)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A client for the Discord REST API.
+#[derive(Clone)]
+pub struct Client {
+    http: reqwest::Client,
+    auth: Option<AuthToken>,
+    timeout: Option<Duration>,
+    max_retries: u32,
+    retry_if: RetryClassifier,
+    retry_non_idempotent: bool,
+    max_retry_after: Duration,
+    backoff: Backoff,
+    api_base: Option<Arc<str>>,
+    queue: Arc<RequestQueue>,
+}
+
+impl Client {
+    /// Start configuring a client.
+    pub fn builder() -> ClientBuilder {
+        ClientBuilder {
+            auth: None,
+            timeout: None,
+            max_retries: DEFAULT_MAX_RETRIES,
+            retry_if: Arc::new(is_transient),
+            retry_non_idempotent: false,
+            max_retry_after: DEFAULT_MAX_RETRY_AFTER,
+            backoff: Backoff::default(),
+            api_base: None,
+            max_concurrent_requests: DEFAULT_MAX_CONCURRENT_REQUESTS,
+            #[cfg(not(target_arch = "wasm32"))]
+            proxies: Vec::new(),
+            user_agent: HeaderValue::from_static(DEFAULT_USER_AGENT),
+            headers: HeaderMap::new(),
+        }
+    }
+
+    /// Create a new client authenticating with the given bot token.
+    pub fn new<S: AsRef<str>>(token: S) -> Self {
+        Self::builder().token(token).build()
+    }
+
+    /// Create a new client without a bot token, which can only use the endpoints that don't
+    /// need one, such as responding to interactions.
+    pub fn unauthenticated() -> Self {
+        Self::builder().build()
+    }
+
+    /// Start building a request to the given URL.
+    pub(crate) fn request(&self, method: Method, url: String) -> RequestBuilder {
+        let request = self.unauthenticated_request(method, url);
+        match self.auth {
+            Some(ref auth) => request.header(header::AUTHORIZATION, auth.header()),
+            None => request,
+        }
+    }
+
+    /// Returns the token the client authenticates with, if any.
+    pub fn auth(&self) -> Option<&AuthToken> {
+        self.auth.as_ref()
+    }
+
+    /// Fails unless the client authenticates with a bot token, for endpoints that can't be used
+    /// with a bearer token.
+    pub(crate) fn require_bot(&self) -> Result<()> {
+        match self.auth {
+            Some(ref auth) if auth.is_bot() => Ok(()),
+            _ => Err(Error::MissingConfig("bot token")),
+        }
+    }
+
+    /// Start building a request to the given URL, without the client's credentials.
+    pub(crate) fn unauthenticated_request(&self, method: Method, url: String) -> RequestBuilder {
+        self.http.request(method, self.url(url))
+    }
+
+    /// Point a URL created by the endpoint functions at the configured API base, if any.
+    fn url(&self, url: String) -> String {
+        match (&self.api_base, url.strip_prefix(API_ENDPOINT)) {
+            (Some(base), Some(path)) => format!("{}{}", base, path),
+            _ => url,
+        }
+    }
+
+    /// Send a request, deserializing the response body.
+    pub(crate) async fn send<T: DeserializeOwned>(&self, request: RequestBuilder) -> Result<T> {
+        let response = self.send_empty(request).await?;
+        Ok(model::from_slice(&response.bytes().await?)?)
+    }
+
+    /// Send a request, retrying it if it fails for a reason the retry classifier accepts and
+    /// sending it again is safe.
+    pub(crate) async fn send_empty(&self, request: RequestBuilder) -> Result<Response> {
+        let mut request = request.build()?;
+        let priority = Priority::of(request.url());
+        let method = request.method().clone();
+        let mut retries = 0;
+        loop {
+            // Requests with streamed bodies can't be cloned, so are never retried.
+            let retry = if retries < self.max_retries {
+                request.try_clone()
+            } else {
+                None
+            };
+            let error = match self.attempt(request, priority).await {
+                Ok(response) => return Ok(response),
+                Err(err) => err,
+            };
+            match retry {
+                Some(retry) if self.should_retry(&method, &error) => {
+                    retries += 1;
+                    let delay = match error {
+                        Error::RateLimited { retry_after, .. } => retry_after,
+                        _ => self.backoff.delay(retries),
+                    };
+                    tracing::debug!(error = %error, retries, ?delay, "retrying failed request");
+                    runtime::sleep(delay).await;
+                    request = retry;
+                }
+                _ => return Err(error),
+            }
+        }
+    }
+
+    /// Whether a request that failed with the given error should be retried.
+    fn should_retry(&self, method: &Method, error: &Error) -> bool {
+        if let Error::RateLimited { retry_after, .. } = *error {
+            if retry_after > self.max_retry_after {
+                return false;
+            }
+        }
+        (self.retry_if)(error)
+            && (self.retry_non_idempotent || is_idempotent(method) || unsent(error))
+    }
+
+    /// Send a request once, converting any error response into a [`DiscordApiError`], or
+    /// [`Error::RateLimited`] if it was rate limited.
+    async fn attempt(&self, request: Request, priority: Priority) -> Result<Response> {
+        let _slot = self.queue.acquire(priority).await;
+        let send = self.http.execute(request);
+        let response = match self.timeout {
+            Some(timeout) => runtime::timeout(timeout, send)
+                .await
+                .ok_or(Error::Timeout)??,
+            None => send.await?,
+        };
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response);
+        }
+        let body = response.bytes().await?;
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            if let Some(error) = rate_limited(&body) {
+                return Err(error);
+            }
+        }
+        let mut error = serde_json::from_slice(&body).unwrap_or_else(|_| DiscordApiError {
+            status: 0,
+            code: 0,
+            message: String::from_utf8_lossy(&body).into_owned(),
+            errors: None,
+        });
+        error.status = status.as_u16();
+        Err(error.into())
+    }
+}
+
+/// Read the body of a rate limited response. Responses without one, such as those of
+/// Cloudflare bans, are left to be reported as API errors.
+fn rate_limited(body: &[u8]) -> Option<Error> {
+    #[derive(Deserialize)]
+    struct RateLimit {
+        retry_after: f64,
+        #[serde(default)]
+        global: bool,
+    }
+
+    let limit: RateLimit = serde_json::from_slice(body).ok()?;
+    Some(Error::RateLimited {
+        retry_after: Duration::try_from_secs_f64(limit.retry_after).unwrap_or_default(),
+        global: limit.global,
+    })
+}
+
+impl fmt::Debug for Client {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Client")
+            .field("auth", &self.auth)
+            .field("timeout", &self.timeout)
+            .field("max_retries", &self.max_retries)
+            .field("retry_non_idempotent", &self.retry_non_idempotent)
+            .field("max_retry_after", &self.max_retry_after)
+            .field("backoff", &self.backoff)
+            .field("api_base", &self.api_base)
+            .field("max_concurrent_requests", &self.queue.limit())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use reqwest::Method;
+
+    use super::{is_transient, rate_limited, Client};
+    use crate::{
+        errors::{DiscordApiError, Error},
+        rest::users,
+    };
+
+    fn api_error(status: u16) -> Error {
+        DiscordApiError {
+            status,
+            code: 0,
+            message: String::new(),
+            errors: None,
+        }
+        .into()
+    }
+
+    #[test]
+    fn test_is_transient() {
+        assert!(is_transient(&api_error(502)));
+        assert!(is_transient(&Error::Timeout));
+        assert!(!is_transient(&api_error(400)));
+        assert!(!is_transient(&api_error(403)));
+        assert!(!is_transient(&Error::MissingConfig("token")));
+    }
+
+    #[test]
+    fn test_should_retry() {
+        let client = Client::unauthenticated();
+        assert!(client.should_retry(&Method::GET, &Error::Timeout));
+        assert!(client.should_retry(&Method::PATCH, &api_error(502)));
+        assert!(!client.should_retry(&Method::POST, &Error::Timeout));
+        assert!(!client.should_retry(&Method::POST, &api_error(502)));
+
+        let limited = |secs| Error::RateLimited {
+            retry_after: Duration::from_secs(secs),
+            global: false,
+        };
+        assert!(client.should_retry(&Method::POST, &limited(1)));
+        assert!(!client.should_retry(&Method::GET, &limited(60)));
+
+        let client = Client::builder().retry_non_idempotent(true).build();
+        assert!(client.should_retry(&Method::POST, &Error::Timeout));
+    }
+
+    #[test]
+    fn test_rate_limited() {
+        let body =
+            br#"{"message": "You are being rate limited.", "retry_after": 1.5, "global": false}"#;
+        let error = rate_limited(body).unwrap();
+        assert!(matches!(
+            error,
+            Error::RateLimited { retry_after, global: false } if retry_after == Duration::from_millis(1500)
+        ));
+        assert!(is_transient(&error));
+        assert!(rate_limited(b"error code: 1015").is_none());
+    }
+
+    #[test]
+    fn test_api_base() {
+        let client = Client::builder()
+            .api_base("http://localhost:3000/api/v9/")
+            .build();
+        assert_eq!(
+            client.url(users::me()),
+            "http://localhost:3000/api/v9/users/@me"
+        );
+        let client = Client::unauthenticated();
+        assert_eq!(client.url(users::me()), users::me());
+    }
+
+    #[test]
+    fn test_require_bot() {
+        assert!(Client::new("token").require_bot().is_ok());
+        let client = Client::builder().bearer_token("token").build();
+        assert!(matches!(
+            client.require_bot(),
+            Err(Error::MissingConfig("bot token"))
+        ));
+        assert!(Client::unauthenticated().require_bot().is_err());
+    }
+
+    #[test]
+    fn test_requests_queued_by_default() {
+        assert_eq!(Client::new("token").queue.limit(), 50);
+        let client = Client::builder().max_concurrent_requests(4).build();
+        assert_eq!(client.queue.limit(), 4);
+    }
+}
@@ -0,0 +1,24 @@
+use const_format::formatcp;
+use reqwest::Method;
+
+use crate::{
+    errors::Result,
+    model::gateway::GatewayBot,
+    rest::{Client, API_ENDPOINT},
+};
+
+const GATEWAY_API_ENDPOINT: &str = formatcp!("{}/gateway", API_ENDPOINT);
+
+/// Create a URL to look up the gateway information of the current bot.
+pub fn bot() -> String {
+    format!("{}/bot", GATEWAY_API_ENDPOINT)
+}
+
+impl Client {
+    /// Fetch the gateway URL, the recommended number of shards and the session start limit of
+    /// the current bot.
+    pub async fn get_gateway_bot(&self) -> Result<GatewayBot> {
+        self.require_bot()?;
+        self.send(self.request(Method::GET, bot())).await
+    }
+}
@@ -0,0 +1,166 @@
+use const_format::formatcp;
+use reqwest::Method;
+
+use crate::{
+    errors::Result,
+    model::{
+        channel::Channel,
+        member::{CreateBan, Member, ModifyMember},
+        role::Role,
+        snowflake::Snowflake,
+    },
+    rest::{Client, API_ENDPOINT},
+};
+
+const GUILD_API_ENDPOINT: &str = formatcp!("{}/guilds", API_ENDPOINT);
+
+/// Create a URL to look up a guild by its ID.
+pub fn id<S: Into<Snowflake>>(guild_id: S) -> String {
+    format!("{}/{}", GUILD_API_ENDPOINT, guild_id.into())
+}
+
+/// Create a URL to look up a guild's roles.
+pub fn roles<S: Into<Snowflake>>(guild_id: S) -> String {
+    format!("{}/roles", id(guild_id))
+}
+
+/// Create a URL to look up a guild's channels.
+pub fn channels<S: Into<Snowflake>>(guild_id: S) -> String {
+    format!("{}/channels", id(guild_id))
+}
+
+/// Create a URL to look up a guild's members.
+pub fn members<S: Into<Snowflake>>(guild_id: S) -> String {
+    format!("{}/members", id(guild_id))
+}
+
+/// Create a URL to look up a member of a guild.
+pub fn member<S: Into<Snowflake>>(guild_id: S, user_id: S) -> String {
+    format!("{}/{}", members(guild_id), user_id.into())
+}
+
+/// Create a URL to add or remove a role from a member of a guild.
+pub fn member_role<S: Into<Snowflake>>(guild_id: S, user_id: S, role_id: S) -> String {
+    format!("{}/roles/{}", member(guild_id, user_id), role_id.into())
+}
+
+/// Create a URL to look up a guild's bans.
+pub fn bans<S: Into<Snowflake>>(guild_id: S) -> String {
+    format!("{}/bans", id(guild_id))
+}
+
+/// Create a URL to look up the ban of a user from a guild.
+pub fn ban<S: Into<Snowflake>>(guild_id: S, user_id: S) -> String {
+    format!("{}/{}", bans(guild_id), user_id.into())
+}
+
+impl Client {
+    /// Fetch every role in a guild.
+    pub async fn get_guild_roles<S: Into<Snowflake>>(&self, guild_id: S) -> Result<Vec<Role>> {
+        self.require_bot()?;
+        self.send(self.request(Method::GET, roles(guild_id))).await
+    }
+
+    /// Fetch every channel in a guild, not including threads.
+    pub async fn get_guild_channels<S: Into<Snowflake>>(
+        &self,
+        guild_id: S,
+    ) -> Result<Vec<Channel>> {
+        self.require_bot()?;
+        self.send(self.request(Method::GET, channels(guild_id)))
+            .await
+    }
+
+    /// Fetch a member of a guild.
+    pub async fn get_guild_member<S: Into<Snowflake>>(
+        &self,
+        guild_id: S,
+        user_id: S,
+    ) -> Result<Member> {
+        self.require_bot()?;
+        self.send(self.request(Method::GET, member(guild_id, user_id)))
+            .await
+    }
+
+    /// Change a member of a guild, such as their nickname or timeout, returning the updated
+    /// member.
+    pub async fn modify_guild_member<S: Into<Snowflake>>(
+        &self,
+        guild_id: S,
+        user_id: S,
+        changes: &ModifyMember,
+    ) -> Result<Member> {
+        self.require_bot()?;
+        self.send(
+            self.request(Method::PATCH, member(guild_id, user_id))
+                .json(changes),
+        )
+        .await
+    }
+
+    /// Give a role to a member of a guild.
+    pub async fn add_guild_member_role<S: Into<Snowflake>>(
+        &self,
+        guild_id: S,
+        user_id: S,
+        role_id: S,
+    ) -> Result<()> {
+        self.require_bot()?;
+        self.send_empty(self.request(Method::PUT, member_role(guild_id, user_id, role_id)))
+            .await?;
+        Ok(())
+    }
+
+    /// Take a role away from a member of a guild.
+    pub async fn remove_guild_member_role<S: Into<Snowflake>>(
+        &self,
+        guild_id: S,
+        user_id: S,
+        role_id: S,
+    ) -> Result<()> {
+        self.require_bot()?;
+        self.send_empty(self.request(Method::DELETE, member_role(guild_id, user_id, role_id)))
+            .await?;
+        Ok(())
+    }
+
+    /// Kick a member from a guild.
+    pub async fn remove_guild_member<S: Into<Snowflake>>(
+        &self,
+        guild_id: S,
+        user_id: S,
+    ) -> Result<()> {
+        self.require_bot()?;
+        self.send_empty(self.request(Method::DELETE, member(guild_id, user_id)))
+            .await?;
+        Ok(())
+    }
+
+    /// Ban a user from a guild, kicking them if they are a member.
+    pub async fn create_guild_ban<S: Into<Snowflake>>(
+        &self,
+        guild_id: S,
+        user_id: S,
+        ban: &CreateBan,
+    ) -> Result<()> {
+        self.require_bot()?;
+        self.send_empty(
+            self.request(Method::PUT, self::ban(guild_id, user_id))
+                .json(ban),
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Lift the ban of a user from a guild.
+    pub async fn remove_guild_ban<S: Into<Snowflake>>(
+        &self,
+        guild_id: S,
+        user_id: S,
+    ) -> Result<()> {
+        self.require_bot()?;
+        self.send_empty(self.request(Method::DELETE, self::ban(guild_id, user_id)))
+            .await?;
+        Ok(())
+    }
+}
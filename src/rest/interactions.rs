@@ -0,0 +1,123 @@
+use const_format::formatcp;
+use reqwest::Method;
+use serde::Serialize;
+
+use crate::{
+    errors::Result,
+    model::{
+        interaction::{InteractionCallbackType, InteractionResponse},
+        message::{CreateMessage, EditMessage, Message},
+        modal::Modal,
+        snowflake::Snowflake,
+    },
+    rest::{multipart, Client, API_ENDPOINT},
+};
+
+const INTERACTION_API_ENDPOINT: &str = formatcp!("{}/interactions", API_ENDPOINT);
+const WEBHOOK_API_ENDPOINT: &str = formatcp!("{}/webhooks", API_ENDPOINT);
+
+/// Create a URL to respond to an interaction.
+pub fn callback<S: Into<Snowflake>>(interaction_id: S, token: &str) -> String {
+    format!(
+        "{}/{}/{}/callback",
+        INTERACTION_API_ENDPOINT,
+        interaction_id.into(),
+        token
+    )
+}
+
+/// Create a URL to look up the message sent in response to an interaction.
+pub fn original_response<S: Into<Snowflake>>(application_id: S, token: &str) -> String {
+    format!(
+        "{}/{}/{}/messages/@original",
+        WEBHOOK_API_ENDPOINT,
+        application_id.into(),
+        token
+    )
+}
+
+/// Create a URL to send followup messages to an interaction.
+pub fn followups<S: Into<Snowflake>>(application_id: S, token: &str) -> String {
+    format!(
+        "{}/{}/{}",
+        WEBHOOK_API_ENDPOINT,
+        application_id.into(),
+        token
+    )
+}
+
+impl Client {
+    /// Send the initial response to an interaction.
+    pub async fn create_interaction_response<S: Into<Snowflake>>(
+        &self,
+        interaction_id: S,
+        token: &str,
+        response: &InteractionResponse,
+    ) -> Result<()> {
+        let request = self.request(Method::POST, callback(interaction_id, token));
+        let files = response.data.as_ref().map_or(&[][..], |data| &data.files);
+        self.send_empty(multipart::body(request, response, files)?)
+            .await?;
+        Ok(())
+    }
+
+    /// Respond to an interaction with a modal, as its initial response.
+    pub async fn create_modal_response<S: Into<Snowflake>>(
+        &self,
+        interaction_id: S,
+        token: &str,
+        modal: &Modal,
+    ) -> Result<()> {
+        #[derive(Serialize)]
+        struct ModalResponse<'a> {
+            #[serde(rename = "type")]
+            ty: InteractionCallbackType,
+            data: &'a Modal,
+        }
+
+        self.send_empty(
+            self.request(Method::POST, callback(interaction_id, token))
+                .json(&ModalResponse {
+                    ty: InteractionCallbackType::Modal,
+                    data: modal,
+                }),
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Fetch the message sent in response to an interaction.
+    pub async fn get_original_interaction_response<S: Into<Snowflake>>(
+        &self,
+        application_id: S,
+        token: &str,
+    ) -> Result<Message> {
+        self.send(self.request(Method::GET, original_response(application_id, token)))
+            .await
+    }
+
+    /// Edit the message sent in response to an interaction, returning the edited message.
+    pub async fn edit_original_interaction_response<S: Into<Snowflake>>(
+        &self,
+        application_id: S,
+        token: &str,
+        changes: &EditMessage,
+    ) -> Result<Message> {
+        let request = self.request(Method::PATCH, original_response(application_id, token));
+        self.send(multipart::body(request, changes, &changes.files)?)
+            .await
+    }
+
+    /// Send a followup message to an interaction, returning the sent message. Followups can
+    /// be sent for 15 minutes after the interaction was received.
+    pub async fn create_followup_message<S: Into<Snowflake>>(
+        &self,
+        application_id: S,
+        token: &str,
+        message: &CreateMessage,
+    ) -> Result<Message> {
+        let request = self.request(Method::POST, followups(application_id, token));
+        self.send(multipart::body(request, message, &message.files)?)
+            .await
+    }
+}
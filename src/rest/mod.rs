@@ -1,6 +1,20 @@
 //! Defines REST API endpoints for the Discord REST API.
+mod auth;
+pub use auth::AuthToken;
+mod client;
+pub(crate) use client::unsent;
+pub use client::{is_transient, Client, ClientBuilder};
+mod multipart;
+mod queue;
+
 pub mod applications;
+pub mod channels;
+pub mod gateway;
+pub mod guilds;
+pub mod interactions;
+pub mod monetization;
+pub mod oauth2;
 pub mod users;
 
 /// The root-level API endpoint.
-pub(crate) const API_ENDPOINT: &'static str = "https://discordapp.com/api/v9";
+pub(crate) const API_ENDPOINT: &str = "https://discordapp.com/api/v9";
@@ -0,0 +1,133 @@
+use reqwest::Method;
+
+use crate::{
+    errors::Result,
+    model::{
+        monetization::{CreateTestEntitlement, Entitlement, EntitlementQuery, Sku},
+        snowflake::Snowflake,
+    },
+    rest::{applications, Client},
+};
+
+/// Create a URL to look up an application's SKUs.
+pub fn skus<S: Into<Snowflake>>(application_id: S) -> String {
+    format!("{}/skus", applications::id(application_id))
+}
+
+/// Create a URL to look up an application's entitlements.
+pub fn entitlements<S: Into<Snowflake>>(application_id: S) -> String {
+    format!("{}/entitlements", applications::id(application_id))
+}
+
+/// Create a URL to look up an entitlement by its ID.
+pub fn entitlement<A, E>(application_id: A, entitlement_id: E) -> String
+where
+    A: Into<Snowflake>,
+    E: Into<Snowflake>,
+{
+    format!("{}/{}", entitlements(application_id), entitlement_id.into())
+}
+
+/// Create a URL to mark a consumable entitlement as consumed.
+pub fn consume_entitlement<A, E>(application_id: A, entitlement_id: E) -> String
+where
+    A: Into<Snowflake>,
+    E: Into<Snowflake>,
+{
+    format!("{}/consume", entitlement(application_id, entitlement_id))
+}
+
+impl Client {
+    /// Fetch all SKUs for an application.
+    pub async fn get_skus<S: Into<Snowflake>>(&self, application_id: S) -> Result<Vec<Sku>> {
+        self.send(self.request(Method::GET, skus(application_id)))
+            .await
+    }
+
+    /// Fetch the entitlements for an application matching the given query.
+    pub async fn get_entitlements<S: Into<Snowflake>>(
+        &self,
+        application_id: S,
+        query: &EntitlementQuery,
+    ) -> Result<Vec<Entitlement>> {
+        self.send(
+            self.request(Method::GET, entitlements(application_id))
+                .query(query),
+        )
+        .await
+    }
+
+    /// Fetch a single entitlement by its ID.
+    pub async fn get_entitlement<A, E>(
+        &self,
+        application_id: A,
+        entitlement_id: E,
+    ) -> Result<Entitlement>
+    where
+        A: Into<Snowflake>,
+        E: Into<Snowflake>,
+    {
+        self.send(self.request(Method::GET, entitlement(application_id, entitlement_id)))
+            .await
+    }
+
+    /// Mark a one-time purchase consumable entitlement as consumed.
+    pub async fn consume_entitlement<A, E>(
+        &self,
+        application_id: A,
+        entitlement_id: E,
+    ) -> Result<()>
+    where
+        A: Into<Snowflake>,
+        E: Into<Snowflake>,
+    {
+        self.send_empty(self.request(
+            Method::POST,
+            consume_entitlement(application_id, entitlement_id),
+        ))
+        .await?;
+        Ok(())
+    }
+
+    /// Create a test entitlement to a SKU for a given guild or user.
+    pub async fn create_test_entitlement<S: Into<Snowflake>>(
+        &self,
+        application_id: S,
+        entitlement: &CreateTestEntitlement,
+    ) -> Result<Entitlement> {
+        self.send(
+            self.request(Method::POST, entitlements(application_id))
+                .json(entitlement),
+        )
+        .await
+    }
+
+    /// Delete a currently-active test entitlement.
+    pub async fn delete_test_entitlement<A, E>(
+        &self,
+        application_id: A,
+        entitlement_id: E,
+    ) -> Result<()>
+    where
+        A: Into<Snowflake>,
+        E: Into<Snowflake>,
+    {
+        self.send_empty(self.request(Method::DELETE, entitlement(application_id, entitlement_id)))
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::snowflake::Snowflake;
+
+    #[test]
+    fn test_entitlement_ids_of_different_types() {
+        let entitlement_id = Snowflake::from(940565712559304736);
+        assert_eq!(
+            super::consume_entitlement(940565712559304735, entitlement_id),
+            "https://discordapp.com/api/v9/applications/940565712559304735/entitlements/940565712559304736/consume"
+        );
+    }
+}
@@ -0,0 +1,164 @@
+//! Encodes the bodies of requests uploading files, which Discord takes as
+//! `multipart/form-data`.
+use std::io::Write;
+
+use reqwest::{header, RequestBuilder};
+use serde::Serialize;
+
+use crate::{errors::Result, model::message::FileUpload};
+
+/// Set the body of a request to a JSON payload, along with the files to upload if there are
+/// any. Requests uploading files are sent as a form, with the payload in its `payload_json`
+/// field and each file in a `files[n]` field.
+///
+/// The form is encoded up front rather than streamed, so that the request can be retried.
+pub(crate) fn body<T: Serialize>(
+    request: RequestBuilder,
+    payload: &T,
+    files: &[FileUpload],
+) -> Result<RequestBuilder> {
+    if files.is_empty() {
+        return Ok(request.json(payload));
+    }
+    let payload = serde_json::to_vec(payload)?;
+    let boundary = boundary(&payload, files);
+    let mut body = Vec::new();
+    part(
+        &mut body,
+        &boundary,
+        "payload_json",
+        None,
+        "application/json",
+        &payload,
+    );
+    for (index, file) in files.iter().enumerate() {
+        part(
+            &mut body,
+            &boundary,
+            &format!("files[{}]", index),
+            Some(&file.filename),
+            "application/octet-stream",
+            &file.data,
+        );
+    }
+    let _ = write!(body, "--{}--\r\n", boundary);
+    let content_type = format!("multipart/form-data; boundary={}", boundary);
+    Ok(request
+        .header(header::CONTENT_TYPE, content_type)
+        .body(body))
+}
+
+/// Returns a boundary between the fields of a form that appears in none of them.
+fn boundary(payload: &[u8], files: &[FileUpload]) -> String {
+    (0..)
+        .map(|n: u32| format!("slash-boundary-{}", n))
+        .find(|boundary| {
+            let contains = |data: &[u8]| {
+                data.windows(boundary.len())
+                    .any(|window| window == boundary.as_bytes())
+            };
+            !contains(payload)
+                && !files
+                    .iter()
+                    .any(|file| contains(file.filename.as_bytes()) || contains(&file.data))
+        })
+        .expect("every boundary appears in the form")
+}
+
+/// Append a field to a form.
+fn part(
+    body: &mut Vec<u8>,
+    boundary: &str,
+    name: &str,
+    filename: Option<&str>,
+    content_type: &str,
+    data: &[u8],
+) {
+    // Writing to a `Vec` can't fail.
+    let _ = write!(
+        body,
+        "--{}\r\nContent-Disposition: form-data; name=\"{}\"",
+        boundary, name
+    );
+    if let Some(filename) = filename {
+        // Quotes and line breaks would end the header, so are percent-encoded like browsers do.
+        let filename = filename
+            .replace('"', "%22")
+            .replace('\r', "%0D")
+            .replace('\n', "%0A");
+        let _ = write!(body, "; filename=\"{}\"", filename);
+    }
+    let _ = write!(body, "\r\nContent-Type: {}\r\n\r\n", content_type);
+    body.extend_from_slice(data);
+    body.extend_from_slice(b"\r\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::{header, Method};
+    use serde_json::json;
+
+    use super::body;
+    use crate::{
+        model::message::FileUpload,
+        rest::{channels, Client},
+    };
+
+    /// Returns the content type and body of a request built with the given files.
+    fn encode(files: &[FileUpload]) -> (String, String) {
+        let client = Client::new("token");
+        let request = client.request(Method::POST, channels::messages(1));
+        let request = body(request, &json!({ "content": "hi" }), files)
+            .unwrap()
+            .build()
+            .unwrap();
+        let content_type = request.headers()[header::CONTENT_TYPE].to_str().unwrap();
+        let body = request.body().unwrap().as_bytes().unwrap();
+        (
+            content_type.to_string(),
+            String::from_utf8(body.to_vec()).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_json_without_files() {
+        let (content_type, body) = encode(&[]);
+        assert_eq!(content_type, "application/json");
+        assert_eq!(body, r#"{"content":"hi"}"#);
+    }
+
+    #[test]
+    fn test_multipart_with_files() {
+        let file = |filename: &str, data: &str| FileUpload {
+            filename: filename.to_string(),
+            data: data.as_bytes().to_vec(),
+        };
+        let (content_type, body) = encode(&[
+            file("scores.csv", "name,score"),
+            file("a\"b.txt", "slash-boundary-0"),
+        ]);
+        assert_eq!(
+            content_type,
+            "multipart/form-data; boundary=slash-boundary-1"
+        );
+        assert_eq!(
+            body,
+            "--slash-boundary-1\r\n\
+             Content-Disposition: form-data; name=\"payload_json\"\r\n\
+             Content-Type: application/json\r\n\
+             \r\n\
+             {\"content\":\"hi\"}\r\n\
+             --slash-boundary-1\r\n\
+             Content-Disposition: form-data; name=\"files[0]\"; filename=\"scores.csv\"\r\n\
+             Content-Type: application/octet-stream\r\n\
+             \r\n\
+             name,score\r\n\
+             --slash-boundary-1\r\n\
+             Content-Disposition: form-data; name=\"files[1]\"; filename=\"a%22b.txt\"\r\n\
+             Content-Type: application/octet-stream\r\n\
+             \r\n\
+             slash-boundary-0\r\n\
+             --slash-boundary-1--\r\n"
+        );
+    }
+}
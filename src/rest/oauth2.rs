@@ -0,0 +1,47 @@
+use const_format::formatcp;
+use reqwest::Method;
+use serde::Serialize;
+
+use crate::{
+    errors::Result,
+    model::oauth2::AccessToken,
+    rest::{Client, API_ENDPOINT},
+};
+
+const OAUTH2_API_ENDPOINT: &str = formatcp!("{}/oauth2", API_ENDPOINT);
+
+/// Create a URL to exchange credentials for an access token.
+pub fn token() -> String {
+    format!("{}/token", OAUTH2_API_ENDPOINT)
+}
+
+impl Client {
+    /// Exchange an application's client ID and secret for an access token with the given
+    /// scopes, authorized as the application's owner.
+    ///
+    /// With the [`APPLICATIONS_COMMANDS_UPDATE`](crate::model::oauth2::APPLICATIONS_COMMANDS_UPDATE)
+    /// scope, the token can register the application's commands through a client built with
+    /// [`ClientBuilder::bearer_token`](super::ClientBuilder::bearer_token), without a bot token.
+    pub async fn client_credentials_grant<S: AsRef<str>>(
+        &self,
+        client_id: S,
+        client_secret: S,
+        scopes: &[&str],
+    ) -> Result<AccessToken> {
+        #[derive(Serialize)]
+        struct Grant {
+            grant_type: &'static str,
+            scope: String,
+        }
+
+        self.send(
+            self.unauthenticated_request(Method::POST, token())
+                .basic_auth(client_id.as_ref(), Some(client_secret.as_ref()))
+                .form(&Grant {
+                    grant_type: "client_credentials",
+                    scope: scopes.join(" "),
+                }),
+        )
+        .await
+    }
+}
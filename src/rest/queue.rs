@@ -0,0 +1,191 @@
+//! Queues requests once too many are in flight, so responses to interactions aren't held up
+//! by background requests.
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+use reqwest::Url;
+use tokio::sync::oneshot;
+
+/// How urgently a request must be sent, highest last.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum Priority {
+    /// Requests that can wait, such as registering commands.
+    Background = 0,
+    /// Responses and followups to interactions, which must be sent within 3 seconds of
+    /// receiving them.
+    Interaction = 1,
+}
+
+impl Priority {
+    /// Returns the priority of a request to the given URL: interaction callbacks and webhooks
+    /// go first.
+    pub(crate) fn of(url: &Url) -> Self {
+        let interaction = url
+            .path_segments()
+            .into_iter()
+            .flatten()
+            .any(|segment| segment == "interactions" || segment == "webhooks");
+        if interaction {
+            Priority::Interaction
+        } else {
+            Priority::Background
+        }
+    }
+}
+
+/// A limit on the requests in flight, handing the slots freed up to the waiting requests of
+/// the highest priority first, in the order they started waiting.
+#[derive(Debug)]
+pub(crate) struct RequestQueue {
+    limit: usize,
+    state: Mutex<State>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    in_flight: usize,
+    /// The requests waiting for a slot, by priority.
+    waiting: [VecDeque<oneshot::Sender<()>>; 2],
+}
+
+impl RequestQueue {
+    pub(crate) fn new(limit: usize) -> Self {
+        Self {
+            limit: limit.max(1),
+            state: Mutex::default(),
+        }
+    }
+
+    /// Returns how many requests can be in flight at once.
+    pub(crate) fn limit(&self) -> usize {
+        self.limit
+    }
+
+    fn state(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    /// Wait for a slot to send a request in, which is freed once the slot is dropped.
+    pub(crate) async fn acquire(self: &Arc<Self>, priority: Priority) -> Slot {
+        let receiver = {
+            let mut state = self.state();
+            if state.in_flight < self.limit {
+                state.in_flight += 1;
+                return Slot {
+                    queue: self.clone(),
+                };
+            }
+            let (sender, receiver) = oneshot::channel();
+            state.waiting[priority as usize].push_back(sender);
+            receiver
+        };
+        let mut waiting = Waiting {
+            receiver: Some(receiver),
+            queue: self.clone(),
+        };
+        // Slots are handed over by the requests freeing them, which never drop the sender
+        // without sending.
+        let _ = waiting.receiver.as_mut().expect("still waiting").await;
+        waiting.receiver = None;
+        Slot {
+            queue: self.clone(),
+        }
+    }
+
+    /// Hand a freed slot to the next waiting request, or leave it free if there is none.
+    fn release(&self) {
+        let mut state = self.state();
+        for waiting in state.waiting.iter_mut().rev() {
+            while let Some(sender) = waiting.pop_front() {
+                // Requests that stopped waiting are skipped.
+                if sender.send(()).is_ok() {
+                    return;
+                }
+            }
+        }
+        state.in_flight -= 1;
+    }
+}
+
+/// A request waiting for a slot, which passes the slot on if it stops waiting right after
+/// being handed one.
+struct Waiting {
+    receiver: Option<oneshot::Receiver<()>>,
+    queue: Arc<RequestQueue>,
+}
+
+impl Drop for Waiting {
+    fn drop(&mut self) {
+        if let Some(mut receiver) = self.receiver.take() {
+            receiver.close();
+            if receiver.try_recv().is_ok() {
+                self.queue.release();
+            }
+        }
+    }
+}
+
+/// A slot in the queue a request is sent in, freed when dropped.
+#[derive(Debug)]
+pub(crate) struct Slot {
+    queue: Arc<RequestQueue>,
+}
+
+impl Drop for Slot {
+    fn drop(&mut self) {
+        self.queue.release();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::{Priority, RequestQueue};
+
+    #[test]
+    fn test_priority_of() {
+        let callback = "https://discord.com/api/v10/interactions/1/token/callback";
+        assert_eq!(
+            Priority::of(&callback.parse().unwrap()),
+            Priority::Interaction
+        );
+        let commands = "https://discord.com/api/v10/applications/1/commands";
+        assert_eq!(
+            Priority::of(&commands.parse().unwrap()),
+            Priority::Background
+        );
+    }
+
+    #[tokio::test]
+    async fn test_interactions_go_first() {
+        let queue = Arc::new(RequestQueue::new(1));
+        let slot = queue.acquire(Priority::Background).await;
+
+        let order = Arc::new(Mutex::new(vec![]));
+        let wait = |priority| {
+            let queue = queue.clone();
+            let order = order.clone();
+            tokio::spawn(async move {
+                let _slot = queue.acquire(priority).await;
+                order.lock().unwrap().push(priority);
+            })
+        };
+        let background = wait(Priority::Background);
+        let interaction = wait(Priority::Interaction);
+        while queue.state().waiting.iter().map(|w| w.len()).sum::<usize>() < 2 {
+            tokio::task::yield_now().await;
+        }
+
+        drop(slot);
+        background.await.unwrap();
+        interaction.await.unwrap();
+        assert_eq!(
+            *order.lock().unwrap(),
+            [Priority::Interaction, Priority::Background]
+        );
+        assert_eq!(queue.state().in_flight, 0);
+    }
+}
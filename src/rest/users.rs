@@ -1,6 +1,12 @@
 use const_format::formatcp;
+use reqwest::Method;
+use serde::Serialize;
 
-use crate::{model::snowflake::Snowflake, rest::API_ENDPOINT};
+use crate::{
+    errors::Result,
+    model::{channel::Channel, snowflake::Snowflake, user::User},
+    rest::{Client, API_ENDPOINT},
+};
 
 const USER_API_ENDPOINT: &str = formatcp!("{}/users", API_ENDPOINT);
 
@@ -13,3 +19,31 @@ pub fn me() -> String {
 pub fn id<S: Into<Snowflake>>(id: S) -> String {
     format!("{}/{}", USER_API_ENDPOINT, id.into())
 }
+
+/// Create a URL to open direct messages from the current account.
+pub fn my_channels() -> String {
+    format!("{}/channels", me())
+}
+
+impl Client {
+    /// Fetch a user's profile.
+    pub async fn get_user<S: Into<Snowflake>>(&self, user_id: S) -> Result<User> {
+        self.require_bot()?;
+        self.send(self.request(Method::GET, id(user_id))).await
+    }
+
+    /// Open a direct message channel with a user, returning the existing one if there is one.
+    pub async fn create_dm<S: Into<Snowflake>>(&self, user_id: S) -> Result<Channel> {
+        self.require_bot()?;
+
+        #[derive(Serialize)]
+        struct CreateDm {
+            recipient_id: Snowflake,
+        }
+
+        self.send(self.request(Method::POST, my_channels()).json(&CreateDm {
+            recipient_id: user_id.into(),
+        }))
+        .await
+    }
+}
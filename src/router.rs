@@ -0,0 +1,261 @@
+//! Defines the `Router`, holding the commands a client dispatches interactions to.
+use std::{
+    collections::BTreeMap,
+    fmt,
+    sync::{RwLock, RwLockReadGuard},
+};
+
+use crate::{
+    command::{validate, CommandCollector, CommandSet, ValidationError},
+    dispatch::Handler,
+    filter::Filter,
+    model::{
+        command::{ApplicationCommand, ApplicationCommandType},
+        snowflake::Snowflake,
+    },
+    reject::Rejection,
+};
+
+/// The commands a [`Client`](crate::Client) dispatches interactions to, which can be swapped
+/// while it runs with [`Client::replace_commands`](crate::Client::replace_commands).
+///
+/// # Example
+///
+/// ```
+/// use slash::Router;
+///
+/// let router = Router::new()
+///     .command(slash::command("ping").description("Ping the bot"))
+///     .guild_command(940565712559304736, slash::command("ban").description("Ban a user"));
+/// ```
+#[derive(Default)]
+pub struct Router {
+    handlers: Vec<Box<dyn Handler>>,
+    guild_handlers: BTreeMap<Snowflake, Vec<Box<dyn Handler>>>,
+    /// The commands as Discord registered them, with the ids and versions it assigned.
+    registered: RwLock<Vec<ApplicationCommand>>,
+}
+
+impl Router {
+    /// Create a router without any commands.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a command, or any filter built from commands, to dispatch interactions to.
+    ///
+    /// Filters are tried in the order they were added, until one accepts the interaction.
+    pub fn command<F>(mut self, filter: F) -> Self
+    where
+        F: Filter + Send + Sync + 'static,
+        F::Future: 'static,
+        F::Error: Into<Rejection>,
+    {
+        self.handlers.push(Box::new(filter));
+        self
+    }
+
+    /// Add a command, or any filter built from commands, that is only registered to the given
+    /// guild, such as extra commands for an admin guild.
+    ///
+    /// Interactions from the guild are dispatched to its commands before the global ones.
+    pub fn guild_command<S, F>(mut self, guild_id: S, filter: F) -> Self
+    where
+        S: Into<Snowflake>,
+        F: Filter + Send + Sync + 'static,
+        F::Future: 'static,
+        F::Error: Into<Rejection>,
+    {
+        self.guild_handlers
+            .entry(guild_id.into())
+            .or_default()
+            .push(Box::new(filter));
+        self
+    }
+
+    /// Returns the handlers of an interaction from the given guild, in the order to try them.
+    /// Borrowed rather than collected, as this is called for every interaction.
+    pub(crate) fn handlers(
+        &self,
+        guild_id: Option<Snowflake>,
+    ) -> impl Iterator<Item = &dyn Handler> + Send {
+        guild_id
+            .and_then(|guild_id| self.guild_handlers.get(&guild_id))
+            .into_iter()
+            .flatten()
+            .chain(&self.handlers)
+            .map(|handler| &**handler)
+    }
+
+    /// Returns the id Discord assigned to a command once it was registered, or `None` if the
+    /// command isn't registered yet. Chat input commands are looked up before user and
+    /// message commands of the same name.
+    ///
+    /// Commands are known once the client registers them, or finds them already registered.
+    /// Scopes skipped thanks to a
+    /// [`registration_store`](crate::ClientBuilder::registration_store) aren't fetched, so
+    /// their commands stay unknown.
+    pub fn command_id(&self, name: &str) -> Option<Snowflake> {
+        self.registered_command(name)?.id
+    }
+
+    /// Returns the version of a registered command, which Discord updates whenever the
+    /// command changes. See [`command_id`](Self::command_id).
+    pub fn command_version(&self, name: &str) -> Option<Snowflake> {
+        self.registered_command(name)?.version
+    }
+
+    /// Returns a mention of a registered chat input command, which users can click to start
+    /// typing it, such as `</ban:1234>`. Subcommands and groups are mentioned by their full
+    /// name, such as `"tag create"`. See [`command_id`](Self::command_id).
+    pub fn mention(&self, name: &str) -> Option<String> {
+        let command = name.split(' ').next().unwrap_or(name);
+        let command = self
+            .registered_command(command)
+            .filter(|command| command.ty == ApplicationCommandType::ChatInput)?;
+        Some(format!("</{}:{}>", name, command.id?))
+    }
+
+    /// Returns the registered command of the given name, preferring chat input commands.
+    fn registered_command(&self, name: &str) -> Option<ApplicationCommand> {
+        let registered = self.registered();
+        let mut named = registered.iter().filter(|command| command.name == name);
+        named
+            .clone()
+            .find(|command| command.ty == ApplicationCommandType::ChatInput)
+            .or_else(|| named.next())
+            .cloned()
+    }
+
+    fn registered(&self) -> RwLockReadGuard<'_, Vec<ApplicationCommand>> {
+        self.registered
+            .read()
+            .unwrap_or_else(|err| err.into_inner())
+    }
+
+    /// Replace the commands known to be registered to a scope.
+    pub(crate) fn set_registered(
+        &self,
+        guild_id: Option<Snowflake>,
+        commands: &[ApplicationCommand],
+    ) {
+        let mut registered = self
+            .registered
+            .write()
+            .unwrap_or_else(|err| err.into_inner());
+        registered.retain(|command| command.guild_id != guild_id);
+        registered.extend(commands.iter().map(|command| ApplicationCommand {
+            guild_id,
+            ..command.clone()
+        }));
+    }
+
+    /// Collects and validates the registration payloads of every global command.
+    pub(crate) fn commands(&self) -> Result<Vec<ApplicationCommand>, ValidationError> {
+        let commands = collect_commands(&self.handlers);
+        validate::commands(&commands)?;
+        Ok(commands)
+    }
+
+    /// Collects and validates the commands to register to each scope, registering the global
+    /// commands to the dev guild instead if one is given.
+    pub(crate) fn command_sets(
+        &self,
+        dev_guild_id: Option<Snowflake>,
+    ) -> Result<Vec<CommandSet>, ValidationError> {
+        let mut sets = vec![CommandSet {
+            guild_id: dev_guild_id,
+            commands: collect_commands(&self.handlers),
+        }];
+        for (&guild_id, handlers) in &self.guild_handlers {
+            let commands = collect_commands(handlers);
+            match sets.iter_mut().find(|set| set.guild_id == Some(guild_id)) {
+                Some(set) => set.commands.extend(commands),
+                None => sets.push(CommandSet {
+                    guild_id: Some(guild_id),
+                    commands,
+                }),
+            }
+        }
+        for set in &sets {
+            validate::commands(&set.commands)?;
+        }
+        Ok(sets)
+    }
+}
+
+/// Collects the commands declared by each handler.
+fn collect_commands(handlers: &[Box<dyn Handler>]) -> Vec<ApplicationCommand> {
+    let mut collector = CommandCollector::default();
+    for handler in handlers {
+        handler.meta(&mut collector);
+    }
+    collector.commands
+}
+
+impl fmt::Debug for Router {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Router")
+            .field("handlers", &self.handlers.len())
+            .field("guild_handlers", &self.guild_handlers.len())
+            .field("registered", &self.registered().len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Router;
+    use crate::model::{
+        command::{ApplicationCommand, ApplicationCommandType},
+        snowflake::Snowflake,
+    };
+
+    #[test]
+    fn test_command_sets_per_guild() {
+        let router = Router::new()
+            .command(crate::command("ping").description("Ping the bot"))
+            .guild_command(2, crate::command("ban").description("Ban a user"))
+            .guild_command(3, crate::command("kick").description("Kick a user"));
+        let sets = router.command_sets(Some(3.into())).unwrap();
+        let names = |index: usize| {
+            let set = &sets[index];
+            let names: Vec<_> = set.commands.iter().map(|c| c.name.as_str()).collect();
+            (set.guild_id, names)
+        };
+        assert_eq!(names(0), (Some(Snowflake::from(3)), vec!["ping", "kick"]));
+        assert_eq!(names(1), (Some(Snowflake::from(2)), vec!["ban"]));
+    }
+
+    #[test]
+    fn test_registered_commands() {
+        let router = Router::new();
+        let registered = |id: u64, ty, name| ApplicationCommand {
+            id: Some(id.into()),
+            version: Some((id + 100).into()),
+            ..ApplicationCommand::new(ty, name)
+        };
+        router.set_registered(
+            None,
+            &[
+                registered(1, ApplicationCommandType::User, "tag"),
+                registered(2, ApplicationCommandType::ChatInput, "tag"),
+            ],
+        );
+        router.set_registered(
+            Some(5.into()),
+            &[registered(3, ApplicationCommandType::ChatInput, "ban")],
+        );
+        assert_eq!(router.command_id("tag"), Some(2.into()));
+        assert_eq!(router.command_version("ban"), Some(103.into()));
+        assert_eq!(
+            router.mention("tag create").as_deref(),
+            Some("</tag create:2>")
+        );
+        assert_eq!(router.command_id("kick"), None);
+
+        router.set_registered(None, &[]);
+        assert_eq!(router.command_id("tag"), None);
+        assert_eq!(router.command_id("ban"), Some(3.into()));
+    }
+}
@@ -0,0 +1,52 @@
+//! Abstracts the timers and tasks of the async runtime: tokio's natively, and the JavaScript event loop's
+//! on `wasm32`, where tokio's runtime isn't available.
+use std::{future::Future, time::Duration};
+
+use tracing::Instrument;
+
+/// Wait for the given duration.
+pub(crate) async fn sleep(duration: Duration) {
+    #[cfg(not(target_arch = "wasm32"))]
+    tokio::time::sleep(duration).await;
+    #[cfg(target_arch = "wasm32")]
+    gloo_timers::future::sleep(duration).await;
+}
+
+/// Run a future, giving up on it after the given duration.
+pub(crate) async fn timeout<F: Future>(duration: Duration, future: F) -> Option<F::Output> {
+    tokio::select! {
+        output = future => Some(output),
+        () = sleep(duration) => None,
+    }
+}
+
+/// A boxed timer, which is only `Send` where the runtime's timers are.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) type Timer = futures_util::future::BoxFuture<'static, ()>;
+/// A boxed timer, which is only `Send` where the runtime's timers are.
+#[cfg(target_arch = "wasm32")]
+pub(crate) type Timer = futures_util::future::LocalBoxFuture<'static, ()>;
+
+/// Create a boxed timer firing after the given duration, to store in a struct.
+pub(crate) fn timer(duration: Duration) -> Timer {
+    #[cfg(not(target_arch = "wasm32"))]
+    return futures_util::FutureExt::boxed(sleep(duration));
+    #[cfg(target_arch = "wasm32")]
+    return futures_util::FutureExt::boxed_local(sleep(duration));
+}
+
+/// Run a future in the background, on tokio's runtime natively and on the JavaScript event loop
+/// on `wasm32`. The future stays in the current span, so its events are logged with the
+/// interaction that started it.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn spawn<F: Future<Output = ()> + Send + 'static>(future: F) {
+    tokio::spawn(future.in_current_span());
+}
+
+/// Run a future in the background, on tokio's runtime natively and on the JavaScript event loop
+/// on `wasm32`. The future stays in the current span, so its events are logged with the
+/// interaction that started it.
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn spawn<F: Future<Output = ()> + 'static>(future: F) {
+    wasm_bindgen_futures::spawn_local(future.in_current_span());
+}
@@ -0,0 +1,193 @@
+//! Defines the `InteractionService`, which serves the interactions endpoint as a
+//! [`tower::Service`](tower_service::Service).
+use std::{
+    convert::Infallible,
+    fmt,
+    task::{Context, Poll},
+};
+
+use bytes::Bytes;
+use ed25519_dalek::VerifyingKey;
+use futures_util::future::BoxFuture;
+use http::{header, Request, Response, StatusCode};
+use http_body::Body;
+use http_body_util::{BodyExt, Full};
+use tower_service::Service;
+
+use crate::{
+    client::Client,
+    errors::{Error, Result},
+    http::{self as server, SIGNATURE_HEADER, TIMESTAMP_HEADER},
+};
+
+/// A [`tower::Service`](tower_service::Service) handling requests to the interactions
+/// endpoint, created with [`Client::service`].
+///
+/// Requests are checked against the application's public key, and the interactions they
+/// carry are dispatched to the client's commands in the background, so that they're
+/// acknowledged right away. The service never fails: invalid requests are answered with an
+/// error status.
+#[derive(Clone)]
+pub struct InteractionService {
+    client: Client,
+    key: VerifyingKey,
+}
+
+impl InteractionService {
+    pub(crate) fn new(client: Client) -> Result<Self> {
+        let key = *client
+            .public_key()
+            .ok_or(Error::MissingConfig("public key"))?;
+        Ok(Self { client, key })
+    }
+}
+
+impl<B> Service<Request<B>> for InteractionService
+where
+    B: Body + Send + 'static,
+    B::Data: Send,
+{
+    type Response = Response<Full<Bytes>>;
+    type Error = Infallible;
+    type Future = BoxFuture<'static, Result<Self::Response, Infallible>>;
+
+    fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Infallible>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: Request<B>) -> Self::Future {
+        let this = self.clone();
+        Box::pin(async move {
+            let (parts, body) = request.into_parts();
+            let header = |name| {
+                parts
+                    .headers
+                    .get(name)
+                    .and_then(|value| value.to_str().ok())
+            };
+            let body = match body.collect().await {
+                Ok(body) => body.to_bytes(),
+                Err(_) => return Ok(status(StatusCode::BAD_REQUEST)),
+            };
+            let response = server::handle(
+                &this.client,
+                &this.key,
+                header(SIGNATURE_HEADER),
+                header(TIMESTAMP_HEADER),
+                &body,
+                server::Dispatch::Spawn,
+            )
+            .await;
+            let mut builder = Response::builder().status(response.status);
+            if response.body.is_some() {
+                builder = builder.header(header::CONTENT_TYPE, "application/json");
+            }
+            let body = Full::new(response.body.map(Bytes::from).unwrap_or_default());
+            Ok(builder
+                .body(body)
+                .unwrap_or_else(|_| status(StatusCode::INTERNAL_SERVER_ERROR)))
+        })
+    }
+}
+
+/// Create an empty response with the given status.
+fn status(status: StatusCode) -> Response<Full<Bytes>> {
+    let mut response = Response::new(Full::default());
+    *response.status_mut() = status;
+    response
+}
+
+impl fmt::Debug for InteractionService {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InteractionService")
+            .field("client", &self.client)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, time::Duration};
+
+    use ed25519_dalek::{Signer, SigningKey};
+    use http::{Request, StatusCode};
+    use http_body_util::{BodyExt, Full};
+    use tokio::sync::Notify;
+    use tower_service::Service;
+
+    use super::InteractionService;
+    use crate::{Client, Context, Filter};
+
+    fn service(key: &SigningKey) -> InteractionService {
+        Client::builder()
+            .token("token")
+            .application_id(1234)
+            .public_key(hex::encode(key.verifying_key().as_bytes()))
+            .build()
+            .unwrap()
+            .service()
+            .unwrap()
+    }
+
+    fn request(key: &SigningKey, body: &str) -> Request<Full<bytes::Bytes>> {
+        let timestamp = "1700000000";
+        let signature = key.sign(format!("{}{}", timestamp, body).as_bytes());
+        Request::post("/interactions")
+            .header("x-signature-ed25519", hex::encode(signature.to_bytes()))
+            .header("x-signature-timestamp", timestamp)
+            .body(Full::from(body.to_string()))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_service_answers_ping() {
+        let key = SigningKey::from_bytes(&[7; 32]);
+        let body = r#"{"id":"1","application_id":"1234","type":1,"token":"token"}"#;
+        let response = service(&key).call(request(&key, body)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        assert_eq!(&body[..], br#"{"type":1}"#);
+    }
+
+    #[tokio::test]
+    async fn test_service_acknowledges_before_handling() {
+        let key = SigningKey::from_bytes(&[7; 32]);
+        let (release, done) = (Arc::new(Notify::new()), Arc::new(Notify::new()));
+        let client = {
+            let (release, done) = (release.clone(), done.clone());
+            Client::builder()
+                .public_key(hex::encode(key.verifying_key().as_bytes()))
+                .command(crate::command("wait").then(move |_: Context| {
+                    let (release, done) = (release.clone(), done.clone());
+                    async move {
+                        release.notified().await;
+                        done.notify_one();
+                    }
+                }))
+                .build_webhook()
+                .unwrap()
+        };
+        let body = r#"{"id":"1","application_id":"1234","type":2,"token":"token",
+            "data":{"id":"2","name":"wait","type":1}}"#;
+        let call = client.service().unwrap().call(request(&key, body));
+        let response = tokio::time::timeout(Duration::from_secs(5), call)
+            .await
+            .expect("the request waited for the handler")
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+
+        release.notify_one();
+        tokio::time::timeout(Duration::from_secs(5), done.notified())
+            .await
+            .expect("the interaction was never handled");
+    }
+
+    #[tokio::test]
+    async fn test_service_rejects_bad_signature() {
+        let key = SigningKey::from_bytes(&[7; 32]);
+        let other = SigningKey::from_bytes(&[8; 32]);
+        let body = r#"{"id":"1","application_id":"1234","type":1,"token":"token"}"#;
+        let response = service(&key).call(request(&other, body)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+}
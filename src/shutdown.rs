@@ -0,0 +1,119 @@
+//! Defines the `ShutdownHandle` used to stop a client or gateway connection.
+use std::{sync::Arc, time::Duration};
+
+use tokio::sync::watch;
+
+use crate::runtime;
+
+/// A handle that asks a [`Client`](crate::Client) or gateway connection to shut down.
+///
+/// Handles are cheap to clone, and every clone controls the same client.
+#[derive(Debug, Clone)]
+pub struct ShutdownHandle {
+    signal: Arc<watch::Sender<bool>>,
+}
+
+impl ShutdownHandle {
+    pub(crate) fn new() -> Self {
+        Self {
+            signal: Arc::new(watch::channel(false).0),
+        }
+    }
+
+    /// Start shutting down. Calling this more than once has no further effect.
+    pub fn shutdown(&self) {
+        self.signal.send_replace(true);
+    }
+
+    /// Whether a shutdown was requested.
+    pub fn is_shutdown(&self) -> bool {
+        *self.signal.borrow()
+    }
+
+    /// Wait until a shutdown is requested.
+    pub async fn wait(&self) {
+        // Only fails if the sender is dropped, which can't happen while `self` holds it.
+        let _ = self.signal.subscribe().wait_for(|&shutdown| shutdown).await;
+    }
+
+    /// Sleep for the given duration, waking early if a shutdown is requested.
+    #[cfg(feature = "gateway")]
+    pub(crate) async fn sleep(&self, duration: Duration) {
+        tokio::select! {
+            () = runtime::sleep(duration) => {}
+            () = self.wait() => {}
+        }
+    }
+}
+
+/// Counts the interactions being handled, so that shutting down can wait for them to finish.
+#[derive(Debug)]
+pub(crate) struct InFlight {
+    count: Arc<watch::Sender<usize>>,
+}
+
+impl InFlight {
+    pub(crate) fn new() -> Self {
+        Self {
+            count: Arc::new(watch::channel(0).0),
+        }
+    }
+
+    /// Start tracking a task, until the returned guard is dropped.
+    pub(crate) fn start(&self) -> InFlightGuard {
+        self.count.send_modify(|count| *count += 1);
+        InFlightGuard {
+            count: self.count.clone(),
+        }
+    }
+
+    /// Wait for every tracked task to finish, giving up after `timeout`. Returns the number of
+    /// tasks still running.
+    pub(crate) async fn drain(&self, timeout: Duration) -> usize {
+        let mut count = self.count.subscribe();
+        let _ = runtime::timeout(timeout, count.wait_for(|&count| count == 0)).await;
+        let remaining = *count.borrow();
+        remaining
+    }
+}
+
+/// Marks a task tracked by [`InFlight`] as finished when dropped, even if it was cancelled.
+/// It can be moved into a spawned task, to track it from before it starts.
+pub(crate) struct InFlightGuard {
+    count: Arc<watch::Sender<usize>>,
+}
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        self.count.send_modify(|count| *count -= 1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{InFlight, ShutdownHandle};
+
+    #[tokio::test]
+    async fn test_drain_waits_for_guards() {
+        let in_flight = InFlight::new();
+        let guard = in_flight.start();
+        assert_eq!(in_flight.drain(Duration::from_millis(10)).await, 1);
+
+        let drain = in_flight.drain(Duration::from_secs(5));
+        let release = async move { drop(guard) };
+        let (remaining, ()) = tokio::join!(drain, release);
+        assert_eq!(remaining, 0);
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_wakes_waiters() {
+        let shutdown = ShutdownHandle::new();
+        let waiter = shutdown.clone();
+        let wait = tokio::spawn(async move { waiter.wait().await });
+        shutdown.shutdown();
+        wait.await.unwrap();
+        assert!(shutdown.is_shutdown());
+    }
+}
@@ -0,0 +1,38 @@
+//! Fixtures shared by the tests of the crate.
+use std::sync::Arc;
+
+use serde_json::{json, Value};
+
+use crate::{context::Context, model::interaction::Interaction, rest};
+
+/// Returns an application command interaction, with the given fields added to or replacing
+/// the ones every interaction has.
+pub(crate) fn interaction(fields: Value) -> Interaction {
+    let mut interaction = json!({
+        "id": "940565712559304734",
+        "application_id": "940565712559304735",
+        "type": 2,
+        "token": "token",
+    });
+    if let (Value::Object(interaction), Value::Object(fields)) = (&mut interaction, fields) {
+        interaction.extend(fields);
+    }
+    serde_json::from_value(interaction).unwrap()
+}
+
+/// Returns the context of an [`interaction`] with the given fields.
+pub(crate) fn context(fields: Value) -> Context {
+    Context::new(interaction(fields), Arc::new(rest::Client::new("token")))
+}
+
+/// Returns the context of a `CHAT_INPUT` command of the given name, given the options.
+pub(crate) fn command(name: &str, options: Value) -> Context {
+    context(json!({
+        "data": {
+            "id": "940565712559304736",
+            "name": name,
+            "type": 1,
+            "options": options
+        }
+    }))
+}
@@ -0,0 +1,97 @@
+//! Integrates the interactions endpoint with [`warp`](::warp).
+use ::warp::{reply::Response, Rejection};
+
+use crate::{
+    client::Client,
+    errors::{Error, Result},
+    filter::Filter,
+    reject::Rejection as SlashRejection,
+};
+
+/// Create a warp filter serving the interactions endpoint, which verifies that `POST` requests
+/// were sent by Discord and dispatches their interactions to `filter`.
+///
+/// Handlers can respond to their interaction, but no bot token is set to make other REST
+/// requests with. To do so, configure a [`Client`] and use [`client`] instead.
+///
+/// # Example
+///
+/// ```no_run
+/// use slash::{Context, Filter};
+/// use warp::Filter as _;
+///
+/// # async fn run() -> slash::Result<()> {
+/// let ping = slash::command("ping")
+///     .description("Test the bot's latency.")
+///     .then(|context: Context| async move { context.reply("pong!").await });
+///
+/// let interactions =
+///     warp::path("interactions").and(slash::warp::interactions(ping, "<public key>")?);
+/// warp::serve(interactions).run(([0, 0, 0, 0], 8080)).await;
+/// # Ok(())
+/// # }
+/// ```
+pub fn interactions<F>(
+    filter: F,
+    public_key: &str,
+) -> Result<impl ::warp::Filter<Extract = (Response,), Error = Rejection> + Clone>
+where
+    F: Filter + Send + Sync + 'static,
+    F::Future: 'static,
+    F::Error: Into<SlashRejection>,
+{
+    let client = Client::builder()
+        .command(filter)
+        .public_key(public_key)
+        .build_webhook()?;
+    self::client(&client)
+}
+
+/// Create a warp filter serving the interactions endpoint for a [`Client`], which must have
+/// been given its public key.
+pub fn client(
+    client: &Client,
+) -> Result<impl ::warp::Filter<Extract = (Response,), Error = Rejection> + Clone> {
+    let key = *client
+        .public_key()
+        .ok_or(Error::MissingConfig("public key"))?;
+    Ok(crate::http::server::route(client.clone(), key))
+}
+
+#[cfg(test)]
+mod tests {
+    use ed25519_dalek::{Signer, SigningKey};
+    use warp::http::StatusCode;
+
+    use crate::{Context, Filter};
+
+    #[tokio::test]
+    async fn test_interactions_filter_verifies_requests() {
+        let key = SigningKey::from_bytes(&[7; 32]);
+        let ping = crate::command("ping").map(|_: Context| ());
+        let filter =
+            super::interactions(ping, &hex::encode(key.verifying_key().as_bytes())).unwrap();
+
+        let body = r#"{"id":"1","application_id":"1234","type":1,"token":"token"}"#;
+        let timestamp = "1700000000";
+        let signature = key.sign(format!("{}{}", timestamp, body).as_bytes());
+        let response = warp::test::request()
+            .method("POST")
+            .header("x-signature-ed25519", hex::encode(signature.to_bytes()))
+            .header("x-signature-timestamp", timestamp)
+            .body(body)
+            .reply(&filter)
+            .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.body().as_ref(), br#"{"type":1}"#);
+
+        let response = warp::test::request()
+            .method("POST")
+            .header("x-signature-ed25519", "00")
+            .header("x-signature-timestamp", timestamp)
+            .body(body)
+            .reply(&filter)
+            .await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+}